@@ -7,7 +7,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 REMEDIATION_ID="${1:-}"
 
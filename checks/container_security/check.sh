@@ -10,7 +10,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -22,12 +22,12 @@ done
 
 # ---------- Check if OpenClaw container is running ----------
 if ! command -v docker &>/dev/null; then
-    emit_info "Docker not installed — skipping container audit"
+    emit_skipped "Docker not installed — container audit skipped" "Container Audit"
     exit 0
 fi
 
 if ! docker ps --format '{{.Names}}' 2>/dev/null | grep -q "^openclaw$"; then
-    emit_info "OpenClaw container is not running — skipping container audit"
+    emit_skipped "OpenClaw container is not running — container audit skipped" "Container Audit"
     exit 0
 fi
 
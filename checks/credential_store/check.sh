@@ -8,61 +8,12 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
-
-MODE="scan"
-REMEDIATION_ID=""
-while [[ $# -gt 0 ]]; do
-    case "$1" in
-        --mode) MODE="$2"; shift 2 ;;
-        --remediation-id) REMEDIATION_ID="$2"; shift 2 ;;
-        *) shift ;;
-    esac
-done
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 openclaw_dir="$HOME/.openclaw"
 
 if [ ! -d "$openclaw_dir" ]; then
-    emit_info "No ~/.openclaw directory found — skipping credential store checks"
-    exit 0
-fi
-
-# --- Remediation handler ---
-if [ -n "$REMEDIATION_ID" ]; then
-    case "$REMEDIATION_ID" in
-        fix_credentials_perms)
-            creds_dir="$openclaw_dir/credentials"
-            if [ -d "$creds_dir" ]; then
-                chmod 700 "$creds_dir"
-                find "$creds_dir" -type f -exec chmod 600 {} \;
-                emit_pass "Credentials directory and files set to 700/600" "Credential Directory"
-            else
-                emit_fail "Credentials directory not found" "Credential Directory"
-            fi
-            ;;
-        fix_oauth_perms)
-            find "$openclaw_dir/agents" -name "auth-profiles.json" -exec chmod 600 {} \; 2>/dev/null
-            emit_pass "OAuth profile files set to 600" "OAuth Profiles"
-            ;;
-        fix_sessions_perms)
-            find "$openclaw_dir/agents" -name "sessions" -type d -exec chmod 700 {} \; 2>/dev/null
-            find "$openclaw_dir/agents" -path "*/sessions/*.jsonl" -exec chmod 600 {} \; 2>/dev/null
-            emit_pass "Session directories and logs set to 700/600" "Session Transcripts"
-            ;;
-        fix_log_perms)
-            log_dir="/tmp/openclaw"
-            if [ -d "$log_dir" ]; then
-                chmod 700 "$log_dir"
-                find "$log_dir" -name "*.log" -exec chmod 600 {} \; 2>/dev/null
-                emit_pass "Log directory and files set to 700/600" "Log Files"
-            else
-                emit_fail "Log directory not found: $log_dir" "Log Files"
-            fi
-            ;;
-        *)
-            emit_fail "Unknown remediation: $REMEDIATION_ID" "Credential Store"
-            ;;
-    esac
+    emit_skipped "No ~/.openclaw directory found — credential store checks skipped" "Credential Store"
     exit 0
 fi
 
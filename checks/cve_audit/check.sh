@@ -8,7 +8,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -24,7 +24,9 @@ GHSA_FEED_URL="https://raw.githubusercontent.com/jgamblin/OpenClawCVEs/main/ghsa
 
 # --- Detect OpenClaw version ---
 oc_version=""
-if command -v openclaw &>/dev/null; then
+if have_facts && [ -n "${CLAWKEEPER_FACT_OPENCLAW_VERSION:-}" ]; then
+    oc_version="$CLAWKEEPER_FACT_OPENCLAW_VERSION"
+elif command -v openclaw &>/dev/null; then
     oc_version=$(openclaw --version 2>/dev/null | grep -oE '[0-9]+\.[0-9]+\.[0-9]+' | head -1)
 elif command -v npx &>/dev/null; then
     oc_version=$(npx openclaw --version 2>/dev/null | grep -oE '[0-9]+\.[0-9]+\.[0-9]+' | head -1)
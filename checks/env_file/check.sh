@@ -7,7 +7,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -42,6 +42,6 @@ for env_file in "${env_candidates[@]}"; do
 done
 
 if [ "$found_env" = false ]; then
-    emit_info "No .env file found in common locations"
+    emit_skipped "No .env file found in common locations" ".env Permissions"
     emit_info "Expected at ~/openclaw-docker/.env if using Docker setup"
 fi
@@ -8,7 +8,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -24,6 +24,7 @@ sshd_config="/etc/ssh/sshd_config"
 
 if [ ! -f "$sshd_config" ]; then
     emit_warn "sshd_config not found at $sshd_config"
+    emit_evidence "sshd_config" "not found" "present" "$sshd_config"
     emit_fail "Cannot audit SSH configuration" "SSH Hardening"
     exit 0
 fi
@@ -32,9 +33,12 @@ issues=0
 
 # Check PermitRootLogin
 root_login=$(grep -i "^PermitRootLogin" "$sshd_config" 2>/dev/null | awk '{print $2}' || echo "")
+root_login_source="$sshd_config"
 if [ -z "$root_login" ]; then
     root_login=$(grep -rhi "^PermitRootLogin" /etc/ssh/sshd_config.d/ 2>/dev/null | tail -1 | awk '{print $2}' || echo "")
+    [ -n "$root_login" ] && root_login_source="/etc/ssh/sshd_config.d/"
 fi
+emit_evidence "PermitRootLogin" "${root_login:-unset}" "no or prohibit-password" "$root_login_source"
 if [ "$root_login" = "no" ] || [ "$root_login" = "prohibit-password" ]; then
     emit_pass "PermitRootLogin = $root_login" "PermitRootLogin"
 else
@@ -44,9 +48,12 @@ fi
 
 # Check PasswordAuthentication
 pass_auth=$(grep -i "^PasswordAuthentication" "$sshd_config" 2>/dev/null | awk '{print $2}' || echo "")
+pass_auth_source="$sshd_config"
 if [ -z "$pass_auth" ]; then
     pass_auth=$(grep -rhi "^PasswordAuthentication" /etc/ssh/sshd_config.d/ 2>/dev/null | tail -1 | awk '{print $2}' || echo "")
+    [ -n "$pass_auth" ] && pass_auth_source="/etc/ssh/sshd_config.d/"
 fi
+emit_evidence "PasswordAuthentication" "${pass_auth:-unset}" "no" "$pass_auth_source"
 if [ "$pass_auth" = "no" ]; then
     emit_pass "PasswordAuthentication = no" "PasswordAuthentication"
 else
@@ -57,18 +64,22 @@ fi
 # Check X11Forwarding
 x11=$(grep -i "^X11Forwarding" "$sshd_config" 2>/dev/null | awk '{print $2}' || echo "")
 if [ "$x11" = "no" ]; then
+    emit_evidence "X11Forwarding" "$x11" "no" "$sshd_config"
     emit_pass "X11Forwarding = no" "X11Forwarding"
 elif [ "$x11" = "yes" ]; then
     issues=$((issues + 1))
+    emit_evidence "X11Forwarding" "$x11" "no" "$sshd_config"
     emit_fail "X11Forwarding is enabled -- should be 'no' on a headless server" "X11Forwarding"
 fi
 
 # Check MaxAuthTries
 max_auth=$(grep -i "^MaxAuthTries" "$sshd_config" 2>/dev/null | awk '{print $2}' || echo "")
 if [ -n "$max_auth" ] && [ "$max_auth" -le 3 ] 2>/dev/null; then
+    emit_evidence "MaxAuthTries" "$max_auth" "3 or less" "$sshd_config"
     emit_pass "MaxAuthTries = $max_auth" "MaxAuthTries"
 elif [ -n "$max_auth" ] && [ "$max_auth" -gt 6 ] 2>/dev/null; then
     issues=$((issues + 1))
+    emit_evidence "MaxAuthTries" "$max_auth" "3 or less" "$sshd_config"
     emit_fail "MaxAuthTries is $max_auth -- recommend 3 or less" "MaxAuthTries"
 fi
 
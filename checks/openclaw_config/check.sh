@@ -9,7 +9,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -24,7 +24,7 @@ config_dir="$HOME/.openclaw"
 
 # ---------- Check config directory exists ----------
 if [ ! -d "$config_dir" ]; then
-    emit_info "No OpenClaw config directory found (~/.openclaw)"
+    emit_skipped "No OpenClaw config directory found (~/.openclaw)" "OpenClaw Config"
     emit_info "This is expected if OpenClaw isn't installed yet. Skipping config checks."
     exit 0
 fi
@@ -42,7 +42,7 @@ fi
 
 # ---------- Check config file exists ----------
 if [ ! -f "$config_file" ]; then
-    emit_info "No openclaw.json found. Skipping config content checks."
+    emit_skipped "No openclaw.json found — config content checks skipped" "OpenClaw Config"
     exit 0
 fi
 
@@ -8,7 +8,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -21,7 +21,7 @@ done
 config_file="$HOME/.openclaw/openclaw.json"
 
 if [ ! -f "$config_file" ]; then
-    emit_info "No openclaw.json found — skipping hardening checks"
+    emit_skipped "No openclaw.json found — hardening checks skipped" "OpenClaw Hardening"
     exit 0
 fi
 
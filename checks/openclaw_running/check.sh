@@ -9,7 +9,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -24,7 +24,13 @@ emit_info "Checking for running OpenClaw instances..."
 found=false
 
 # ---------- Check for OpenClaw Docker container ----------
-if command -v docker &>/dev/null && docker info &>/dev/null 2>&1; then
+docker_up=false
+if have_facts; then
+    [ "${CLAWKEEPER_FACT_DOCKER_RUNNING:-}" = "1" ] && docker_up=true
+elif command -v docker &>/dev/null && docker info &>/dev/null 2>&1; then
+    docker_up=true
+fi
+if [ "$docker_up" = true ]; then
     oc_containers=$(docker ps --format '{{.Names}} {{.Image}}' 2>/dev/null | grep -i "openclaw" || true)
     if [ -n "$oc_containers" ]; then
         found=true
@@ -64,10 +70,13 @@ if [ -n "$port_check" ]; then
         emit_pass "Gateway bound to localhost only" "OpenClaw Gateway"
     else
         emit_warn "Gateway binding could not be determined"
+        emit_skipped "Gateway binding not verified" "OpenClaw Gateway"
     fi
+elif [ "$found" = true ]; then
+    emit_skipped "Gateway port 18789 is not in use — binding not checked" "OpenClaw Gateway"
 fi
 
 if [ "$found" = false ]; then
-    emit_info "No running OpenClaw instance detected"
+    emit_skipped "No running OpenClaw instance detected" "OpenClaw Gateway"
     emit_info "This is expected if you haven't installed OpenClaw yet."
 fi
@@ -8,11 +8,11 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "$SCRIPT_DIR/../../lib/helpers.sh"
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 sessions_dir="$HOME/.openclaw/agents"
 if [ ! -d "$sessions_dir" ]; then
-    emit_info "No agents directory found — skipping session command scan"
+    emit_skipped "No agents directory found — session command scan skipped" "Session Commands"
     exit 0
 fi
 
@@ -8,7 +8,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -110,6 +110,6 @@ for skills_dir in "${skills_dirs[@]}"; do
 done
 
 if [ "$found_skills" = false ]; then
-    emit_info "No skills directories found — skipping skills audit"
+    emit_skipped "No skills directories found — skills audit skipped" "Skills Audit"
     emit_info "Checked: ~/.openclaw/skills/ and ./skills/"
 fi
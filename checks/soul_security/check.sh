@@ -9,7 +9,7 @@
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 # shellcheck source=../../lib/helpers.sh
-source "${SCRIPT_DIR}/../../lib/helpers.sh" 2>/dev/null || true
+source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"
 
 MODE="scan"
 while [[ $# -gt 0 ]]; do
@@ -94,6 +94,6 @@ for soul_file in "${soul_files[@]}"; do
 done
 
 if [ "$found_soul" = false ]; then
-    emit_info "No SOUL.md files found — skipping SOUL.md audit"
+    emit_skipped "No SOUL.md files found — SOUL.md audit skipped" "SOUL.md Audit"
     emit_info "Checked: ~/.openclaw/SOUL.md and ./SOUL.md"
 fi
@@ -20,6 +20,21 @@ set -uo pipefail
 # --- JSON Output Helpers (used by check.sh / remediate.sh scripts) ----------
 # These emit structured JSON lines to stdout. Both the CLI orchestrator and
 # the Tauri backend parse this output.
+#
+# Output follows check protocol v1: a {"protocol":1} header, then one object
+# per line tagged by "type": status, info, warn, prompt, evidence or metric.
+# Anything else belongs on stderr.
+
+CLAWKEEPER_PROTOCOL_VERSION=1
+
+# Print the protocol header before a script's first line of output. Each
+# check runs in its own process (a subshell in the bundle), so each gets one.
+_emit_header() {
+    if [ -z "${_CLAWKEEPER_HEADER_SENT:-}" ]; then
+        printf '{"protocol":%s}\n' "$CLAWKEEPER_PROTOCOL_VERSION"
+        _CLAWKEEPER_HEADER_SENT=1
+    fi
+}
 
 # Escape a string for safe JSON embedding
 _json_escape() {
@@ -34,34 +49,65 @@ _json_escape() {
 emit_pass() {
     local detail="$1"
     local check_name="${2:-}"
-    printf '{"status":"PASS","check_name":"%s","detail":"%s"}\n' \
+    _emit_header
+    printf '{"type":"status","status":"PASS","check_name":"%s","detail":"%s"}\n' \
         "$(_json_escape "$check_name")" "$(_json_escape "$detail")"
 }
 
 emit_fail() {
     local detail="$1"
     local check_name="${2:-}"
-    printf '{"status":"FAIL","check_name":"%s","detail":"%s"}\n' \
+    _emit_header
+    printf '{"type":"status","status":"FAIL","check_name":"%s","detail":"%s"}\n' \
         "$(_json_escape "$check_name")" "$(_json_escape "$detail")"
 }
 
 emit_info() {
     local message="$1"
+    _emit_header
     printf '{"type":"info","message":"%s"}\n' "$(_json_escape "$message")"
 }
 
 emit_warn() {
     local message="$1"
+    _emit_header
     printf '{"type":"warn","message":"%s"}\n' "$(_json_escape "$message")"
 }
 
 emit_skipped() {
     local detail="$1"
     local check_name="${2:-}"
-    printf '{"status":"SKIPPED","check_name":"%s","detail":"%s"}\n' \
+    _emit_header
+    printf '{"type":"status","status":"SKIPPED","check_name":"%s","detail":"%s"}\n' \
         "$(_json_escape "$check_name")" "$(_json_escape "$detail")"
 }
 
+# Record what a check observed, e.g.
+#   emit_evidence "PermitRootLogin" "yes" "no" "/etc/ssh/sshd_config"
+# Evidence supports the next status line; expected and source are optional.
+emit_evidence() {
+    local key="$1"
+    local observed="$2"
+    local expected="${3:-}"
+    local source="${4:-}"
+    _emit_header
+    printf '{"type":"evidence","key":"%s","observed":"%s","expected":"%s","source":"%s"}\n' \
+        "$(_json_escape "$key")" \
+        "$(_json_escape "$observed")" \
+        "$(_json_escape "$expected")" \
+        "$(_json_escape "$source")"
+}
+
+# Record a measured value. value must be a number; unit is optional.
+emit_metric() {
+    local name="$1"
+    local value="$2"
+    local unit="${3:-}"
+    _emit_header
+    printf '{"type":"metric","name":"%s","value":%s,"unit":"%s"}\n' \
+        "$(_json_escape "$name")" "$value" "$(_json_escape "$unit")"
+}
+
 # Emit a remediation prompt. The orchestrator will ask the user and call
 # remediate.sh if accepted. fail_detail is used when the user declines in
 # scan mode; skip_detail when they decline in setup mode.
@@ -70,13 +116,23 @@ emit_prompt() {
     local remediation_id="$2"
     local fail_detail="${3:-}"
     local skip_detail="${4:-}"
-    printf '{"action":"prompt","message":"%s","remediation_id":"%s","fail_detail":"%s","skip_detail":"%s"}\n' \
+    _emit_header
+    printf '{"type":"prompt","message":"%s","remediation_id":"%s","fail_detail":"%s","skip_detail":"%s"}\n' \
         "$(_json_escape "$message")" \
         "$(_json_escape "$remediation_id")" \
         "$(_json_escape "$fail_detail")" \
         "$(_json_escape "$skip_detail")"
 }
 
+# --- Host Facts (used by check.sh scripts) ---------------------------------
+# The desktop app and clawkeeper-scan probe the host once per scan and pass
+# what they found as CLAWKEEPER_FACT_* variables (OPENCLAW_VERSION,
+# DOCKER_RUNNING, ...). The bash CLI doesn't, so a check should fall back to
+# detecting for itself when have_facts fails.
+have_facts() {
+    [ "${CLAWKEEPER_FACTS:-}" = "1" ]
+}
+
 # --- JSON Parsing Helper (used by the orchestrator) -------------------------
 # Extract a value from a simple flat JSON object. No jq dependency.
 # Usage: _jval '{"key":"value"}' "key"  → prints "value"
@@ -165,6 +221,7 @@ INTERACTIVE=true
 SCAN_ONLY=false
 REPORT_FILE=""
 REPORT_LINES=()
+CHECK_SCORES=()
 HOMEBREW_FAILED=false
 CAN_INSTALL_SOFTWARE=true
 
@@ -264,7 +321,7 @@ init_gum_icons() {
     _GUM_WARN_ICON=$(gum style --foreground "$GUM_YELLOW" "⚠")
     _GUM_SKIP_ICON=$(gum style --foreground "$GUM_YELLOW" "⊘")
     _GUM_INFO_ICON=$(gum style --foreground "$GUM_DIM" "→")
-    _GUM_FIXED_SUFFIX=$(gum style --foreground "$GUM_DIM" "(fixed)")
+    _GUM_FIXED_SUFFIX=$(gum style --foreground "$GUM_DIM" "(just fixed)")
     _GUM_SKIPPED_SUFFIX=$(gum style --foreground "$GUM_DIM" "(accepted risk)")
 }
 
@@ -452,8 +509,8 @@ print_phase_summary() {
 print_expectations() {
     echo ""
     if [ "$HAS_GUM" = true ]; then
-        gum style --foreground "$GUM_DIM" -- "  This wizard walks you through 5 phases:"
         gum style --foreground "$GUM_DIM" -- \
+            "  This wizard walks you through 5 phases:" \
             "    1. Host Hardening   — reduce your attack surface" \
             "    2. Network          — verify network security" \
             "    3. Prerequisites    — install required software" \
@@ -503,21 +560,19 @@ step_header() {
     _compact_flush
     echo ""
     if [ "$HAS_GUM" = true ]; then
-        gum style --bold --foreground "$GUM_BOLD_WHITE" -- "$1"
+        gum style --bold --foreground "$GUM_BOLD_WHITE" -- "Step ${TOTAL}: $1"
     else
-        echo -e "${BOLD}$1${RESET}"
+        echo -e "${BOLD}Step ${TOTAL}: $1${RESET}"
     fi
 }
 
 pass() {
     PASS=$((PASS + 1))
     if [ "$COMPACT_OUTPUT" = true ] && [ "$_COMPACT_THIS_CHECK" = true ]; then
-        local display_name="${_COMPACT_STEP_NAME}"
-        [ -n "$2" ] && [ "$2" != "$_COMPACT_STEP_NAME" ] && display_name="$2"
         if [ "$HAS_GUM" = true ]; then
-            _compact_emit "  ${_GUM_PASS_ICON} ${display_name}"
+            _compact_emit "  ${_GUM_PASS_ICON} ${_COMPACT_STEP_NAME}"
         else
-            _compact_emit "$(echo -e "  ${GREEN}✓${RESET} ${display_name}")"
+            _compact_emit "$(echo -e "  ${GREEN}✓${RESET} ${_COMPACT_STEP_NAME}")"
         fi
         log_result "PASS" "$2" "$1"
         return
@@ -533,12 +588,10 @@ pass() {
 fail() {
     FAIL=$((FAIL + 1))
     if [ "$COMPACT_OUTPUT" = true ] && [ "$_COMPACT_THIS_CHECK" = true ]; then
-        local display_name="${_COMPACT_STEP_NAME}"
-        [ -n "$2" ] && [ "$2" != "$_COMPACT_STEP_NAME" ] && display_name="$2"
         if [ "$HAS_GUM" = true ]; then
-            _compact_emit "  ${_GUM_FAIL_ICON} ${display_name}"
+            _compact_emit "  ${_GUM_FAIL_ICON} ${_COMPACT_STEP_NAME}"
         else
-            _compact_emit "$(echo -e "  ${RED}✗${RESET} ${display_name}")"
+            _compact_emit "$(echo -e "  ${RED}✗${RESET} ${_COMPACT_STEP_NAME}")"
         fi
         log_result "FAIL" "$2" "$1"
         return
@@ -554,12 +607,10 @@ fail() {
 fixed() {
     FIXED=$((FIXED + 1))
     if [ "$COMPACT_OUTPUT" = true ] && [ "$_COMPACT_THIS_CHECK" = true ]; then
-        local display_name="${_COMPACT_STEP_NAME}"
-        [ -n "$2" ] && [ "$2" != "$_COMPACT_STEP_NAME" ] && display_name="$2"
         if [ "$HAS_GUM" = true ]; then
-            _compact_emit "  ${_GUM_PASS_ICON} ${display_name} ${_GUM_FIXED_SUFFIX}"
+            _compact_emit "  ${_GUM_PASS_ICON} ${_COMPACT_STEP_NAME} ${_GUM_FIXED_SUFFIX}"
         else
-            _compact_emit "$(echo -e "  ${GREEN}✓${RESET} ${display_name} ${DIM}(fixed)${RESET}")"
+            _compact_emit "$(echo -e "  ${GREEN}✓${RESET} ${_COMPACT_STEP_NAME} ${DIM}(fixed)${RESET}")"
         fi
         log_result "FIXED" "$2" "$1"
         return
@@ -567,20 +618,26 @@ fixed() {
     if [ "$HAS_GUM" = true ]; then
         echo "  ${_GUM_PASS_ICON} $1 ${_GUM_FIXED_SUFFIX}"
     else
-        echo -e "  ${GREEN}✓${RESET} $1 ${DIM}(fixed)${RESET}"
+        echo -e "  ${GREEN}✓${RESET} $1 ${DIM}(just fixed)${RESET}"
     fi
     log_result "FIXED" "$2" "$1"
+    # After the 3rd fix, a subtle "at scale" hint (suppress in compact mode)
+    if [ "$FIXED" -eq 3 ] && [ "$COMPACT_OUTPUT" != true ]; then
+        if [ "$HAS_GUM" = true ]; then
+            echo "  $(gum style --foreground "$GUM_DIM" "Track drift across hosts:") $(gum style --foreground "$GUM_CYAN" "clawkeeper.sh agent --install")"
+        else
+            echo -e "  ${DIM}Track drift across hosts: ${RESET}${CYAN}clawkeeper.sh agent --install${RESET}"
+        fi
+    fi
 }
 
 skipped() {
     SKIPPED=$((SKIPPED + 1))
     if [ "$COMPACT_OUTPUT" = true ] && [ "$_COMPACT_THIS_CHECK" = true ]; then
-        local display_name="${_COMPACT_STEP_NAME}"
-        [ -n "$2" ] && [ "$2" != "$_COMPACT_STEP_NAME" ] && display_name="$2"
         if [ "$HAS_GUM" = true ]; then
-            _compact_emit "  ${_GUM_SKIP_ICON} ${display_name} ${_GUM_SKIPPED_SUFFIX}"
+            _compact_emit "  ${_GUM_SKIP_ICON} ${_COMPACT_STEP_NAME} ${_GUM_SKIPPED_SUFFIX}"
         else
-            _compact_emit "$(echo -e "  ${YELLOW}⊘${RESET} ${display_name} ${DIM}(risk)${RESET}")"
+            _compact_emit "$(echo -e "  ${YELLOW}⊘${RESET} ${_COMPACT_STEP_NAME} ${DIM}(risk)${RESET}")"
         fi
         log_result "SKIPPED" "$2" "$1"
         return
@@ -634,6 +691,40 @@ ask_yn() {
     esac
 }
 
+# === Grading policy (from checks/grading.toml) ===========================
+
+__grading_policy() {
+    cat <<'POLICY'
+# Grading policy shared by the CLI (lib/scanner.sh), the SaaS agent
+# (lib/agent.sh) and the desktop app. scripts/bundle.sh inlines this file
+# into clawkeeper.sh, so keep it to flat `key = value` lines.
+
+# The score is the percentage of scored checks that pass, each check
+# weighted by its `weight` or else its severity (critical 10, high 5,
+# medium 3, low 1). Each status below counts as "pass", "fail" or "ignore"
+# (left out of the score); a scan with nothing scored is 100. PASS always
+# counts as a pass and FAIL as a fail. ERROR is a check that crashed or
+# reported nothing. TIMEOUT comes from the desktop app only, as does
+# WAIVED (a failure covered by a waiver), which is always left out.
+fixed = "pass"
+skipped = "ignore"
+timeout = "ignore"
+error = "ignore"
+
+# A check with severity = "critical" that fails, times out or errors caps
+# the grade here.
+critical_failure_cap = "C"
+
+# Minimum score for each grade, each below the one for the grade above;
+# anything lower is an F.
+[thresholds]
+A = 95
+B = 85
+C = 70
+D = 50
+POLICY
+}
+
 # === Scanner (check runner, detection, reporting) ========================
 
 # ============================================================================
@@ -680,10 +771,13 @@ run_check() {
     local remediate_func="__remediate_${check_id}"
 
     # Read metadata from the __meta_<id> function (set by bundle) or fallback
-    local check_name="$check_id"
+    local check_name="$check_id" check_severity="medium" check_weight=""
     if type "__meta_${check_id}" &>/dev/null; then
         check_name=$("__meta_${check_id}" name)
+        check_severity=$("__meta_${check_id}" severity)
+        check_weight=$("__meta_${check_id}" weight)
     fi
+    local first_line=${#REPORT_LINES[@]}
 
     local mode="scan"
     [ "$SCAN_ONLY" != true ] && mode="setup"
@@ -695,7 +789,7 @@ run_check() {
     # In compact mode, check if output contains any prompts
     _COMPACT_THIS_CHECK=false
     if [ "$COMPACT_OUTPUT" = true ]; then
-        if ! echo "$json_output" | grep -q '"action":"prompt"'; then
+        if ! echo "$json_output" | grep -qE '"(type|action)":"prompt"'; then
             _COMPACT_THIS_CHECK=true
         else
             # Flush buffered compact output before verbose check
@@ -721,12 +815,9 @@ run_check() {
         j_check=$(_jval "$line" "check_name")
         [ -z "$j_check" ] && j_check="$check_name"
 
-        if [ -n "$j_type" ]; then
-            case "$j_type" in
-                info) info "$j_message" ;;
-                warn) warn "$j_message" ;;
-            esac
-        elif [ "$j_action" = "prompt" ]; then
+        # Protocol v1 tags every line with "type"; older output used
+        # "action" for prompts and no tag on status lines
+        if [ "$j_type" = "prompt" ] || [ "$j_action" = "prompt" ]; then
             if ask_yn "$j_message"; then
                 # Run remediation
                 local rem_output
@@ -741,17 +832,17 @@ run_check() {
                     r_check=$(_jval "$rem_line" "check_name")
                     [ -z "$r_check" ] && r_check="$j_check"
 
-                    if [ -n "$r_type" ]; then
-                        case "$r_type" in
-                            info) info "$r_message" ;;
-                            warn) warn "$r_message" ;;
-                        esac
-                    elif [ -n "$r_status" ]; then
+                    if [ -n "$r_status" ]; then
                         case "$r_status" in
                             PASS)  fixed "$r_detail" "$r_check" ;;
                             FIXED) fixed "$r_detail" "$r_check" ;;
                             FAIL)  fail "$r_detail" "$r_check" ;;
                         esac
+                    else
+                        case "$r_type" in
+                            info) info "$r_message" ;;
+                            warn) warn "$r_message" ;;
+                        esac
                     fi
                 done <<< "$rem_output"
             else
@@ -771,8 +862,42 @@ run_check() {
                 FIXED)   fixed "$j_detail" "$j_check" ;;
                 SKIPPED) skipped "$j_detail" "$j_check" ;;
             esac
+        else
+            case "$j_type" in
+                info) info "$j_message" ;;
+                warn) warn "$j_message" ;;
+            esac
         fi
     done <<< "$json_output"
+
+    _record_check_score "$first_line" "$check_severity" "$check_weight"
+}
+
+# Folds the report lines a check added (from index $1) into one status, as
+# the desktop scanner does: FAIL > FIXED > PASS > SKIPPED, ERROR if it
+# reported nothing. Appends "status|weight|severity" to CHECK_SCORES.
+_record_check_score() {
+    local first="$1" severity="$2" weight="$3" status="" i line_status
+    for ((i = first; i < ${#REPORT_LINES[@]}; i++)); do
+        line_status="${REPORT_LINES[$i]%%|*}"
+        case "$line_status" in
+            FAIL) status="FAIL" ;;
+            FIXED) [ "$status" != "FAIL" ] && status="FIXED" ;;
+            PASS) [ "$status" != "FAIL" ] && [ "$status" != "FIXED" ] && status="PASS" ;;
+            SKIPPED) [ -z "$status" ] && status="SKIPPED" ;;
+        esac
+    done
+    [ -z "$status" ] && status="ERROR"
+
+    if [ -z "$weight" ]; then
+        case "$severity" in
+            critical) weight=10 ;;
+            high) weight=5 ;;
+            low) weight=1 ;;
+            *) weight=3 ;;
+        esac
+    fi
+    CHECK_SCORES+=("$status|$weight|$severity")
 }
 
 # --- Admin / Install Capability Check ---------------------------------------
@@ -953,43 +1078,106 @@ detect_openclaw_installed() {
     fi
 }
 
+# --- Grading ----------------------------------------------------------------
+# Thresholds and status handling come from checks/grading.toml, which
+# bundle.sh inlines as __grading_policy. The defaults below match that file.
+
+_grading_value() {
+    local key="$1" default="$2" value=""
+    if type __grading_policy &>/dev/null; then
+        value=$(__grading_policy | sed -n "s/^${key} *= *\"\{0,1\}\([A-Za-z0-9.]*\).*/\1/p" | head -1)
+    fi
+    echo "${value:-$default}"
+}
+
+# Weighted percentage of scored checks passing, from CHECK_SCORES. FIXED,
+# SKIPPED and ERROR count as "pass", "fail" or "ignore" per the policy; a
+# scan with nothing scored is 100.
+_weighted_score() {
+    printf '%s\n' ${CHECK_SCORES[@]+"${CHECK_SCORES[@]}"} | awk -F'|' \
+        -v fixed="$(_grading_value fixed pass)" \
+        -v skipped="$(_grading_value skipped ignore)" \
+        -v error="$(_grading_value error ignore)" '
+        NF >= 2 {
+            counts = "fail"
+            if ($1 == "PASS") counts = "pass"
+            else if ($1 == "FIXED") counts = fixed
+            else if ($1 == "SKIPPED") counts = skipped
+            else if ($1 == "ERROR") counts = error
+            if (counts == "pass") { passing += $2; scored += $2 }
+            else if (counts == "fail") scored += $2
+        }
+        END { if (scored > 0) printf "%.4f\n", passing * 100 / scored; else print 100 }'
+}
+
+# The weighted score rounded to a whole percentage.
+compute_score() {
+    printf '%.0f\n' "$(_weighted_score)"
+}
+
+# Letter grade: the best grade whose threshold the score meets (by default
+# this scan's weighted score), capped at critical_failure_cap while a
+# critical check is failing.
+compute_grade() {
+    local score="${1:-$(_weighted_score)}" grade="F" candidate default min
+    for candidate in A B C D; do
+        case "$candidate" in
+            A) default=95 ;;
+            B) default=85 ;;
+            C) default=70 ;;
+            D) default=50 ;;
+        esac
+        min=$(_grading_value "$candidate" "$default")
+        if awk -v s="$score" -v m="$min" 'BEGIN { exit !(s >= m) }'; then
+            grade="$candidate"
+            break
+        fi
+    done
+
+    local cap="C"
+    type __grading_policy &>/dev/null && cap=$(_grading_value critical_failure_cap "")
+    if [ -n "$cap" ] && [[ "$grade" < "$cap" ]] && _has_critical_failure; then
+        grade="$cap"
+    fi
+    echo "$grade"
+}
+
+_has_critical_failure() {
+    local entry
+    for entry in ${CHECK_SCORES[@]+"${CHECK_SCORES[@]}"}; do
+        case "$entry" in
+            FAIL\|*\|critical|ERROR\|*\|critical) return 0 ;;
+        esac
+    done
+    return 1
+}
+
 # --- Report -----------------------------------------------------------------
 
 print_report() {
     echo ""
     if [ "$HAS_GUM" = true ]; then
-        echo "$(gum style --bold --foreground "$GUM_CYAN" "════════════════════════════════════════════════════")"
+        gum style --bold --foreground "$GUM_CYAN" --border double --border-foreground "$GUM_BORDER_FG" --padding "0 2" -- ""
     else
         echo -e "${CYAN}${BOLD}════════════════════════════════════════════════════${RESET}"
     fi
     echo ""
 
-    # Calculate grade — SKIPPED = accepted risk (not penalized like FAIL)
-    local scored_checks=$((PASS + FAIL + FIXED))
-    local effective_pass=$((PASS + FIXED))
-    local score=0
-
-    if [ "$scored_checks" -gt 0 ]; then
-        score=$(( (effective_pass * 100) / scored_checks ))
-    fi
+    local score grade
+    score=$(compute_score)
+    grade=$(compute_grade)
 
-    local grade="F"
     local grade_color="$RED"
     local gum_grade_color="$GUM_RED"
-    if [ "$score" -ge 95 ]; then
-        grade="A"; grade_color="$GREEN"; gum_grade_color="$GUM_GREEN"
-    elif [ "$score" -ge 85 ]; then
-        grade="B"; grade_color="$GREEN"; gum_grade_color="$GUM_GREEN"
-    elif [ "$score" -ge 70 ]; then
-        grade="C"; grade_color="$YELLOW"; gum_grade_color="$GUM_YELLOW"
-    elif [ "$score" -ge 50 ]; then
-        grade="D"; grade_color="$YELLOW"; gum_grade_color="$GUM_YELLOW"
-    fi
+    case "$grade" in
+        A|B) grade_color="$GREEN"; gum_grade_color="$GUM_GREEN" ;;
+        C|D) grade_color="$YELLOW"; gum_grade_color="$GUM_YELLOW" ;;
+    esac
 
     if [ "$HAS_GUM" = true ]; then
-        echo "  $(gum style --bold "Security Grade:") $(gum style --bold --foreground "$gum_grade_color" "$grade") $(gum style --foreground "$GUM_DIM" "(${score}% of checks passing)")"
+        echo "  $(gum style --bold "Security Grade:") $(gum style --bold --foreground "$gum_grade_color" "$grade") $(gum style --foreground "$GUM_DIM" "(${score}% weighted by severity)")"
     else
-        echo -e "  ${BOLD}Security Grade: ${grade_color}${BOLD}$grade${RESET} ${DIM}(${score}% of checks passing)${RESET}"
+        echo -e "  ${BOLD}Security Grade: ${grade_color}${BOLD}$grade${RESET} ${DIM}(${score}% weighted by severity)${RESET}"
     fi
     echo ""
 
@@ -1055,7 +1243,7 @@ print_report() {
     fi
 
     if [ "$HAS_GUM" = true ]; then
-        echo "$(gum style --bold --foreground "$GUM_CYAN" "════════════════════════════════════════════════════")"
+        gum style --bold --foreground "$GUM_CYAN" --border double --border-foreground "$GUM_BORDER_FG" --padding "0 2" -- ""
     else
         echo -e "${CYAN}${BOLD}════════════════════════════════════════════════════${RESET}"
     fi
@@ -1097,7 +1285,7 @@ print_report() {
         else
             echo -e "  ${GREEN}✓${RESET} Agent connected — view your dashboard at ${CYAN}clawkeeper.dev${RESET}"
         fi
-    elif [ "$has_api_key" != true ] && [ "$SCAN_ONLY" = true ]; then
+    elif [ "$has_api_key" != true ]; then
         echo "  Track your score over time with a free dashboard:"
         if [ "$HAS_GUM" = true ]; then
             echo "  → Sign up at $(gum style --foreground "$GUM_CYAN" "https://clawkeeper.dev/signup")"
@@ -1110,84 +1298,6 @@ print_report() {
     echo ""
 }
 
-print_install_summary() {
-    # Only show after a setup/deploy that actually installed OpenClaw
-    if [ "$SCAN_ONLY" = true ] || [ "$OPENCLAW_INSTALLED" != true ]; then
-        return
-    fi
-
-    local install_type config_path autostart container_line
-    if [ "$DEPLOY_MODE" = "docker" ]; then
-        install_type="Docker"
-        config_path="~/openclaw-docker/"
-        autostart=""
-        container_line="openclaw"
-    else
-        install_type="Native (npm)"
-        config_path="~/.openclaw/"
-        container_line=""
-        if [ "$PLATFORM" = "macos" ]; then
-            autostart="LaunchAgent (runs on login)"
-        elif [ "$PLATFORM" = "linux" ]; then
-            autostart="systemd service"
-        else
-            autostart="manual"
-        fi
-    fi
-
-    echo ""
-
-    if [ "$HAS_GUM" = true ]; then
-        echo "$(gum style --border double --border-foreground "$GUM_BORDER_FG" --padding "1 3" -- \
-            "$(gum style --bold --foreground "$GUM_GREEN" "✓ OpenClaw installed and running!")" \
-            "" \
-            "$(gum style --foreground "$GUM_DIM" "  Type         ")$(gum style --foreground "$GUM_CYAN" "$install_type")" \
-            "$(gum style --foreground "$GUM_DIM" "  Gateway      ")$(gum style --foreground "$GUM_CYAN" "http://localhost:18789")" \
-            "$(gum style --foreground "$GUM_DIM" "  Config       ")$(gum style --foreground "$GUM_CYAN" "$config_path")" \
-            $([ -n "$autostart" ] && echo "\"$(gum style --foreground "$GUM_DIM" "  Auto-start   ")$(gum style --foreground "$GUM_CYAN" "$autostart")\"") \
-            $([ -n "$container_line" ] && echo "\"$(gum style --foreground "$GUM_DIM" "  Container    ")$(gum style --foreground "$GUM_CYAN" "$container_line")\"") \
-        )"
-        echo ""
-        echo "  $(gum style --bold --foreground "$GUM_DIM" "── Connect to Clawkeeper Dashboard ────────────")"
-        echo ""
-        echo "  $(gum style --foreground 15 "Track your security score, get scan history,")"
-        echo "  $(gum style --foreground 15 "and receive alerts when your grade changes:")"
-        echo ""
-        echo "  $(gum style --foreground "$GUM_DIM" "1.") $(gum style --bold "Sign up")     $(gum style --foreground "$GUM_DIM" "→")  $(gum style --foreground "$GUM_CYAN" "https://clawkeeper.dev/signup")"
-        echo "  $(gum style --foreground "$GUM_DIM" "2.") $(gum style --bold "Connect")     $(gum style --foreground "$GUM_DIM" "→")  $(gum style --foreground "$GUM_CYAN" "clawkeeper.sh agent --install")"
-        echo "  $(gum style --foreground "$GUM_DIM" "3.") $(gum style --bold "First scan")  $(gum style --foreground "$GUM_DIM" "→")  $(gum style --foreground "$GUM_CYAN" "clawkeeper.sh agent run")"
-        echo ""
-        echo "  $(gum style --foreground "$GUM_DIM" "Scans upload automatically every hour after setup.")"
-    else
-        echo -e "${CYAN}${BOLD}══════════════════════════════════════════════════${RESET}"
-        echo -e "  ${GREEN}${BOLD}✓ OpenClaw installed and running!${RESET}"
-        echo ""
-        echo -e "  ${DIM}Type         ${RESET}${CYAN}$install_type${RESET}"
-        echo -e "  ${DIM}Gateway      ${RESET}${CYAN}http://localhost:18789${RESET}"
-        echo -e "  ${DIM}Config       ${RESET}${CYAN}$config_path${RESET}"
-        if [ -n "$autostart" ]; then
-            echo -e "  ${DIM}Auto-start   ${RESET}${CYAN}$autostart${RESET}"
-        fi
-        if [ -n "$container_line" ]; then
-            echo -e "  ${DIM}Container    ${RESET}${CYAN}$container_line${RESET}"
-        fi
-        echo -e "${CYAN}${BOLD}══════════════════════════════════════════════════${RESET}"
-        echo ""
-        echo -e "  ${DIM}${BOLD}── Connect to Clawkeeper Dashboard ────────────${RESET}"
-        echo ""
-        echo "  Track your security score, get scan history,"
-        echo "  and receive alerts when your grade changes:"
-        echo ""
-        echo -e "  ${DIM}1.${RESET} ${BOLD}Sign up${RESET}     ${DIM}→${RESET}  ${CYAN}https://clawkeeper.dev/signup${RESET}"
-        echo -e "  ${DIM}2.${RESET} ${BOLD}Connect${RESET}     ${DIM}→${RESET}  ${CYAN}clawkeeper.sh agent --install${RESET}"
-        echo -e "  ${DIM}3.${RESET} ${BOLD}First scan${RESET}  ${DIM}→${RESET}  ${CYAN}clawkeeper.sh agent run${RESET}"
-        echo ""
-        echo -e "  ${DIM}Scans upload automatically every hour after setup.${RESET}"
-    fi
-
-    echo ""
-}
-
 save_report() {
     if [ -z "$REPORT_FILE" ]; then
         return
@@ -1202,12 +1312,9 @@ save_report() {
         os_info="$PLATFORM"
     fi
 
-    local scored_checks=$((PASS + FAIL + FIXED))
-    local effective_pass=$((PASS + FIXED))
-    local score=0
-    if [ "$scored_checks" -gt 0 ]; then
-        score=$(( (effective_pass * 100) / scored_checks ))
-    fi
+    local score grade
+    score=$(compute_score)
+    grade=$(compute_grade)
 
     {
         echo "CLAW Keeper Security Report"
@@ -1219,6 +1326,7 @@ save_report() {
         echo "---"
         echo ""
         echo "Score: ${score}%"
+        echo "Grade: $grade"
         echo "Passed: $PASS"
         echo "Fixed: $FIXED"
         echo "Failed: $FAIL"
@@ -1561,13 +1669,9 @@ agent_run() {
         skipped_count=$(grep "^Accepted risks:" "$report_file" | head -1 | sed 's/Accepted risks: *//')
     fi
 
-    # Calculate grade
-    if [ "$score" -ge 95 ]; then grade="A"
-    elif [ "$score" -ge 85 ]; then grade="B"
-    elif [ "$score" -ge 70 ]; then grade="C"
-    elif [ "$score" -ge 50 ]; then grade="D"
-    else grade="F"
-    fi
+    # The scan records its grade; older reports only have the score
+    grade=$(grep "^Grade:" "$report_file" | head -1 | sed 's/Grade: *//')
+    [ -z "$grade" ] && grade=$(compute_grade "$score")
 
     # Parse individual checks from report (STATUS | CHECK_NAME | DETAIL)
     local checks_json="["
@@ -1933,7 +2037,7 @@ setup_native_launchd() {
     local plist_file="$plist_dir/com.openclaw.agent.plist"
 
     if [ -f "$plist_file" ]; then
-        pass "LaunchAgent already exists at ~/Library/LaunchAgents/com.openclaw.agent.plist" "LaunchAgent"
+        pass "LaunchAgent already exists at $plist_file" "LaunchAgent"
         return
     fi
 
@@ -1989,7 +2093,7 @@ setup_native_launchd() {
 PLIST_EOF
 
     chmod 644 "$plist_file"
-    fixed "LaunchAgent created at ~/Library/LaunchAgents/com.openclaw.agent.plist" "LaunchAgent"
+    fixed "LaunchAgent created at $plist_file" "LaunchAgent"
     info "It will auto-start OpenClaw next time you log in."
 
     if ask_yn "Load and start OpenClaw now?"; then
@@ -2004,70 +2108,6 @@ PLIST_EOF
     fi
 }
 
-prompt_native_daemon_start() {
-    step_header "Start OpenClaw Daemon"
-
-    # If OpenClaw is already running (e.g. from the LaunchAgent step), skip.
-    if pgrep -f "openclaw.*gateway" &>/dev/null; then
-        pass "OpenClaw daemon is already running" "Daemon Start"
-        return
-    fi
-
-    local openclaw_bin
-    openclaw_bin=$(command -v openclaw 2>/dev/null || echo "")
-
-    if [ -z "$openclaw_bin" ]; then
-        fail "openclaw binary not found in PATH" "Daemon Start"
-        info "Ensure OpenClaw is installed: npm install -g openclaw"
-        return
-    fi
-
-    info "OpenClaw is installed but the daemon is not yet running."
-    echo ""
-    highlight_msg "  Command: openclaw onboard --install-daemon"
-    echo ""
-
-    if ask_yn "Start the OpenClaw daemon now?"; then
-        info "Starting OpenClaw daemon..."
-        echo ""
-
-        local started=false
-
-        # Primary: openclaw onboard --install-daemon
-        if "$openclaw_bin" onboard --install-daemon </dev/tty 2>&1; then
-            started=true
-        else
-            warn "onboard --install-daemon did not succeed — trying alternative..."
-            # Fallback: openclaw gateway install
-            if "$openclaw_bin" gateway install </dev/tty 2>&1; then
-                started=true
-            else
-                warn "gateway install did not succeed — starting gateway directly..."
-                # Last resort: start the gateway process directly
-                nohup "$openclaw_bin" gateway --port 18789 \
-                    > "$HOME/.openclaw/openclaw.log" 2>&1 &
-                sleep 3
-                if pgrep -f "openclaw.*gateway" &>/dev/null; then
-                    started=true
-                fi
-            fi
-        fi
-
-        echo ""
-        if $started || pgrep -f "openclaw.*gateway" &>/dev/null; then
-            fixed "OpenClaw daemon is running" "Daemon Start"
-        else
-            fail "Could not start the OpenClaw daemon" "Daemon Start"
-            info "Try starting manually:"
-            info "  openclaw onboard --install-daemon"
-            info "  openclaw gateway --port 18789"
-        fi
-    else
-        skipped "Daemon start deferred" "Daemon Start"
-        info "When you're ready, run: openclaw onboard --install-daemon"
-    fi
-}
-
 # --- Docker -----------------------------------------------------------------
 
 harden_docker_desktop() {
@@ -2482,10 +2522,7 @@ setup_openclaw_config() {
         _openclaw_config_set "gateway.controlUi.enabled" "false"   && succeeded=$((succeeded + 1)) || failed_keys+=("gateway.controlUi.enabled")
         _openclaw_config_set "discovery.mdns.mode" "off"        && succeeded=$((succeeded + 1)) || failed_keys+=("discovery.mdns.mode")
         _openclaw_config_set "discovery.wideArea.enabled" "false" && succeeded=$((succeeded + 1)) || failed_keys+=("discovery.wideArea.enabled")
-        _openclaw_config_set "agents.defaults.sandbox.mode" "all" && succeeded=$((succeeded + 1)) || failed_keys+=("agents.defaults.sandbox.mode")
-        _openclaw_config_set "tools.exec.host" "sandbox"       && succeeded=$((succeeded + 1)) || failed_keys+=("tools.exec.host")
         _openclaw_config_set "tools.exec.applyPatch.workspaceOnly" "true" && succeeded=$((succeeded + 1)) || failed_keys+=("tools.exec.applyPatch.workspaceOnly")
-        _openclaw_config_set "session.dmScope" "per-channel-peer" && succeeded=$((succeeded + 1)) || failed_keys+=("session.dmScope")
         _openclaw_config_set "logging.redactSensitive" "tools" && succeeded=$((succeeded + 1)) || failed_keys+=("logging.redactSensitive")
 
         if [ ${#failed_keys[@]} -eq 0 ]; then
@@ -2501,6 +2538,21 @@ setup_openclaw_config() {
         info "openclaw binary not found — writing config JSON directly."
         _write_openclaw_config_json "$config_file" "$bind_mode"
     fi
+
+    echo ""
+    accent_msg "  Configuration:"
+    dim_msg "    • gateway.mode = local (run gateway on this machine)"
+    if [ "$DEPLOY_MODE" = "docker" ]; then
+        dim_msg "    • gateway.bind = auto (Docker compose restricts to 127.0.0.1)"
+    else
+        dim_msg "    • gateway.bind = loopback (localhost only, not exposed to network)"
+    fi
+    dim_msg "    • gateway.auth.mode = token (required for every connection)"
+    dim_msg "    • gateway.controlUi.enabled = false (web dashboard disabled)"
+    dim_msg "    • discovery.mdns.mode = off (no mDNS broadcast on local network)"
+    dim_msg "    • discovery.wideArea.enabled = false (no wide-area DNS-SD)"
+    dim_msg "    • tools.exec.applyPatch.workspaceOnly = true (can't write outside workspace)"
+    dim_msg "    • logging.redactSensitive = tools (keys redacted in logs)"
 }
 
 _write_openclaw_config_json() {
@@ -2528,27 +2580,13 @@ _write_openclaw_config_json() {
       "enabled": false
     }
   },
-  "agents": {
-    "defaults": {
-      "sandbox": {
-        "mode": "all"
-      }
-    }
-  },
   "tools": {
     "exec": {
-      "host": "sandbox",
       "applyPatch": {
         "workspaceOnly": true
       }
     }
   },
-  "session": {
-    "dmScope": "per-channel-peer"
-  },
-  "channels": {
-    "dmPolicy": "pairing"
-  },
   "logging": {
     "redactSensitive": "tools"
   }
@@ -2905,7 +2943,7 @@ uninstall_openclaw() {
     # ── Summary ──
     echo ""
     if [ "$HAS_GUM" = true ]; then
-        echo "$(gum style --bold --foreground "$GUM_CYAN" "════════════════════════════════════════════════════")"
+        gum style --bold --foreground "$GUM_CYAN" --border double --border-foreground "$GUM_BORDER_FG" --padding "0 2" -- ""
     else
         echo -e "  ${CYAN}${BOLD}════════════════════════════════════════════════════${RESET}"
     fi
@@ -3164,8 +3202,10 @@ shield_main() {
 
 __meta_admin_user() {
     case "$1" in
-        name) echo "User Account" ;;
-        id)   echo "admin_user" ;;
+        name)     echo "User Account" ;;
+        id)       echo "admin_user" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -3225,8 +3265,10 @@ fi
 
 __meta_airdrop() {
     case "$1" in
-        name) echo "AirDrop & Handoff" ;;
-        id)   echo "airdrop" ;;
+        name)     echo "AirDrop & Handoff" ;;
+        id)       echo "airdrop" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -3303,8 +3345,10 @@ esac
 
 __meta_analytics() {
     case "$1" in
-        name) echo "Analytics & Telemetry" ;;
-        id)   echo "analytics" ;;
+        name)     echo "Analytics & Telemetry" ;;
+        id)       echo "analytics" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -3382,8 +3426,10 @@ esac
 
 __meta_automatic_login() {
     case "$1" in
-        name) echo "Automatic Login" ;;
-        id)   echo "automatic_login" ;;
+        name)     echo "Automatic Login" ;;
+        id)       echo "automatic_login" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -3443,8 +3489,10 @@ esac
 
 __meta_bluetooth() {
     case "$1" in
-        name) echo "Bluetooth" ;;
-        id)   echo "bluetooth" ;;
+        name)     echo "Bluetooth" ;;
+        id)       echo "bluetooth" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -3520,8 +3568,10 @@ esac
 
 __meta_container_security() {
     case "$1" in
-        name) echo "Container Security Audit" ;;
-        id)   echo "container_security" ;;
+        name)     echo "Container Security Audit" ;;
+        id)       echo "container_security" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -3546,12 +3596,12 @@ done
 
 # ---------- Check if OpenClaw container is running ----------
 if ! command -v docker &>/dev/null; then
-    emit_info "Docker not installed — skipping container audit"
+    emit_skipped "Docker not installed — container audit skipped" "Container Audit"
 return 0
 fi
 
 if ! docker ps --format '{{.Names}}' 2>/dev/null | grep -q "^openclaw$"; then
-    emit_info "OpenClaw container is not running — skipping container audit"
+    emit_skipped "OpenClaw container is not running — container audit skipped" "Container Audit"
 return 0
 fi
 
@@ -3690,8 +3740,10 @@ fi
 
 __meta_credential_exposure() {
     case "$1" in
-        name) echo "Credential Exposure Scan" ;;
-        id)   echo "credential_exposure" ;;
+        name)     echo "Credential Exposure Scan" ;;
+        id)       echo "credential_exposure" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -3813,8 +3865,10 @@ fi
 
 __meta_credential_store() {
     case "$1" in
-        name) echo "Credential Store Security" ;;
-        id)   echo "credential_store" ;;
+        name)     echo "Credential Store Security" ;;
+        id)       echo "credential_store" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -3827,59 +3881,10 @@ __check_credential_store() {
 # ============================================================================
 
 
-MODE="scan"
-REMEDIATION_ID=""
-while [[ $# -gt 0 ]]; do
-    case "$1" in
-        --mode) MODE="$2"; shift 2 ;;
-        --remediation-id) REMEDIATION_ID="$2"; shift 2 ;;
-        *) shift ;;
-    esac
-done
-
 openclaw_dir="$HOME/.openclaw"
 
 if [ ! -d "$openclaw_dir" ]; then
-    emit_info "No ~/.openclaw directory found — skipping credential store checks"
-return 0
-fi
-
-# --- Remediation handler ---
-if [ -n "$REMEDIATION_ID" ]; then
-    case "$REMEDIATION_ID" in
-        fix_credentials_perms)
-            creds_dir="$openclaw_dir/credentials"
-            if [ -d "$creds_dir" ]; then
-                chmod 700 "$creds_dir"
-                find "$creds_dir" -type f -exec chmod 600 {} \;
-                emit_pass "Credentials directory and files set to 700/600" "Credential Directory"
-            else
-                emit_fail "Credentials directory not found" "Credential Directory"
-            fi
-            ;;
-        fix_oauth_perms)
-            find "$openclaw_dir/agents" -name "auth-profiles.json" -exec chmod 600 {} \; 2>/dev/null
-            emit_pass "OAuth profile files set to 600" "OAuth Profiles"
-            ;;
-        fix_sessions_perms)
-            find "$openclaw_dir/agents" -name "sessions" -type d -exec chmod 700 {} \; 2>/dev/null
-            find "$openclaw_dir/agents" -path "*/sessions/*.jsonl" -exec chmod 600 {} \; 2>/dev/null
-            emit_pass "Session directories and logs set to 700/600" "Session Transcripts"
-            ;;
-        fix_log_perms)
-            log_dir="/tmp/openclaw"
-            if [ -d "$log_dir" ]; then
-                chmod 700 "$log_dir"
-                find "$log_dir" -name "*.log" -exec chmod 600 {} \; 2>/dev/null
-                emit_pass "Log directory and files set to 700/600" "Log Files"
-            else
-                emit_fail "Log directory not found: $log_dir" "Log Files"
-            fi
-            ;;
-        *)
-            emit_fail "Unknown remediation: $REMEDIATION_ID" "Credential Store"
-            ;;
-    esac
+    emit_skipped "No ~/.openclaw directory found — credential store checks skipped" "Credential Store"
 return 0
 fi
 
@@ -3991,12 +3996,62 @@ else
 fi
 }
 
+__remediate_credential_store() {
+# ============================================================================
+# Clawkeeper Remediation: Credential Store Security
+# Tightens permissions on credentials, OAuth profiles, session transcripts
+# and log files.
+# Outputs JSON lines to stdout.
+# ============================================================================
+
+
+REMEDIATION_ID="${1:-}"
+openclaw_dir="$HOME/.openclaw"
+
+case "$REMEDIATION_ID" in
+    fix_credentials_perms)
+        creds_dir="$openclaw_dir/credentials"
+        if [ -d "$creds_dir" ]; then
+            chmod 700 "$creds_dir"
+            find "$creds_dir" -type f -exec chmod 600 {} \;
+            emit_pass "Credentials directory and files set to 700/600" "Credential Directory"
+        else
+            emit_fail "Credentials directory not found" "Credential Directory"
+        fi
+        ;;
+    fix_oauth_perms)
+        find "$openclaw_dir/agents" -name "auth-profiles.json" -exec chmod 600 {} \; 2>/dev/null
+        emit_pass "OAuth profile files set to 600" "OAuth Profiles"
+        ;;
+    fix_sessions_perms)
+        find "$openclaw_dir/agents" -name "sessions" -type d -exec chmod 700 {} \; 2>/dev/null
+        find "$openclaw_dir/agents" -path "*/sessions/*.jsonl" -exec chmod 600 {} \; 2>/dev/null
+        emit_pass "Session directories and logs set to 700/600" "Session Transcripts"
+        ;;
+    fix_log_perms)
+        log_dir="/tmp/openclaw"
+        if [ -d "$log_dir" ]; then
+            chmod 700 "$log_dir"
+            find "$log_dir" -name "*.log" -exec chmod 600 {} \; 2>/dev/null
+            emit_pass "Log directory and files set to 700/600" "Log Files"
+        else
+            emit_fail "Log directory not found: $log_dir" "Log Files"
+        fi
+        ;;
+    *)
+        emit_fail "Unknown remediation: $REMEDIATION_ID" "Credential Store"
+        ;;
+esac
+}
+
 # --- Check: cve_audit ---
 
 __meta_cve_audit() {
     case "$1" in
-        name) echo "OpenClaw CVE Audit" ;;
-        id)   echo "cve_audit" ;;
+        name)     echo "OpenClaw CVE Audit" ;;
+        id)       echo "cve_audit" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4023,7 +4078,9 @@ GHSA_FEED_URL="https://raw.githubusercontent.com/jgamblin/OpenClawCVEs/main/ghsa
 
 # --- Detect OpenClaw version ---
 oc_version=""
-if command -v openclaw &>/dev/null; then
+if have_facts && [ -n "${CLAWKEEPER_FACT_OPENCLAW_VERSION:-}" ]; then
+    oc_version="$CLAWKEEPER_FACT_OPENCLAW_VERSION"
+elif command -v openclaw &>/dev/null; then
     oc_version=$(openclaw --version 2>/dev/null | grep -oE '[0-9]+\.[0-9]+\.[0-9]+' | head -1)
 elif command -v npx &>/dev/null; then
     oc_version=$(npx openclaw --version 2>/dev/null | grep -oE '[0-9]+\.[0-9]+\.[0-9]+' | head -1)
@@ -4200,8 +4257,10 @@ fi
 
 __meta_docker_installed() {
     case "$1" in
-        name) echo "Docker Desktop" ;;
-        id)   echo "docker_installed" ;;
+        name)     echo "Docker Desktop" ;;
+        id)       echo "docker_installed" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4376,8 +4435,10 @@ esac
 
 __meta_env_file() {
     case "$1" in
-        name) echo ".env File Security" ;;
-        id)   echo "env_file" ;;
+        name)     echo ".env File Security" ;;
+        id)       echo "env_file" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4422,7 +4483,7 @@ for env_file in "${env_candidates[@]}"; do
 done
 
 if [ "$found_env" = false ]; then
-    emit_info "No .env file found in common locations"
+    emit_skipped "No .env file found in common locations" ".env Permissions"
     emit_info "Expected at ~/openclaw-docker/.env if using Docker setup"
 fi
 }
@@ -4458,8 +4519,10 @@ esac
 
 __meta_filevault() {
     case "$1" in
-        name) echo "FileVault (Full-Disk Encryption)" ;;
-        id)   echo "filevault" ;;
+        name)     echo "FileVault (Full-Disk Encryption)" ;;
+        id)       echo "filevault" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4504,8 +4567,10 @@ emit_fail "FileVault status unknown" "FileVault"
 
 __meta_firewall() {
     case "$1" in
-        name) echo "macOS Firewall" ;;
-        id)   echo "firewall" ;;
+        name)     echo "macOS Firewall" ;;
+        id)       echo "firewall" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4598,8 +4663,10 @@ esac
 
 __meta_gateway_advanced() {
     case "$1" in
-        name) echo "Gateway Advanced Security" ;;
-        id)   echo "gateway_advanced" ;;
+        name)     echo "Gateway Advanced Security" ;;
+        id)       echo "gateway_advanced" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4623,7 +4690,7 @@ done
 config_file="$HOME/.openclaw/openclaw.json"
 
 if [ ! -f "$config_file" ]; then
-    emit_info "No openclaw.json found — skipping advanced gateway checks"
+    emit_skipped "No openclaw.json found — advanced gateway checks skipped" "Gateway Advanced"
 return 0
 fi
 
@@ -4734,8 +4801,10 @@ fi
 
 __meta_homebrew() {
     case "$1" in
-        name) echo "Homebrew" ;;
-        id)   echo "homebrew" ;;
+        name)     echo "Homebrew" ;;
+        id)       echo "homebrew" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4824,8 +4893,10 @@ esac
 
 __meta_icloud() {
     case "$1" in
-        name) echo "iCloud" ;;
-        id)   echo "icloud" ;;
+        name)     echo "iCloud" ;;
+        id)       echo "icloud" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4867,8 +4938,10 @@ emit_fail "iCloud is signed in (disable manually in System Settings)" "iCloud"
 
 __meta_linux_auto_updates() {
     case "$1" in
-        name) echo "Automatic Security Updates" ;;
-        id)   echo "linux_auto_updates" ;;
+        name)     echo "Automatic Security Updates" ;;
+        id)       echo "linux_auto_updates" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -4967,8 +5040,10 @@ esac
 
 __meta_linux_disk_encryption() {
     case "$1" in
-        name) echo "Disk Encryption" ;;
-        id)   echo "linux_disk_encryption" ;;
+        name)     echo "Disk Encryption" ;;
+        id)       echo "linux_disk_encryption" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5021,8 +5096,10 @@ fi
 
 __meta_linux_docker() {
     case "$1" in
-        name) echo "Docker Engine" ;;
-        id)   echo "linux_docker" ;;
+        name)     echo "Docker Engine" ;;
+        id)       echo "linux_docker" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5132,8 +5209,10 @@ esac
 
 __meta_linux_essentials() {
     case "$1" in
-        name) echo "Essential Packages" ;;
-        id)   echo "linux_essentials" ;;
+        name)     echo "Essential Packages" ;;
+        id)       echo "linux_essentials" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5250,8 +5329,10 @@ esac
 
 __meta_linux_fail2ban() {
     case "$1" in
-        name) echo "Fail2ban" ;;
-        id)   echo "linux_fail2ban" ;;
+        name)     echo "Fail2ban" ;;
+        id)       echo "linux_fail2ban" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5343,8 +5424,10 @@ esac
 
 __meta_linux_firewall() {
     case "$1" in
-        name) echo "Firewall (UFW)" ;;
-        id)   echo "linux_firewall" ;;
+        name)     echo "Firewall (UFW)" ;;
+        id)       echo "linux_firewall" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5438,8 +5521,10 @@ esac
 
 __meta_linux_network() {
     case "$1" in
-        name) echo "Network Configuration" ;;
-        id)   echo "linux_network" ;;
+        name)     echo "Network Configuration" ;;
+        id)       echo "linux_network" ;;
+        severity) echo "medium" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5487,8 +5572,10 @@ emit_pass "Network info displayed" "Network"
 
 __meta_linux_node() {
     case "$1" in
-        name) echo "Node.js" ;;
-        id)   echo "linux_node" ;;
+        name)     echo "Node.js" ;;
+        id)       echo "linux_node" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5591,8 +5678,10 @@ esac
 
 __meta_linux_open_ports() {
     case "$1" in
-        name) echo "Open Ports Audit" ;;
-        id)   echo "linux_open_ports" ;;
+        name)     echo "Open Ports Audit" ;;
+        id)       echo "linux_open_ports" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5658,8 +5747,10 @@ fi
 
 __meta_linux_ssh_hardening() {
     case "$1" in
-        name) echo "SSH Hardening" ;;
-        id)   echo "linux_ssh_hardening" ;;
+        name)     echo "SSH Hardening" ;;
+        id)       echo "linux_ssh_hardening" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5686,6 +5777,7 @@ sshd_config="/etc/ssh/sshd_config"
 
 if [ ! -f "$sshd_config" ]; then
     emit_warn "sshd_config not found at $sshd_config"
+    emit_evidence "sshd_config" "not found" "present" "$sshd_config"
     emit_fail "Cannot audit SSH configuration" "SSH Hardening"
 return 0
 fi
@@ -5694,9 +5786,12 @@ issues=0
 
 # Check PermitRootLogin
 root_login=$(grep -i "^PermitRootLogin" "$sshd_config" 2>/dev/null | awk '{print $2}' || echo "")
+root_login_source="$sshd_config"
 if [ -z "$root_login" ]; then
     root_login=$(grep -rhi "^PermitRootLogin" /etc/ssh/sshd_config.d/ 2>/dev/null | tail -1 | awk '{print $2}' || echo "")
+    [ -n "$root_login" ] && root_login_source="/etc/ssh/sshd_config.d/"
 fi
+emit_evidence "PermitRootLogin" "${root_login:-unset}" "no or prohibit-password" "$root_login_source"
 if [ "$root_login" = "no" ] || [ "$root_login" = "prohibit-password" ]; then
     emit_pass "PermitRootLogin = $root_login" "PermitRootLogin"
 else
@@ -5706,9 +5801,12 @@ fi
 
 # Check PasswordAuthentication
 pass_auth=$(grep -i "^PasswordAuthentication" "$sshd_config" 2>/dev/null | awk '{print $2}' || echo "")
+pass_auth_source="$sshd_config"
 if [ -z "$pass_auth" ]; then
     pass_auth=$(grep -rhi "^PasswordAuthentication" /etc/ssh/sshd_config.d/ 2>/dev/null | tail -1 | awk '{print $2}' || echo "")
+    [ -n "$pass_auth" ] && pass_auth_source="/etc/ssh/sshd_config.d/"
 fi
+emit_evidence "PasswordAuthentication" "${pass_auth:-unset}" "no" "$pass_auth_source"
 if [ "$pass_auth" = "no" ]; then
     emit_pass "PasswordAuthentication = no" "PasswordAuthentication"
 else
@@ -5719,18 +5817,22 @@ fi
 # Check X11Forwarding
 x11=$(grep -i "^X11Forwarding" "$sshd_config" 2>/dev/null | awk '{print $2}' || echo "")
 if [ "$x11" = "no" ]; then
+    emit_evidence "X11Forwarding" "$x11" "no" "$sshd_config"
     emit_pass "X11Forwarding = no" "X11Forwarding"
 elif [ "$x11" = "yes" ]; then
     issues=$((issues + 1))
+    emit_evidence "X11Forwarding" "$x11" "no" "$sshd_config"
     emit_fail "X11Forwarding is enabled -- should be 'no' on a headless server" "X11Forwarding"
 fi
 
 # Check MaxAuthTries
 max_auth=$(grep -i "^MaxAuthTries" "$sshd_config" 2>/dev/null | awk '{print $2}' || echo "")
 if [ -n "$max_auth" ] && [ "$max_auth" -le 3 ] 2>/dev/null; then
+    emit_evidence "MaxAuthTries" "$max_auth" "3 or less" "$sshd_config"
     emit_pass "MaxAuthTries = $max_auth" "MaxAuthTries"
 elif [ -n "$max_auth" ] && [ "$max_auth" -gt 6 ] 2>/dev/null; then
     issues=$((issues + 1))
+    emit_evidence "MaxAuthTries" "$max_auth" "3 or less" "$sshd_config"
     emit_fail "MaxAuthTries is $max_auth -- recommend 3 or less" "MaxAuthTries"
 fi
 
@@ -5788,8 +5890,10 @@ esac
 
 __meta_linux_unnecessary_services() {
     case "$1" in
-        name) echo "Unnecessary Services" ;;
-        id)   echo "linux_unnecessary_services" ;;
+        name)     echo "Unnecessary Services" ;;
+        id)       echo "linux_unnecessary_services" ;;
+        severity) echo "medium" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5894,8 +5998,10 @@ esac
 
 __meta_linux_user_account() {
     case "$1" in
-        name) echo "User Account" ;;
-        id)   echo "linux_user_account" ;;
+        name)     echo "User Account" ;;
+        id)       echo "linux_user_account" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -5966,8 +6072,10 @@ fi
 
 __meta_location_services() {
     case "$1" in
-        name) echo "Location Services" ;;
-        id)   echo "location_services" ;;
+        name)     echo "Location Services" ;;
+        id)       echo "location_services" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6046,8 +6154,10 @@ esac
 
 __meta_mdns_bonjour() {
     case "$1" in
-        name) echo "mDNS / Bonjour (OpenClaw Discovery)" ;;
-        id)   echo "mdns_bonjour" ;;
+        name)     echo "mDNS / Bonjour (OpenClaw Discovery)" ;;
+        id)       echo "mdns_bonjour" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6087,8 +6197,10 @@ fi
 
 __meta_native_openclaw() {
     case "$1" in
-        name) echo "OpenClaw (npm)" ;;
-        id)   echo "native_openclaw" ;;
+        name)     echo "OpenClaw (npm)" ;;
+        id)       echo "native_openclaw" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6149,7 +6261,8 @@ case "$REMEDIATION_ID" in
 
         # Run npm install with a 90-second timeout to prevent indefinite hangs.
         # macOS doesn't have the timeout command, so use a background job.
-        npm install -g openclaw@latest >"$HOME/.openclaw/npm-install.log" 2>&1 &
+        local tmplog="/tmp/clawkeeper-npm-install.$$.log"
+        npm install -g openclaw@latest >"$tmplog" 2>&1 &
         local npm_pid=$!
         local waited=0
         while kill -0 "$npm_pid" 2>/dev/null && [ $waited -lt 90 ]; do
@@ -6166,9 +6279,9 @@ case "$REMEDIATION_ID" in
         else
             wait "$npm_pid"
             npm_rc=$?
-            npm_output=$(cat "$HOME/.openclaw/npm-install.log" 2>/dev/null || echo "")
+            npm_output=$(cat "$tmplog" 2>/dev/null || echo "")
         fi
-        rm -f "$HOME/.openclaw/npm-install.log"
+        rm -f "$tmplog"
 
         # Retry with sudo if npm global prefix requires elevation.
         if [ $npm_rc -ne 0 ] && echo "$npm_output" | grep -qi "EACCES\|permission denied"; then
@@ -6202,8 +6315,10 @@ esac
 
 __meta_network_isolation() {
     case "$1" in
-        name) echo "Network Isolation" ;;
-        id)   echo "network_isolation" ;;
+        name)     echo "Network Isolation" ;;
+        id)       echo "network_isolation" ;;
+        severity) echo "medium" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6260,8 +6375,10 @@ emit_pass "Network info displayed for manual verification" "Network Isolation"
 
 __meta_node() {
     case "$1" in
-        name) echo "Node.js" ;;
-        id)   echo "node" ;;
+        name)     echo "Node.js" ;;
+        id)       echo "node" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6348,8 +6465,10 @@ esac
 
 __meta_openclaw_config() {
     case "$1" in
-        name) echo "OpenClaw Configuration Audit" ;;
-        id)   echo "openclaw_config" ;;
+        name)     echo "OpenClaw Configuration Audit" ;;
+        id)       echo "openclaw_config" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6376,7 +6495,7 @@ config_dir="$HOME/.openclaw"
 
 # ---------- Check config directory exists ----------
 if [ ! -d "$config_dir" ]; then
-    emit_info "No OpenClaw config directory found (~/.openclaw)"
+    emit_skipped "No OpenClaw config directory found (~/.openclaw)" "OpenClaw Config"
     emit_info "This is expected if OpenClaw isn't installed yet. Skipping config checks."
 return 0
 fi
@@ -6394,7 +6513,7 @@ fi
 
 # ---------- Check config file exists ----------
 if [ ! -f "$config_file" ]; then
-    emit_info "No openclaw.json found. Skipping config content checks."
+    emit_skipped "No openclaw.json found — config content checks skipped" "OpenClaw Config"
 return 0
 fi
 
@@ -6509,8 +6628,10 @@ esac
 
 __meta_openclaw_hardening() {
     case "$1" in
-        name) echo "OpenClaw Hardening Audit" ;;
-        id)   echo "openclaw_hardening" ;;
+        name)     echo "OpenClaw Hardening Audit" ;;
+        id)       echo "openclaw_hardening" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6534,7 +6655,7 @@ done
 config_file="$HOME/.openclaw/openclaw.json"
 
 if [ ! -f "$config_file" ]; then
-    emit_info "No openclaw.json found — skipping hardening checks"
+    emit_skipped "No openclaw.json found — hardening checks skipped" "OpenClaw Hardening"
 return 0
 fi
 
@@ -6594,8 +6715,10 @@ fi
 
 __meta_openclaw_running() {
     case "$1" in
-        name) echo "OpenClaw Instance Detection" ;;
-        id)   echo "openclaw_running" ;;
+        name)     echo "OpenClaw Instance Detection" ;;
+        id)       echo "openclaw_running" ;;
+        severity) echo "medium" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6622,7 +6745,13 @@ emit_info "Checking for running OpenClaw instances..."
 found=false
 
 # ---------- Check for OpenClaw Docker container ----------
-if command -v docker &>/dev/null && docker info &>/dev/null 2>&1; then
+docker_up=false
+if have_facts; then
+    [ "${CLAWKEEPER_FACT_DOCKER_RUNNING:-}" = "1" ] && docker_up=true
+elif command -v docker &>/dev/null && docker info &>/dev/null 2>&1; then
+    docker_up=true
+fi
+if [ "$docker_up" = true ]; then
     oc_containers=$(docker ps --format '{{.Names}} {{.Image}}' 2>/dev/null | grep -i "openclaw" || true)
     if [ -n "$oc_containers" ]; then
         found=true
@@ -6662,11 +6791,14 @@ if [ -n "$port_check" ]; then
         emit_pass "Gateway bound to localhost only" "OpenClaw Gateway"
     else
         emit_warn "Gateway binding could not be determined"
+        emit_skipped "Gateway binding not verified" "OpenClaw Gateway"
     fi
+elif [ "$found" = true ]; then
+    emit_skipped "Gateway port 18789 is not in use — binding not checked" "OpenClaw Gateway"
 fi
 
 if [ "$found" = false ]; then
-    emit_info "No running OpenClaw instance detected"
+    emit_skipped "No running OpenClaw instance detected" "OpenClaw Gateway"
     emit_info "This is expected if you haven't installed OpenClaw yet."
 fi
 }
@@ -6675,8 +6807,10 @@ fi
 
 __meta_openclaw_version() {
     case "$1" in
-        name) echo "OpenClaw Version & CVE Check" ;;
-        id)   echo "openclaw_version" ;;
+        name)     echo "OpenClaw Version & CVE Check" ;;
+        id)       echo "openclaw_version" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6709,7 +6843,9 @@ MIN_SAFE_VERSION="2026.1.29"
 
 # --- Detect OpenClaw version ---
 oc_version=""
-if command -v openclaw &>/dev/null; then
+if have_facts && [ -n "${CLAWKEEPER_FACT_OPENCLAW_VERSION:-}" ]; then
+    oc_version="$CLAWKEEPER_FACT_OPENCLAW_VERSION"
+elif command -v openclaw &>/dev/null; then
     oc_version=$(openclaw --version 2>/dev/null | grep -oE '[0-9]+\.[0-9]+\.[0-9]+' | head -1)
 elif command -v npx &>/dev/null; then
     oc_version=$(npx openclaw --version 2>/dev/null | grep -oE '[0-9]+\.[0-9]+\.[0-9]+' | head -1)
@@ -6779,8 +6915,10 @@ fi
 
 __meta_remote_login() {
     case "$1" in
-        name) echo "Remote Login" ;;
-        id)   echo "remote_login" ;;
+        name)     echo "Remote Login" ;;
+        id)       echo "remote_login" ;;
+        severity) echo "critical" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6831,8 +6969,10 @@ emit_skipped "Remote Login status unknown" "Remote Login"
 
 __meta_screen_sharing() {
     case "$1" in
-        name) echo "Screen Sharing" ;;
-        id)   echo "screen_sharing" ;;
+        name)     echo "Screen Sharing" ;;
+        id)       echo "screen_sharing" ;;
+        severity) echo "medium" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6875,8 +7015,10 @@ fi
 
 __meta_session_commands() {
     case "$1" in
-        name) echo "Session Rogue Commands" ;;
-        id)   echo "session_commands" ;;
+        name)     echo "Session Rogue Commands" ;;
+        id)       echo "session_commands" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -6891,7 +7033,7 @@ __check_session_commands() {
 
 sessions_dir="$HOME/.openclaw/agents"
 if [ ! -d "$sessions_dir" ]; then
-    emit_info "No agents directory found — skipping session command scan"
+    emit_skipped "No agents directory found — session command scan skipped" "Session Commands"
 return 0
 fi
 
@@ -6978,8 +7120,10 @@ fi
 
 __meta_siri() {
     case "$1" in
-        name) echo "Siri" ;;
-        id)   echo "siri" ;;
+        name)     echo "Siri" ;;
+        id)       echo "siri" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -7053,8 +7197,10 @@ esac
 
 __meta_skills_security() {
     case "$1" in
-        name) echo "Skills Security Audit" ;;
-        id)   echo "skills_security" ;;
+        name)     echo "Skills Security Audit" ;;
+        id)       echo "skills_security" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -7167,7 +7313,7 @@ for skills_dir in "${skills_dirs[@]}"; do
 done
 
 if [ "$found_skills" = false ]; then
-    emit_info "No skills directories found — skipping skills audit"
+    emit_skipped "No skills directories found — skills audit skipped" "Skills Audit"
     emit_info "Checked: ~/.openclaw/skills/ and ./skills/"
 fi
 }
@@ -7212,8 +7358,10 @@ esac
 
 __meta_soul_security() {
     case "$1" in
-        name) echo "SOUL.md Security Audit" ;;
-        id)   echo "soul_security" ;;
+        name)     echo "SOUL.md Security Audit" ;;
+        id)       echo "soul_security" ;;
+        severity) echo "high" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -7310,7 +7458,7 @@ for soul_file in "${soul_files[@]}"; do
 done
 
 if [ "$found_soul" = false ]; then
-    emit_info "No SOUL.md files found — skipping SOUL.md audit"
+    emit_skipped "No SOUL.md files found — SOUL.md audit skipped" "SOUL.md Audit"
     emit_info "Checked: ~/.openclaw/SOUL.md and ./SOUL.md"
 fi
 }
@@ -7345,8 +7493,10 @@ esac
 
 __meta_spotlight() {
     case "$1" in
-        name) echo "Spotlight Indexing" ;;
-        id)   echo "spotlight" ;;
+        name)     echo "Spotlight Indexing" ;;
+        id)       echo "spotlight" ;;
+        severity) echo "low" ;;
+        weight)   echo "" ;;
     esac
 }
 
@@ -7891,7 +8041,6 @@ main() {
                         setup_native_env_file
                         setup_openclaw_config
                         setup_native_launchd
-                        prompt_native_daemon_start
                     else
                         echo ""
                         warn "Node.js is not available — cannot install OpenClaw"
@@ -7931,7 +8080,6 @@ main() {
                 setup_native_env_file
                 setup_openclaw_config
                 setup_native_launchd
-                prompt_native_daemon_start
             else
                 echo ""
                 warn "Node.js is not available — cannot deploy OpenClaw"
@@ -7987,8 +8135,6 @@ main() {
     _compact_flush
     print_phase_summary
 
-    print_install_summary
-
     # Final report
     print_report
     save_report
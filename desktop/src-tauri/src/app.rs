@@ -0,0 +1,350 @@
+//! The desktop app: Tauri commands over the scan engine.
+
+use std::sync::Mutex;
+
+use tauri::ipc::Channel;
+use tauri::Manager;
+
+use crate::types::{
+    CatalogListing, CheckMeta, FrameworkCoverage, GradingPolicy, PhaseInfo, ProfileDef, ScanDiff,
+    ScanEvent, ScanListing, ScanRecord, ScanSelection,
+};
+use crate::{
+    check_catalog, check_runner, compliance, deploy, drift, export, facts, history, platform,
+    remediation, scoring, selection, waivers,
+};
+
+/// Shared scan state: the cancellation handle for the scan in flight, if
+/// any, and the last completed scan so remediations can update it.
+#[derive(Default)]
+struct ScanControl {
+    cancel: Mutex<Option<check_runner::CancelToken>>,
+    last_scan: Mutex<Option<ScanRecord>>,
+}
+
+/// Holds the in-flight scan's cancel token in `ScanControl` until dropped,
+/// so the slot is freed however `start_scan` returns.
+struct RunningScan<'a>(&'a Mutex<Option<check_runner::CancelToken>>);
+
+impl<'a> RunningScan<'a> {
+    /// Claim the slot for `cancel`; fails if another scan holds it.
+    fn claim(
+        slot: &'a Mutex<Option<check_runner::CancelToken>>,
+        cancel: &check_runner::CancelToken,
+    ) -> Result<Self, String> {
+        let mut current = slot.lock().unwrap();
+        if current.is_some() {
+            return Err("A scan is already running".to_string());
+        }
+        *current = Some(cancel.clone());
+        Ok(RunningScan(slot))
+    }
+}
+
+impl Drop for RunningScan<'_> {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().take();
+    }
+}
+
+#[tauri::command]
+async fn start_scan(
+    app: tauri::AppHandle,
+    concurrency: Option<usize>,
+    timeout_secs: Option<u64>,
+    strict: Option<bool>,
+    selection: Option<ScanSelection>,
+    on_event: Channel<ScanEvent>,
+) -> Result<(), String> {
+    // One scan at a time: a second would orphan the first one's cancel token
+    let control = app.state::<ScanControl>();
+    let cancel = check_runner::CancelToken::default();
+    let _running = RunningScan::claim(&control.cancel, &cancel)?;
+
+    let base = check_catalog::resolve_resource_base(&app)?;
+    let host = platform::detect_platform();
+    // One broken check.toml shouldn't stop the whole scan
+    let (catalog, skipped) = check_catalog::load_catalog_lenient(&base, &host)?;
+    let profiles = selection::load_profiles(&base)?;
+    let checks =
+        selection::select_checks(catalog.clone(), &selection.unwrap_or_default(), &profiles)?;
+    let phases = check_catalog::load_phases(&base)?;
+    let phases = check_catalog::build_phase_list(&checks, &phases, &host);
+    let grading = scoring::load_policy(&base)?;
+    let accepted_risks = waivers::load_default()?;
+    let facts = facts::gather(&host).await;
+    let started = history::now();
+
+    on_event
+        .send(ScanEvent::ScanStarted {
+            platform: host.clone(),
+            facts: facts.clone(),
+            checks: checks.clone(),
+            phases: phases.clone(),
+        })
+        .map_err(|e| format!("Channel send error: {}", e))?;
+    for problem in skipped {
+        on_event
+            .send(ScanEvent::CatalogWarning {
+                path: problem.path,
+                message: problem.message,
+            })
+            .map_err(|e| format!("Channel send error: {}", e))?;
+    }
+
+    let mut options = check_runner::ScanOptions {
+        grading: grading.clone(),
+        waivers: accepted_risks,
+        ..Default::default()
+    };
+    if let Some(concurrency) = concurrency {
+        options.concurrency = concurrency;
+    }
+    if let Some(timeout_secs) = timeout_secs {
+        options.default_timeout_secs = timeout_secs;
+    }
+    if let Some(strict) = strict {
+        options.strict_protocol = strict;
+    }
+
+    let result = check_runner::run_scan(
+        base,
+        checks,
+        phases.clone(),
+        &facts,
+        options,
+        cancel.clone(),
+        &on_event,
+    )
+    .await;
+
+    // Only completed scans are kept; a cancelled one has partial results
+    let record = match result {
+        Ok(results) if !cancel.is_cancelled() => Some(history::build_record(
+            started, host, results, catalog, phases, &grading,
+        )),
+        Ok(_) => None,
+        Err(e) => {
+            control.last_scan.lock().unwrap().take();
+            return Err(e);
+        }
+    };
+
+    if let Some(ref record) = record {
+        history::save_scan(&history::history_dir(&app)?, record)?;
+    }
+    *control.last_scan.lock().unwrap() = record;
+    Ok(())
+}
+
+#[tauri::command]
+async fn cancel_scan(app: tauri::AppHandle) -> Result<(), String> {
+    let control = app.state::<ScanControl>();
+    let cancel = control.cancel.lock().unwrap().clone();
+    match cancel {
+        Some(cancel) => {
+            cancel.cancel();
+            Ok(())
+        }
+        None => Err("No scan is running".into()),
+    }
+}
+
+#[tauri::command]
+async fn run_remediation(
+    app: tauri::AppHandle,
+    check_id: String,
+    remediation_id: String,
+    on_event: Channel<ScanEvent>,
+) -> Result<(), String> {
+    // A scan in flight would read the host while the remediation changes it
+    if app.state::<ScanControl>().cancel.lock().unwrap().is_some() {
+        return Err("A scan is running; remediate once it has finished".to_string());
+    }
+    let base = check_catalog::resolve_resource_base(&app)?;
+    let (checks, skipped) =
+        check_catalog::load_catalog_lenient(&base, &platform::detect_platform())?;
+    let check = checks.iter().find(|c| c.id == check_id).ok_or_else(|| {
+        match skipped
+            .iter()
+            .find(|p| p.check_id.as_deref() == Some(&check_id))
+        {
+            Some(problem) => problem.message.clone(),
+            None => format!("Unknown check: {}", check_id),
+        }
+    })?;
+    let grading = scoring::load_policy(&base)?;
+
+    let verified = remediation::remediate(&base, check, &remediation_id, &on_event).await?;
+
+    // Fold the verified result into the last scan, re-save it and
+    // re-send the tally
+    let updated = {
+        let control = app.state::<ScanControl>();
+        let mut last_scan = control.last_scan.lock().unwrap();
+        last_scan.as_mut().map(|record| {
+            let existing = record
+                .checks
+                .iter_mut()
+                .find(|r| r.check_id == verified.check_id);
+            match existing {
+                Some(existing) => *existing = verified.clone(),
+                None => record.checks.push(verified.clone()),
+            }
+            record.summary = scoring::summarize(&record.checks, &grading);
+            record.clone()
+        })
+    };
+
+    // Without a scan in this session there is no tally to update; the
+    // re-run's CheckCompleted already carries the verified result
+    let Some(record) = updated else {
+        return Ok(());
+    };
+    history::save_scan(&history::history_dir(&app)?, &record)?;
+    on_event
+        .send(ScanEvent::ScanCompleted(record.summary))
+        .map_err(|e| format!("Channel send error: {}", e))
+}
+
+#[tauri::command]
+async fn list_scans(app: tauri::AppHandle) -> Result<Vec<ScanListing>, String> {
+    history::list_scans(&history::history_dir(&app)?)
+}
+
+#[tauri::command]
+async fn get_scan(app: tauri::AppHandle, id: String) -> Result<ScanRecord, String> {
+    history::load_scan(&history::history_dir(&app)?, &id)
+}
+
+#[tauri::command]
+async fn delete_scan(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    history::delete_scan(&history::history_dir(&app)?, &id)
+}
+
+/// Scan id that refers to the last scan run in this session rather than
+/// one loaded from history.
+const LIVE_SCAN_ID: &str = "live";
+
+/// The catalog and phases `record` ran with. Scans saved before those were
+/// recorded get today's catalog instead, loaded as leniently as a scan
+/// loads it; the scan already reported what it had to skip.
+fn scan_catalog(
+    app: &tauri::AppHandle,
+    record: &ScanRecord,
+) -> Result<(Vec<CheckMeta>, Vec<PhaseInfo>), String> {
+    if !record.catalog.is_empty() {
+        return Ok((record.catalog.clone(), record.phases.clone()));
+    }
+    let base = check_catalog::resolve_resource_base(app)?;
+    let (checks, _) = check_catalog::load_catalog_lenient(&base, &record.platform)?;
+    let phases = check_catalog::load_phases(&base)?;
+    let phases = check_catalog::build_phase_list(&checks, &phases, &record.platform);
+    Ok((checks, phases))
+}
+
+fn resolve_scan(app: &tauri::AppHandle, id: &str) -> Result<ScanRecord, String> {
+    if id == LIVE_SCAN_ID {
+        return app
+            .state::<ScanControl>()
+            .last_scan
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| "No scan has completed in this session".to_string());
+    }
+    history::load_scan(&history::history_dir(app)?, id)
+}
+
+/// Compare two scans by id; either may be "live" for the current session.
+#[tauri::command]
+async fn diff_scans(app: tauri::AppHandle, a: String, b: String) -> Result<ScanDiff, String> {
+    let from = resolve_scan(&app, &a)?;
+    let to = resolve_scan(&app, &b)?;
+    Ok(drift::diff_scans(&from, &to))
+}
+
+/// Render a scan (by id, or "live") as SARIF, JUnit XML or HTML and return the document.
+#[tauri::command]
+async fn export_scan(app: tauri::AppHandle, id: String, format: String) -> Result<String, String> {
+    let record = resolve_scan(&app, &id)?;
+    let (checks, phases) = scan_catalog(&app, &record)?;
+    export::render(&format, &record, &checks, &phases)
+}
+
+/// How a scan (by id, or "live") covers each compliance framework in
+/// checks/frameworks.toml.
+#[tauri::command]
+async fn get_compliance(
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<Vec<FrameworkCoverage>, String> {
+    let record = resolve_scan(&app, &id)?;
+    let (checks, _) = scan_catalog(&app, &record)?;
+    let base = check_catalog::resolve_resource_base(&app)?;
+    let frameworks = compliance::load_frameworks(&base)?;
+    Ok(compliance::coverage(&record, &checks, &frameworks))
+}
+
+/// The grading policy scans are scored with, so the UI can explain grades.
+#[tauri::command]
+async fn get_grading_policy(app: tauri::AppHandle) -> Result<GradingPolicy, String> {
+    let base = check_catalog::resolve_resource_base(&app)?;
+    scoring::load_policy(&base)
+}
+
+/// The checks a scan would load on this host, with the check.toml files it
+/// would skip.
+#[tauri::command]
+async fn get_catalog(app: tauri::AppHandle) -> Result<CatalogListing, String> {
+    let base = check_catalog::resolve_resource_base(&app)?;
+    let (checks, problems) =
+        check_catalog::load_catalog_lenient(&base, &platform::detect_platform())?;
+    Ok(CatalogListing { checks, problems })
+}
+
+/// The scan profiles from checks/profiles.toml, for the profile picker.
+#[tauri::command]
+async fn list_profiles(app: tauri::AppHandle) -> Result<Vec<ProfileDef>, String> {
+    let base = check_catalog::resolve_resource_base(&app)?;
+    selection::load_profiles(&base)
+}
+
+#[tauri::command]
+async fn detect_openclaw() -> Result<deploy::OpenClawStatus, String> {
+    Ok(deploy::detect_openclaw().await)
+}
+
+#[tauri::command]
+async fn start_deploy(
+    mode: String,
+    api_key: Option<String>,
+    on_event: Channel<deploy::DeployEvent>,
+) -> Result<(), String> {
+    deploy::run_deploy(&mode, api_key, &on_event).await
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
+        .manage(ScanControl::default())
+        .invoke_handler(tauri::generate_handler![
+            start_scan,
+            cancel_scan,
+            run_remediation,
+            list_scans,
+            get_scan,
+            delete_scan,
+            diff_scans,
+            export_scan,
+            get_compliance,
+            get_grading_policy,
+            get_catalog,
+            list_profiles,
+            detect_openclaw,
+            start_deploy,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
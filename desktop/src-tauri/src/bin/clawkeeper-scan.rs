@@ -0,0 +1,753 @@
+//! Headless scanner: runs the same catalog and runner as the desktop app,
+//! writing events to the terminal (or as JSON lines) instead of a Tauri
+//! channel. Intended for servers and CI where there is no GUI.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use clawkeeper_desktop_lib::check_runner::{self, CancelToken, EventSink, ScanOptions};
+use clawkeeper_desktop_lib::gates::ScanGates;
+use clawkeeper_desktop_lib::types::{
+    CatalogProblem, CheckResult, HostFacts, ScanEvent, ScanSelection,
+};
+use clawkeeper_desktop_lib::{catalog_lint, check_catalog};
+use clawkeeper_desktop_lib::{
+    compliance, export, facts, history, platform, protocol, scoring, selection, waivers,
+};
+
+const USAGE: &str = "\
+Usage: clawkeeper-scan [options]
+       clawkeeper-scan validate [options]
+       clawkeeper-scan lint [--base <dir>] [--format <text|json>]
+
+Commands:
+  validate               Run every check and report output that breaks the
+                         check protocol instead of scanning (exit 1 if any);
+                         checks this host can't run are listed as not
+                         validated
+  lint                   Check every check.toml and check directory for
+                         problems without running anything (exit 1 if any)
+
+Options:
+  --format <text|json>   Output format (default: text)
+  --base <dir>           Directory containing checks/ and lib/
+  --concurrency <n>      Checks to run at once within a phase
+  --timeout <secs>       Default per-check timeout
+  --strict               Report check output that breaks the protocol
+  --lenient              Skip checks with a broken check.toml instead of
+                         failing
+  --waivers <file>       Accepted risks to report as WAIVED (default:
+                         ~/.clawkeeper/waivers.toml)
+  -v, --verbose          Also print info messages (text format)
+  -h, --help             Show this help
+
+Selecting checks (each repeatable; a check must match every kind given):
+  --profile <id>         Run a profile from checks/profiles.toml (quick,
+                         full, server, workstation, ci)
+  --check <id>           Only this check
+  --phase <id>           Only checks in this phase
+  --tag <tag>            Only checks with this tag
+  --severity <level>     Only checks of this severity (critical, high,
+                         medium or low)
+  --platform <name>      Only checks whose check.toml platform is this
+                         (macos, linux or all)
+  --exclude <id|tag>     Leave out this check, or checks with this tag
+
+Reports (written after the scan, before gates are evaluated):
+  --sarif <file>         Write findings as SARIF 2.1.0
+  --junit <file>         Write results as JUnit XML
+  --html <file>          Write a standalone HTML report
+  --compliance <file>    Write control coverage for each framework in
+                         checks/frameworks.toml as JSON (and, in text
+                         format, print a summary)
+
+CI gates (exit 1 if any is not met):
+  --gates <file>         Load gates from a TOML file
+  --min-score <n>        Fail if the score is below n
+  --min-grade <grade>    Fail if the grade is worse than this (A-F)
+  --fail-on-tag <tag>    Fail if any check with this tag or id fails
+                         (repeatable)
+  --fail-on-severity <level>
+                         Fail if any check of this severity fails
+                         (repeatable)
+
+Extra checks are read from each directory in $CLAWKEEPER_CHECK_PATH
+(separated by ':'), then ~/.clawkeeper/checks, then the bundled checks/.
+A check replaces any check with the same id later in that list.
+
+Exit status: 0 on success, 1 if a gate failed (or validate or lint found
+problems), 2 on error, 130 if interrupted.
+";
+
+/// Exit statuses for CI.
+const EXIT_GATE_FAILED: i32 = 1;
+const EXIT_VIOLATIONS: i32 = 1;
+const EXIT_LINT_FAILED: i32 = 1;
+const EXIT_ERROR: i32 = 2;
+const EXIT_INTERRUPTED: i32 = 130;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Command {
+    Scan,
+    /// Lint check output rather than scan
+    Validate,
+    /// Lint the catalog without running checks
+    Lint,
+}
+
+struct Args {
+    command: Command,
+    format: Format,
+    /// Skip broken check.toml files rather than failing
+    lenient: bool,
+    base: Option<PathBuf>,
+    verbose: bool,
+    options: ScanOptions,
+    selection: ScanSelection,
+    gates: ScanGates,
+    /// Waiver file to use instead of ~/.clawkeeper/waivers.toml
+    waivers: Option<PathBuf>,
+    /// (format, path) pairs to write once the scan completes
+    exports: Vec<(String, PathBuf)>,
+    /// Where to write compliance coverage, if anywhere
+    compliance: Option<PathBuf>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        command: Command::Scan,
+        format: Format::Text,
+        lenient: false,
+        base: None,
+        verbose: false,
+        options: ScanOptions::default(),
+        selection: ScanSelection::default(),
+        gates: ScanGates::default(),
+        waivers: None,
+        exports: Vec::new(),
+        compliance: None,
+    };
+    // Flags override the gates file regardless of argument order
+    let mut gates_file = None;
+    let mut flag_gates = ScanGates::default();
+
+    let mut iter = std::env::args().skip(1).peekable();
+    match iter.peek().map(String::as_str) {
+        Some("validate") => args.command = Command::Validate,
+        Some("lint") => args.command = Command::Lint,
+        _ => {}
+    }
+    if args.command != Command::Scan {
+        iter.next();
+    }
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| {
+            iter.next()
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match arg.as_str() {
+            "--format" => {
+                args.format = match value("--format")?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    other => return Err(format!("Unknown format: {}", other)),
+                }
+            }
+            "--base" => args.base = Some(PathBuf::from(value("--base")?)),
+            "--concurrency" => {
+                args.options.concurrency = value("--concurrency")?
+                    .parse()
+                    .map_err(|_| "--concurrency must be a number".to_string())?
+            }
+            "--timeout" => {
+                args.options.default_timeout_secs = value("--timeout")?
+                    .parse()
+                    .map_err(|_| "--timeout must be a number of seconds".to_string())?
+            }
+            "--strict" => args.options.strict_protocol = true,
+            "--lenient" => args.lenient = true,
+            "--profile" => args.selection.profile = Some(value("--profile")?),
+            "--check" => args.selection.filter.checks.push(value("--check")?),
+            "--phase" => args.selection.filter.phases.push(value("--phase")?),
+            "--tag" => args.selection.filter.tags.push(value("--tag")?),
+            "--severity" => args.selection.filter.severities.push(value("--severity")?),
+            "--platform" => args.selection.filter.platforms.push(value("--platform")?),
+            "--exclude" => args.selection.filter.exclude.push(value("--exclude")?),
+            "--waivers" => args.waivers = Some(PathBuf::from(value("--waivers")?)),
+            "--sarif" => args
+                .exports
+                .push(("sarif".into(), PathBuf::from(value("--sarif")?))),
+            "--junit" => args
+                .exports
+                .push(("junit".into(), PathBuf::from(value("--junit")?))),
+            "--html" => args
+                .exports
+                .push(("html".into(), PathBuf::from(value("--html")?))),
+            "--compliance" => args.compliance = Some(PathBuf::from(value("--compliance")?)),
+            "--gates" => gates_file = Some(PathBuf::from(value("--gates")?)),
+            "--min-score" => {
+                flag_gates.min_score = Some(
+                    value("--min-score")?
+                        .parse()
+                        .map_err(|_| "--min-score must be a number".to_string())?,
+                )
+            }
+            "--min-grade" => flag_gates.min_grade = Some(value("--min-grade")?.to_uppercase()),
+            "--fail-on-tag" => flag_gates.fail_on_tags.push(value("--fail-on-tag")?),
+            "--fail-on-severity" => flag_gates
+                .fail_on_severities
+                .push(value("--fail-on-severity")?),
+            "-v" | "--verbose" => args.verbose = true,
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                std::process::exit(0);
+            }
+            other => return Err(format!("Unknown option: {}", other)),
+        }
+    }
+
+    if let Some(path) = gates_file {
+        args.gates = ScanGates::load(&path)?;
+    }
+    if flag_gates.min_score.is_some() {
+        args.gates.min_score = flag_gates.min_score;
+    }
+    if flag_gates.min_grade.is_some() {
+        args.gates.min_grade = flag_gates.min_grade;
+    }
+    args.gates.fail_on_tags.extend(flag_gates.fail_on_tags);
+    args.gates
+        .fail_on_severities
+        .extend(flag_gates.fail_on_severities);
+
+    Ok(args)
+}
+
+/// Discards events; `validate` reports from the results instead.
+struct QuietSink;
+
+impl EventSink for QuietSink {
+    fn emit(&self, _event: ScanEvent) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Writes each event as one JSON line, the same shape the frontend receives.
+struct JsonSink;
+
+impl EventSink for JsonSink {
+    fn emit(&self, event: ScanEvent) -> Result<(), String> {
+        let line = serde_json::to_string(&event)
+            .map_err(|e| format!("Failed to serialize event: {}", e))?;
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", line).map_err(|e| format!("Failed to write output: {}", e))
+    }
+}
+
+fn status_icon(status: &str) -> &'static str {
+    match status {
+        "PASS" | "FIXED" => "✓",
+        "FAIL" => "✗",
+        "SKIPPED" => "○",
+        "WAIVED" => "~",
+        _ => "!",
+    }
+}
+
+/// One line on what the scan found out about the host, e.g.
+/// "Docker running · OpenClaw 2026.1.29 (native, running) · listening on 22, 631".
+fn describe_facts(facts: &HostFacts) -> String {
+    let mut parts = Vec::new();
+    parts.push(
+        match (facts.docker, facts.docker_running) {
+            (true, true) => "Docker running",
+            (true, false) => "Docker stopped",
+            _ => "no Docker",
+        }
+        .to_string(),
+    );
+    parts.push(match &facts.openclaw_install {
+        Some(install) => format!(
+            "OpenClaw {}({}, {})",
+            facts
+                .openclaw_version
+                .as_ref()
+                .map(|v| format!("{} ", v))
+                .unwrap_or_default(),
+            install,
+            if facts.openclaw_running {
+                "running"
+            } else {
+                "stopped"
+            }
+        ),
+        None if facts.openclaw => "OpenClaw config only".to_string(),
+        None => "no OpenClaw".to_string(),
+    });
+    if !facts.listening_ports.is_empty() {
+        let ports: Vec<String> = facts.listening_ports.iter().map(u16::to_string).collect();
+        parts.push(format!("listening on {}", ports.join(", ")));
+    }
+    parts.join(" · ")
+}
+
+/// Human-readable output modelled on the bash CLI.
+struct TextSink {
+    verbose: bool,
+    /// Check names by id, filled in from `ScanStarted`
+    names: Mutex<Vec<(String, String)>>,
+    /// Findings of the check in progress, printed under it once it completes
+    findings: Mutex<Vec<(String, String, String)>>,
+}
+
+impl TextSink {
+    fn name_of(&self, check_id: &str) -> String {
+        self.names
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(id, _)| id == check_id)
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| check_id.to_string())
+    }
+}
+
+impl EventSink for TextSink {
+    fn emit(&self, event: ScanEvent) -> Result<(), String> {
+        let line = match event {
+            ScanEvent::ScanStarted {
+                platform,
+                facts,
+                checks,
+                ..
+            } => {
+                let host = platform.distro_name.unwrap_or(platform.os);
+                let mut line = format!(
+                    "Clawkeeper Security Scan — {} ({} checks)",
+                    host,
+                    checks.len()
+                );
+                if self.verbose {
+                    line.push_str(&format!("\n  Host: {}", describe_facts(&facts)));
+                }
+                // Say where any checks not shipped with clawkeeper came from
+                for check in checks.iter().filter(|c| c.origin.source != "bundled") {
+                    line.push_str(&format!("\n  + {} from {}", check.id, check.origin.dir));
+                    if !check.origin.shadows.is_empty() {
+                        line.push_str(&format!(" (replaces {})", check.origin.shadows.join(", ")));
+                    }
+                }
+                *self.names.lock().unwrap() = checks.into_iter().map(|c| (c.id, c.name)).collect();
+                line
+            }
+            ScanEvent::CatalogWarning { path, message } => {
+                format!("  ⚠ Skipped {}: {}", path, message)
+            }
+            ScanEvent::PhaseStarted { phase_label, .. } => {
+                format!("\n═══ {} ═══", phase_label)
+            }
+            ScanEvent::Info { message, .. } if self.verbose => format!("    → {}", message),
+            ScanEvent::Warn { message, .. } => format!("    ⚠ {}", message),
+            ScanEvent::Error { check_id, message } => {
+                format!("    ! {}: {}", self.name_of(&check_id), message)
+            }
+            ScanEvent::ProtocolViolation { line, message, .. } => {
+                format!("    ⚠ protocol, line {}: {}", line, message)
+            }
+            ScanEvent::Diagnostic { stderr, .. } if self.verbose && !stderr.is_empty() => stderr
+                .lines()
+                .map(|l| format!("    stderr: {}", l))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ScanEvent::Finding {
+                name,
+                status,
+                detail,
+                ..
+            } => {
+                self.findings.lock().unwrap().push((status, name, detail));
+                return Ok(());
+            }
+            ScanEvent::CheckCompleted {
+                check_name,
+                status,
+                detail,
+                evidence,
+                ..
+            } => {
+                let mut line = format!("  {} {} — {}", status_icon(&status), check_name, detail);
+                // A single finding is already the check's own line
+                let findings = std::mem::take(&mut *self.findings.lock().unwrap());
+                if findings.len() > 1 {
+                    for (status, name, detail) in findings {
+                        line.push_str(&format!(
+                            "\n      {} {}: {}",
+                            status_icon(&status),
+                            name,
+                            detail
+                        ));
+                    }
+                }
+                // Evidence explains failures; passing checks only show it in verbose
+                if self.verbose || status == "FAIL" {
+                    for evidence in &evidence {
+                        line.push_str(&format!(
+                            "\n      evidence: {}",
+                            export::describe_evidence(evidence)
+                        ));
+                    }
+                }
+                line
+            }
+            ScanEvent::ScanCompleted(summary) => {
+                let mut line = format!(
+                    "\nSecurity Grade: {} ({:.0}%)\n  Passed: {}  Failed: {}  Fixed: {}  Skipped: {}  Waived: {}  Errors: {}",
+                    summary.grade,
+                    summary.score,
+                    summary.passed,
+                    summary.failed,
+                    summary.fixed,
+                    summary.skipped,
+                    summary.waived,
+                    summary.errors
+                );
+                if !summary.critical_failures.is_empty() {
+                    let names: Vec<String> = summary
+                        .critical_failures
+                        .iter()
+                        .map(|id| self.name_of(id))
+                        .collect();
+                    line.push_str(&format!("\n  Critical failures: {}", names.join(", ")));
+                }
+                line
+            }
+            ScanEvent::GatesEvaluated { passed, violations } => {
+                let mut line = format!("\nCI gates: {}", if passed { "passed" } else { "FAILED" });
+                for violation in violations {
+                    line.push_str(&format!("\n  ✗ [{}] {}", violation.gate, violation.message));
+                }
+                line
+            }
+            ScanEvent::ScanCancelled { completed, total } => {
+                format!(
+                    "\nScan cancelled ({} of {} checks completed)",
+                    completed, total
+                )
+            }
+            _ => return Ok(()),
+        };
+
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{}", line).map_err(|e| format!("Failed to write output: {}", e))
+    }
+}
+
+/// Run the scan and return the process exit status.
+async fn run(args: Args) -> Result<i32, String> {
+    let base = match args.base {
+        Some(base) => base,
+        None => check_catalog::resolve_headless_base()?,
+    };
+    if args.command == Command::Lint {
+        return report_problems(&catalog_lint::lint_catalog(&base)?, args.format);
+    }
+
+    let host = platform::detect_platform();
+    let (catalog, skipped) = if args.lenient {
+        check_catalog::load_catalog_lenient(&base, &host)?
+    } else {
+        (check_catalog::load_catalog(&base, &host)?, Vec::new())
+    };
+    let profiles = selection::load_profiles(&base)?;
+    let checks = selection::select_checks(catalog.clone(), &args.selection, &profiles)?;
+    let phases = check_catalog::load_phases(&base)?;
+    let phases = check_catalog::build_phase_list(&checks, &phases, &host);
+    let mut options = args.options;
+    options.grading = scoring::load_policy(&base)?;
+    options.strict_protocol |= args.command == Command::Validate;
+    options.waivers = match args.waivers {
+        // An explicitly named file has to exist
+        Some(ref path) if !path.exists() => {
+            return Err(format!("Waiver file not found: {}", path.display()))
+        }
+        Some(ref path) => waivers::load_waivers(path)?,
+        None => waivers::load_default()?,
+    };
+    let grading = options.grading.clone();
+    let facts = facts::gather(&host).await;
+    let started = history::now();
+
+    let sink: Box<dyn EventSink> = match args.format {
+        _ if args.command == Command::Validate => Box::new(QuietSink),
+        Format::Json => Box::new(JsonSink),
+        Format::Text => Box::new(TextSink {
+            verbose: args.verbose,
+            names: Mutex::new(Vec::new()),
+            findings: Mutex::new(Vec::new()),
+        }),
+    };
+
+    sink.emit(ScanEvent::ScanStarted {
+        platform: host.clone(),
+        facts: facts.clone(),
+        checks: checks.clone(),
+        phases: phases.clone(),
+    })?;
+    for problem in skipped {
+        sink.emit(ScanEvent::CatalogWarning {
+            path: problem.path,
+            message: problem.message,
+        })?;
+    }
+
+    // Ctrl-C cancels in-flight checks cleanly instead of orphaning them
+    let cancel = CancelToken::default();
+    let on_interrupt = cancel.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            on_interrupt.cancel();
+        }
+    });
+
+    let results = check_runner::run_scan(
+        base.clone(),
+        checks.clone(),
+        phases.clone(),
+        &facts,
+        options,
+        cancel.clone(),
+        sink.as_ref(),
+    )
+    .await?;
+
+    if cancel.is_cancelled() {
+        return Ok(EXIT_INTERRUPTED);
+    }
+    if args.command == Command::Validate {
+        // Checks the scan couldn't run here: other platforms' checks that
+        // the selection would pick, checks skipped for unmet requires, and
+        // checks that timed out before their output could be judged
+        let profile = args
+            .selection
+            .profile
+            .as_ref()
+            .and_then(|id| profiles.iter().find(|p| &p.id == id));
+        let mut not_validated: Vec<(String, String)> = check_catalog::load_excluded(&base, &host)?
+            .into_iter()
+            .filter(|c| profile.is_none_or(|p| selection::matches(&p.filter, c)))
+            .filter(|c| selection::matches(&args.selection.filter, c))
+            .map(|check| {
+                let reason = format!("platform \"{}\" doesn't match this host", check.platform);
+                (check.id, reason)
+            })
+            .collect();
+        not_validated.extend(checks.iter().filter_map(|check| {
+            facts::unmet(&facts, &check.requires).map(|reason| (check.id.clone(), reason))
+        }));
+        not_validated.extend(
+            results
+                .iter()
+                .filter(|r| r.status == "TIMEOUT")
+                .map(|r| (r.check_id.clone(), r.detail.to_lowercase())),
+        );
+        return report_violations(&results, &not_validated, args.format);
+    }
+
+    let record = history::build_record(started, host, results, catalog, phases, &grading);
+    if args.format == Format::Text {
+        report_waivers(&record.checks)?;
+    }
+    for (format, path) in &args.exports {
+        let document = export::render(format, &record, &record.catalog, &record.phases)?;
+        std::fs::write(path, document)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+    if let Some(path) = &args.compliance {
+        let frameworks = compliance::load_frameworks(&base)?;
+        let coverage = compliance::coverage(&record, &record.catalog, &frameworks);
+        let document = serde_json::to_string_pretty(&coverage)
+            .map_err(|e| format!("Failed to serialize compliance coverage: {}", e))?;
+        std::fs::write(path, document)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        if args.format == Format::Text {
+            let mut lines: Vec<String> = coverage
+                .iter()
+                .map(|c| format!("  {}", compliance::describe(c)))
+                .collect();
+            if lines.is_empty() {
+                lines.push("  No framework applies to this host".to_string());
+            }
+            let mut stdout = std::io::stdout().lock();
+            writeln!(stdout, "\nCompliance:\n{}", lines.join("\n"))
+                .map_err(|e| format!("Failed to write output: {}", e))?;
+        }
+    }
+
+    if args.gates.is_empty() {
+        return Ok(0);
+    }
+
+    let violations = args
+        .gates
+        .evaluate(&record.summary, &record.checks, &checks)?;
+    let passed = violations.is_empty();
+    sink.emit(ScanEvent::GatesEvaluated { passed, violations })?;
+
+    Ok(if passed { 0 } else { EXIT_GATE_FAILED })
+}
+
+/// List the accepted risks behind WAIVED results, apart from the findings.
+fn report_waivers(results: &[CheckResult]) -> Result<(), String> {
+    let lines: Vec<String> = results
+        .iter()
+        .flat_map(|result| {
+            result
+                .waivers
+                .iter()
+                .map(|waiver| format!("  ~ {}: {}", result.check_name, waivers::describe(waiver)))
+        })
+        .collect();
+    if lines.is_empty() {
+        return Ok(());
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    writeln!(
+        stdout,
+        "\nAccepted risks (left out of the score):\n{}",
+        lines.join("\n")
+    )
+    .map_err(|e| format!("Failed to write output: {}", e))
+}
+
+/// Print catalog problems as `path: message`; exit 1 if there are any.
+fn report_problems(problems: &[CatalogProblem], format: Format) -> Result<i32, String> {
+    let mut stdout = std::io::stdout().lock();
+    for problem in problems {
+        let line = match format {
+            Format::Json => serde_json::to_string(problem)
+                .map_err(|e| format!("Failed to serialize problem: {}", e))?,
+            Format::Text => format!("{}: {}", problem.path, problem.message),
+        };
+        writeln!(stdout, "{}", line).map_err(|e| format!("Failed to write output: {}", e))?;
+    }
+
+    if format == Format::Text {
+        let summary = match problems.len() {
+            0 => "Catalog OK".to_string(),
+            1 => "\n1 problem".to_string(),
+            n => format!("\n{} problems", n),
+        };
+        writeln!(stdout, "{}", summary).map_err(|e| format!("Failed to write output: {}", e))?;
+    }
+
+    Ok(if problems.is_empty() {
+        0
+    } else {
+        EXIT_LINT_FAILED
+    })
+}
+
+/// Print each check's protocol violations; exit 1 if there are any.
+fn report_violations(
+    results: &[CheckResult],
+    not_validated: &[(String, String)],
+    format: Format,
+) -> Result<i32, String> {
+    let mut stdout = std::io::stdout().lock();
+    let mut failing = 0;
+    let validated: Vec<&CheckResult> = results
+        .iter()
+        .filter(|r| !not_validated.iter().any(|(id, _)| *id == r.check_id))
+        .collect();
+    for result in &validated {
+        if !result.protocol_violations.is_empty() {
+            failing += 1;
+        }
+        let report = match format {
+            Format::Json => serde_json::json!({
+                "check_id": result.check_id,
+                "violations": result.protocol_violations,
+            })
+            .to_string(),
+            Format::Text => {
+                let mut report = format!(
+                    "  {} {}",
+                    if result.protocol_violations.is_empty() {
+                        "✓"
+                    } else {
+                        "✗"
+                    },
+                    result.check_id
+                );
+                for violation in &result.protocol_violations {
+                    report.push_str(&format!(
+                        "\n      line {}: {}",
+                        violation.line, violation.message
+                    ));
+                }
+                report
+            }
+        };
+        writeln!(stdout, "{}", report).map_err(|e| format!("Failed to write output: {}", e))?;
+    }
+    for (check_id, reason) in not_validated {
+        let report = match format {
+            Format::Json => serde_json::json!({
+                "check_id": check_id,
+                "not_validated": reason,
+            })
+            .to_string(),
+            Format::Text => format!("  - {} not validated: {}", check_id, reason),
+        };
+        writeln!(stdout, "{}", report).map_err(|e| format!("Failed to write output: {}", e))?;
+    }
+
+    if format == Format::Text {
+        let mut summary = format!(
+            "\n{} of {} checks follow protocol v{}",
+            validated.len() - failing,
+            validated.len(),
+            protocol::PROTOCOL_VERSION
+        );
+        if !not_validated.is_empty() {
+            summary.push_str(&format!(
+                " ({} not validated on this host)",
+                not_validated.len()
+            ));
+        }
+        writeln!(stdout, "{}", summary).map_err(|e| format!("Failed to write output: {}", e))?;
+    }
+
+    Ok(if failing == 0 { 0 } else { EXIT_VIOLATIONS })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("clawkeeper-scan: {}\n\n{}", e, USAGE);
+            std::process::exit(EXIT_ERROR);
+        }
+    };
+
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("failed to start async runtime");
+
+    match runtime.block_on(run(args)) {
+        Ok(code) => std::process::exit(code),
+        Err(e) => {
+            eprintln!("clawkeeper-scan: {}", e);
+            std::process::exit(EXIT_ERROR);
+        }
+    }
+}
@@ -0,0 +1,585 @@
+//! Lints every check in every check root, whatever its platform, and
+//! reports all problems at once rather than stopping at the first bad
+//! check.toml.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::types::{CatalogProblem, CheckMeta, PhaseDef};
+use crate::{check_catalog, compliance, facts, plan, scoring, selection};
+
+/// Keys check.toml may set; anything else is most likely a typo.
+const KNOWN_KEYS: [&str; 15] = [
+    "id",
+    "name",
+    "phase",
+    "platform",
+    "description",
+    "requires_sudo",
+    "order",
+    "exclusive",
+    "timeout_secs",
+    "tags",
+    "severity",
+    "weight",
+    "requires",
+    "after",
+    "controls",
+];
+
+const SEVERITIES: [&str; 4] = ["critical", "high", "medium", "low"];
+
+/// Lint the catalog under `base`, plus any user and $CLAWKEEPER_CHECK_PATH
+/// checks. Returns every problem found, root by root in directory order;
+/// an empty list means the catalog is clean.
+pub fn lint_catalog(base: &Path) -> Result<Vec<CatalogProblem>, String> {
+    let checks_dir = base.join("checks");
+    let mut problems = Vec::new();
+    // Without phases.toml, every phase would be reported as unknown
+    let phases = match check_catalog::load_phases(base) {
+        Ok(phases) => Some(phases),
+        Err(message) => {
+            let path = checks_dir.join("phases.toml").display().to_string();
+            // load_phases names the file already
+            let message = message.trim_start_matches(&format!("{}: ", path));
+            problems.push(CatalogProblem {
+                path: path.clone(),
+                check_id: None,
+                message: message.to_string(),
+            });
+            None
+        }
+    };
+
+    // Checks that would actually run, for the order check: a check shadowed
+    // by one with the same id in a higher root is left out
+    let mut checks: Vec<(PathBuf, CheckMeta)> = Vec::new();
+    for root in check_catalog::check_roots(base) {
+        let mut dirs: Vec<_> = std::fs::read_dir(&root.dir)
+            .map_err(|e| format!("Failed to read {}: {}", root.dir.display(), e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+
+        let mut root_ids: BTreeMap<String, PathBuf> = BTreeMap::new();
+        for dir in &dirs {
+            let bundled = root.source == "bundled";
+            let Some(meta) = lint_check_dir(dir, bundled, phases.as_deref(), &mut problems) else {
+                continue;
+            };
+            let toml_path = dir.join("check.toml");
+            if let Some(first) = root_ids.get(&meta.id) {
+                problems.push(CatalogProblem {
+                    path: toml_path.display().to_string(),
+                    check_id: Some(meta.id.clone()),
+                    message: format!("id is also used by {}", first.display()),
+                });
+                continue;
+            }
+            root_ids.insert(meta.id.clone(), dir.clone());
+            if !checks.iter().any(|(_, other)| other.id == meta.id) {
+                checks.push((toml_path, meta));
+            }
+        }
+    }
+    lint_orders(&checks, &mut problems);
+    lint_after(&checks, phases.as_deref(), &mut problems);
+    lint_profiles(base, &checks, phases.as_deref(), &mut problems);
+    lint_controls(base, &checks, &mut problems);
+    lint_grading(base, &mut problems);
+
+    Ok(problems)
+}
+
+/// Lint one check directory, returning its metadata if check.toml parsed.
+/// `bundled` is whether it's in the bundled checks/ root.
+fn lint_check_dir(
+    dir: &Path,
+    bundled: bool,
+    phases: Option<&[PhaseDef]>,
+    problems: &mut Vec<CatalogProblem>,
+) -> Option<CheckMeta> {
+    let dir_name = dir.file_name()?.to_string_lossy().to_string();
+    let toml_path = dir.join("check.toml");
+    let mut problem = |path: &Path, check_id: Option<&str>, message: String| {
+        problems.push(CatalogProblem {
+            path: path.display().to_string(),
+            check_id: check_id.map(str::to_string),
+            message,
+        });
+    };
+
+    if !toml_path.exists() {
+        problem(dir, None, "missing check.toml".to_string());
+        return None;
+    }
+
+    let content = match std::fs::read_to_string(&toml_path) {
+        Ok(content) => content,
+        Err(e) => {
+            problem(&toml_path, None, format!("Failed to read: {}", e));
+            return None;
+        }
+    };
+
+    // Parse loosely first so unknown keys can be reported alongside a
+    // schema error in the same file
+    if let Ok(table) = content.parse::<toml::Table>() {
+        for key in table.keys() {
+            if !KNOWN_KEYS.contains(&key.as_str()) {
+                problem(&toml_path, None, format!("unknown key \"{}\"", key));
+            }
+        }
+    }
+
+    let meta: CheckMeta = match toml::from_str(&content) {
+        Ok(meta) => meta,
+        Err(e) => {
+            problem(
+                &toml_path,
+                None,
+                format!("Failed to parse: {}", e.message()),
+            );
+            return None;
+        }
+    };
+    let id = Some(meta.id.as_str());
+
+    if meta.id != dir_name {
+        problem(
+            &toml_path,
+            id,
+            format!(
+                "id \"{}\" does not match directory \"{}\"",
+                meta.id, dir_name
+            ),
+        );
+    }
+    if phases.is_some_and(|phases| !phases.iter().any(|p| p.id == meta.phase)) {
+        problem(
+            &toml_path,
+            id,
+            format!("phase \"{}\" is not defined in phases.toml", meta.phase),
+        );
+    }
+    if !SEVERITIES.contains(&meta.severity.as_str()) {
+        problem(
+            &toml_path,
+            id,
+            format!(
+                "severity must be critical, high, medium or low, not \"{}\"",
+                meta.severity
+            ),
+        );
+    }
+    for tag in &meta.tags {
+        if SEVERITIES.contains(&tag.as_str()) {
+            problem(
+                &toml_path,
+                id,
+                format!(
+                    "tag \"{}\" duplicates severity; set severity = \"{}\" instead",
+                    tag, tag
+                ),
+            );
+        }
+    }
+    if meta.timeout_secs == Some(0) {
+        problem(
+            &toml_path,
+            id,
+            "timeout_secs must be at least 1".to_string(),
+        );
+    }
+    if meta.weight.is_some_and(|w| w < 0.0) {
+        problem(&toml_path, id, "weight must not be negative".to_string());
+    }
+    for name in &meta.requires {
+        if !facts::REQUIREMENTS.iter().any(|r| r.fact == name) {
+            problem(
+                &toml_path,
+                id,
+                format!("requires unknown fact \"{}\"", name),
+            );
+        }
+    }
+
+    let script = dir.join("check.sh");
+    match std::fs::read_to_string(&script) {
+        Ok(source) => {
+            if source.contains("emit_prompt") && !dir.join("remediate.sh").exists() {
+                problem(
+                    &script,
+                    id,
+                    "offers a remediation (emit_prompt) but has no remediate.sh".to_string(),
+                );
+            }
+        }
+        Err(_) => problem(dir, id, "missing check.sh".to_string()),
+    }
+
+    // ../../lib only exists beside the bundled checks
+    if !bundled {
+        for name in ["check.sh", "remediate.sh"] {
+            let script = dir.join(name);
+            let Ok(source) = std::fs::read_to_string(&script) else {
+                continue;
+            };
+            if sources_relative_lib(&source) {
+                problem(
+                    &script,
+                    id,
+                    "sources ../../lib, which only exists in the bundled checks; \
+                     use source \"${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}\""
+                        .to_string(),
+                );
+            }
+        }
+    }
+
+    Some(meta)
+}
+
+/// Whether a script sources anything by a path relative to the repo's
+/// lib/, outside comments. A fallback for an unset $CLAWKEEPER_HELPERS,
+/// as in `source "${CLAWKEEPER_HELPERS:-$SCRIPT_DIR/../../lib/helpers.sh}"`,
+/// is fine.
+fn sources_relative_lib(source: &str) -> bool {
+    source.lines().map(str::trim_start).any(|line| {
+        !line.starts_with('#')
+            && (line.starts_with("source ") || line.starts_with(". "))
+            && line.contains("../../lib")
+            && !line.contains("${CLAWKEEPER_HELPERS:-")
+    })
+}
+
+/// `after` must name other checks in the same phase, without a cycle. One
+/// in an earlier phase has already finished, so naming it does nothing.
+fn lint_after(
+    checks: &[(PathBuf, CheckMeta)],
+    phases: Option<&[PhaseDef]>,
+    problems: &mut Vec<CatalogProblem>,
+) {
+    let phase_order = |id: &str| {
+        phases
+            .unwrap_or_default()
+            .iter()
+            .find(|p| p.id == id)
+            .map(|p| p.order)
+    };
+    for (path, meta) in checks {
+        for dep in &meta.after {
+            let message = match checks.iter().find(|(_, other)| &other.id == dep) {
+                None => format!("after names unknown check \"{}\"", dep),
+                Some((_, other)) if other.id == meta.id => "after names itself".to_string(),
+                Some((_, other)) => match (phase_order(&meta.phase), phase_order(&other.phase)) {
+                    (Some(own), Some(theirs)) if theirs > own => {
+                        format!("after names {}, which is in a later phase", dep)
+                    }
+                    (Some(own), Some(theirs)) if theirs < own => format!(
+                        "after names {}, which is in an earlier phase and always finishes first",
+                        dep
+                    ),
+                    _ => continue,
+                },
+            };
+            problems.push(CatalogProblem {
+                path: path.display().to_string(),
+                check_id: Some(meta.id.clone()),
+                message,
+            });
+        }
+    }
+
+    let all: Vec<&CheckMeta> = checks.iter().map(|(_, meta)| meta).collect();
+    if let Some(cycle) = plan::find_cycle(&all) {
+        if let Some((path, meta)) = checks.iter().find(|(_, meta)| meta.id == cycle[0]) {
+            problems.push(CatalogProblem {
+                path: path.display().to_string(),
+                check_id: Some(meta.id.clone()),
+                message: format!("after forms a cycle: {}", cycle.join(" → ")),
+            });
+        }
+    }
+}
+
+/// Orders decide run order, so two checks that can run on the same host
+/// must not share one.
+fn lint_orders(checks: &[(PathBuf, CheckMeta)], problems: &mut Vec<CatalogProblem>) {
+    let mut by_order: BTreeMap<u32, Vec<&(PathBuf, CheckMeta)>> = BTreeMap::new();
+    for check in checks {
+        by_order.entry(check.1.order).or_default().push(check);
+    }
+
+    for (order, group) in by_order {
+        for (i, (path, meta)) in group.iter().enumerate() {
+            let clashes: Vec<&str> = group[..i]
+                .iter()
+                .filter(|(_, other)| platforms_overlap(&meta.platform, &other.platform))
+                .map(|(_, other)| other.id.as_str())
+                .collect();
+            if !clashes.is_empty() {
+                problems.push(CatalogProblem {
+                    path: path.display().to_string(),
+                    check_id: Some(meta.id.clone()),
+                    message: format!("order {} is also used by {}", order, clashes.join(", ")),
+                });
+            }
+        }
+    }
+}
+
+/// Every id, phase and tag a profile in checks/profiles.toml names must
+/// exist, or the profile silently selects less than intended.
+/// Every grading.toml problem the desktop app would refuse to load with,
+/// such as thresholds out of order.
+fn lint_grading(base: &Path, problems: &mut Vec<CatalogProblem>) {
+    if let Err(message) = scoring::load_policy(base) {
+        let path = base
+            .join("checks")
+            .join("grading.toml")
+            .display()
+            .to_string();
+        problems.push(CatalogProblem {
+            path: path.clone(),
+            check_id: None,
+            message: message
+                .trim_start_matches(&format!("{}: ", path))
+                .to_string(),
+        });
+    }
+}
+
+fn lint_profiles(
+    base: &Path,
+    checks: &[(PathBuf, CheckMeta)],
+    phases: Option<&[PhaseDef]>,
+    problems: &mut Vec<CatalogProblem>,
+) {
+    let path = base
+        .join("checks")
+        .join("profiles.toml")
+        .display()
+        .to_string();
+    let profiles = match selection::load_profiles(base) {
+        Ok(profiles) => profiles,
+        Err(message) => {
+            problems.push(CatalogProblem {
+                path: path.clone(),
+                check_id: None,
+                message: message
+                    .trim_start_matches(&format!("{}: ", path))
+                    .to_string(),
+            });
+            return;
+        }
+    };
+
+    let is_check = |id: &str| checks.iter().any(|(_, c)| c.id == id);
+    let is_tag = |tag: &str| checks.iter().any(|(_, c)| c.tags.iter().any(|t| t == tag));
+    for profile in &profiles {
+        let filter = &profile.filter;
+        let unknown = filter
+            .checks
+            .iter()
+            .filter(|id| !is_check(id))
+            .map(|id| format!("unknown check \"{}\"", id))
+            .chain(
+                filter
+                    .phases
+                    .iter()
+                    .filter(|id| phases.is_some_and(|phases| !phases.iter().any(|p| &p.id == *id)))
+                    .map(|id| format!("unknown phase \"{}\"", id)),
+            )
+            .chain(
+                filter
+                    .tags
+                    .iter()
+                    .filter(|tag| !is_tag(tag))
+                    .map(|tag| format!("no check has tag \"{}\"", tag)),
+            )
+            .chain(
+                filter
+                    .severities
+                    .iter()
+                    .filter(|s| !SEVERITIES.contains(&s.as_str()))
+                    .map(|s| format!("unknown severity \"{}\"", s)),
+            )
+            .chain(
+                filter
+                    .exclude
+                    .iter()
+                    .filter(|e| !is_check(e) && !is_tag(e))
+                    .map(|e| format!("excludes \"{}\", which is neither a check nor a tag", e)),
+            );
+        for message in unknown {
+            problems.push(CatalogProblem {
+                path: path.clone(),
+                check_id: None,
+                message: format!("profile \"{}\": {}", profile.id, message),
+            });
+        }
+    }
+}
+
+/// Every control a check.toml maps must be listed in checks/frameworks.toml,
+/// or the check silently counts towards nothing, and a finding it maps a
+/// control to must be one the check reports.
+fn lint_controls(base: &Path, checks: &[(PathBuf, CheckMeta)], problems: &mut Vec<CatalogProblem>) {
+    let frameworks = match compliance::load_frameworks(base) {
+        Ok(frameworks) => frameworks,
+        Err(message) => {
+            let path = base
+                .join("checks")
+                .join("frameworks.toml")
+                .display()
+                .to_string();
+            problems.push(CatalogProblem {
+                path: path.clone(),
+                check_id: None,
+                message: message
+                    .trim_start_matches(&format!("{}: ", path))
+                    .to_string(),
+            });
+            return;
+        }
+    };
+
+    for (path, meta) in checks {
+        for (framework_id, mapping) in &meta.controls {
+            let Some(framework) = frameworks.iter().find(|f| &f.id == framework_id) else {
+                problems.push(CatalogProblem {
+                    path: path.display().to_string(),
+                    check_id: Some(meta.id.clone()),
+                    message: format!("controls names unknown framework \"{}\"", framework_id),
+                });
+                continue;
+            };
+            for (control_id, finding) in compliance::mapped_controls(mapping) {
+                // A prompt's finding is named after the check; anything
+                // else has to be a status line's name in check.sh
+                if let Some(finding) = finding.filter(|f| *f != meta.name) {
+                    let script = path.with_file_name("check.sh");
+                    let named = |source: String| finding_names(&source).any(|n| n == finding);
+                    if std::fs::read_to_string(&script).is_ok_and(|s| !named(s)) {
+                        problems.push(CatalogProblem {
+                            path: path.display().to_string(),
+                            check_id: Some(meta.id.clone()),
+                            message: format!(
+                                "control \"{}\" maps to finding \"{}\", which check.sh never names",
+                                control_id, finding
+                            ),
+                        });
+                    }
+                }
+                if !framework.controls.iter().any(|c| c.id == control_id) {
+                    problems.push(CatalogProblem {
+                        path: path.display().to_string(),
+                        check_id: Some(meta.id.clone()),
+                        message: format!(
+                            "control \"{}\" is not listed for {} in frameworks.toml",
+                            control_id, framework_id
+                        ),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Whether one host could match both platforms. Anything other than
+/// "macos" or "all" is Linux (a distro id or family).
+fn platforms_overlap(a: &str, b: &str) -> bool {
+    a == "all" || b == "all" || a == b || (a != "macos" && b != "macos")
+}
+
+/// The finding names check.sh gives its status lines: the second argument
+/// of each emit_pass, emit_fail or emit_skipped call, outside comments.
+fn finding_names(source: &str) -> impl Iterator<Item = String> + '_ {
+    source.lines().map(without_comment).flat_map(|line| {
+        ["emit_pass ", "emit_fail ", "emit_skipped "]
+            .into_iter()
+            .flat_map(move |call| line.match_indices(call).map(move |(at, _)| at + call.len()))
+            .filter_map(move |args| shell_words(&line[args..]).nth(1))
+    })
+}
+
+/// `line` up to its comment: a `#` starting a word outside quotes.
+fn without_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut word_start = true;
+    for (at, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') if word_start => return &line[..at],
+            _ => {}
+        }
+        word_start = quote.is_none() && c.is_whitespace();
+    }
+    line
+}
+
+/// Split the start of a command line into words, unquoting "..." and '...'
+/// and stopping at the first word that ends the command.
+fn shell_words(line: &str) -> impl Iterator<Item = String> + '_ {
+    let mut rest = line;
+    std::iter::from_fn(move || {
+        rest = rest.trim_start();
+        let (word, end) = match rest.chars().next()? {
+            quote @ ('"' | '\'') => {
+                let close = rest[1..].find(quote)? + 1;
+                (rest[1..close].to_string(), close + 1)
+            }
+            ';' | '|' | '&' | '#' => return None,
+            _ => {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || matches!(c, ';' | '|' | '&'))
+                    .unwrap_or(rest.len());
+                (rest[..end].to_string(), end)
+            }
+        };
+        rest = &rest[end..];
+        Some(word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finding_names_take_the_second_argument() {
+        let source = r#"
+emit_pass "UFW firewall is active" "Firewall"
+if [ "$x" = no ]; then emit_fail 'Root login allowed' PermitRootLogin; fi
+emit_skipped "Not installed" "Docker" "extra"
+emit_pass "No finding name"
+# emit_fail "Commented out" "Ghost"
+echo done # emit_fail "Trailing comment" "Ghost"
+"#;
+        let names: Vec<String> = finding_names(source).collect();
+        assert_eq!(names, ["Firewall", "PermitRootLogin", "Docker"]);
+    }
+
+    #[test]
+    fn shell_words_stop_at_the_end_of_the_command() {
+        let words: Vec<String> = shell_words(r#""a b" 'c' d;e f"#).collect();
+        assert_eq!(words, ["a b", "c", "d"]);
+        let words: Vec<String> = shell_words("x && y").collect();
+        assert_eq!(words, ["x"]);
+    }
+
+    #[test]
+    fn comments_start_a_word_outside_quotes() {
+        assert_eq!(without_comment("  # all comment"), "  ");
+        assert_eq!(without_comment("echo a # b"), "echo a ");
+        assert_eq!(
+            without_comment(r#"echo "a # b" c#d"#),
+            r#"echo "a # b" c#d"#
+        );
+        assert_eq!(without_comment("echo ${#var}"), "echo ${#var}");
+    }
+}
@@ -1,34 +1,74 @@
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-use tauri::Manager;
+use serde::Deserialize;
 
-use crate::types::{CheckMeta, PhaseInfo};
+use crate::platform;
+use crate::types::{CatalogProblem, CheckMeta, CheckOrigin, PhaseDef, PhaseInfo, PlatformInfo};
 
-/// Phase display labels
-fn phase_label(phase_id: &str) -> &str {
-    match phase_id {
-        "host_hardening" => "Host Hardening",
-        "network" => "Network",
-        "prerequisites" => "Prerequisites",
-        "security_audit" => "Security Audit",
-        _ => phase_id,
-    }
+/// The top level of checks/phases.toml
+#[derive(Deserialize)]
+struct PhaseFile {
+    #[serde(default)]
+    phase: Vec<PhaseDef>,
 }
 
-/// Phase sort order
-fn phase_order(phase_id: &str) -> u32 {
-    match phase_id {
-        "host_hardening" => 1,
-        "network" => 2,
-        "prerequisites" => 3,
-        "security_audit" => 4,
-        _ => 99,
+/// Load the phase definitions from checks/phases.toml. An id may appear
+/// more than once, but only with different platforms.
+pub fn load_phases(base: &Path) -> Result<Vec<PhaseDef>, String> {
+    let path = base.join("checks").join("phases.toml");
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file: PhaseFile = toml::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    let mut seen = HashSet::new();
+    for phase in &file.phase {
+        if !seen.insert((phase.id.as_str(), phase.platform.as_deref())) {
+            return Err(format!(
+                "{}: phase \"{}\" is defined more than once for platform \"{}\"",
+                path.display(),
+                phase.id,
+                phase.platform.as_deref().unwrap_or("all")
+            ));
+        }
     }
+
+    Ok(file.phase)
+}
+
+/// The definition of `phase_id` that applies to `host`, preferring one
+/// written for the host's platform over a general one.
+pub fn find_phase<'a>(
+    phases: &'a [PhaseDef],
+    phase_id: &str,
+    host: &PlatformInfo,
+) -> Option<&'a PhaseDef> {
+    let mut candidates = phases.iter().filter(|p| p.id == phase_id);
+    candidates
+        .clone()
+        .find(|p| {
+            p.platform
+                .as_deref()
+                .is_some_and(|platform| platform::matches_platform(platform, host))
+        })
+        .or_else(|| candidates.find(|p| p.platform.is_none()))
+}
+
+/// Display label for a phase, falling back to its id.
+pub(crate) fn phase_label<'a>(phases: &'a [PhaseInfo], phase_id: &'a str) -> &'a str {
+    phases
+        .iter()
+        .find(|p| p.id == phase_id)
+        .map_or(phase_id, |p| p.label.as_str())
 }
 
 /// Resolve the base resource directory containing checks/ and lib/.
 /// In dev mode, falls back to the repo root via CARGO_MANIFEST_DIR.
+#[cfg(feature = "desktop")]
 pub fn resolve_resource_base(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    use tauri::Manager;
+
     // Try Tauri resource resolver first (production bundle)
     if let Ok(resource_path) = app
         .path()
@@ -43,7 +83,39 @@ pub fn resolve_resource_base(app: &tauri::AppHandle) -> Result<PathBuf, String>
         }
     }
 
-    // Dev mode fallback: CARGO_MANIFEST_DIR/../../ → repo root
+    // Dev mode fallback
+    dev_resource_base()
+}
+
+/// Resolve the resource base outside the Tauri app (headless binary).
+/// Tries $CLAWKEEPER_HOME, then the executable's directory and its parent,
+/// then the dev-mode repo root.
+pub fn resolve_headless_base() -> Result<PathBuf, String> {
+    let mut candidates = Vec::new();
+    if let Some(home) = std::env::var_os("CLAWKEEPER_HOME") {
+        candidates.push(PathBuf::from(home));
+    }
+    if let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        candidates.push(exe_dir.clone());
+        if let Some(parent) = exe_dir.parent() {
+            candidates.push(parent.to_path_buf());
+        }
+    }
+
+    for candidate in candidates {
+        if candidate.join("checks").exists() {
+            return Ok(candidate);
+        }
+    }
+
+    dev_resource_base()
+}
+
+/// Dev mode: CARGO_MANIFEST_DIR/../../ → repo root
+fn dev_resource_base() -> Result<PathBuf, String> {
     let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let repo_root = manifest_dir
         .parent()
@@ -61,64 +133,208 @@ pub fn resolve_resource_base(app: &tauri::AppHandle) -> Result<PathBuf, String>
     }
 }
 
-/// Load all check.toml files, filter to macOS-compatible checks, sort by order.
-pub fn load_catalog(base: &PathBuf) -> Result<Vec<CheckMeta>, String> {
+/// Load all check.toml files from every check root (see `check_roots`),
+/// filter to checks for the host platform, sort by order.
+/// Fails on the first check.toml that can't be read or parsed.
+pub fn load_catalog(base: &Path, host: &PlatformInfo) -> Result<Vec<CheckMeta>, String> {
+    read_catalog(base, host, true, false).map(|(checks, _)| checks)
+}
+
+/// Like `load_catalog`, but skips checks whose check.toml can't be read or
+/// parsed, returning them as problems instead.
+pub fn load_catalog_lenient(
+    base: &Path,
+    host: &PlatformInfo,
+) -> Result<(Vec<CheckMeta>, Vec<CatalogProblem>), String> {
+    read_catalog(base, host, true, true)
+}
+
+/// The checks `load_catalog_lenient` leaves out because their platform
+/// doesn't match the host.
+pub fn load_excluded(base: &Path, host: &PlatformInfo) -> Result<Vec<CheckMeta>, String> {
+    read_catalog(base, host, false, true).map(|(checks, _)| checks)
+}
+
+/// Extra check directories, separated like $PATH.
+pub const CHECK_PATH_VAR: &str = "CLAWKEEPER_CHECK_PATH";
+
+/// A directory of check directories.
+pub struct CheckRoot {
+    /// "env", "user" or "bundled"; recorded in each check's origin
+    pub source: &'static str,
+    pub dir: PathBuf,
+}
+
+/// Every check root, highest precedence first: each entry of
+/// $CLAWKEEPER_CHECK_PATH, then ~/.clawkeeper/checks, then the bundled
+/// checks/. Extra roots that don't exist are left out.
+pub fn check_roots(base: &Path) -> Vec<CheckRoot> {
+    let mut roots = Vec::new();
+    if let Some(paths) = std::env::var_os(CHECK_PATH_VAR) {
+        for dir in std::env::split_paths(&paths) {
+            roots.push(CheckRoot { source: "env", dir });
+        }
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        roots.push(CheckRoot {
+            source: "user",
+            dir: PathBuf::from(home).join(".clawkeeper").join("checks"),
+        });
+    }
+    roots.retain(|root| root.dir.is_dir());
+    roots.push(CheckRoot {
+        source: "bundled",
+        dir: base.join("checks"),
+    });
+    roots
+}
+
+/// `on_host` picks the checks for the host's platform, or else the rest.
+fn read_catalog(
+    base: &Path,
+    host: &PlatformInfo,
+    on_host: bool,
+    lenient: bool,
+) -> Result<(Vec<CheckMeta>, Vec<CatalogProblem>), String> {
     let checks_dir = base.join("checks");
     if !checks_dir.exists() {
         return Err(format!("checks/ directory not found at {}", checks_dir.display()));
     }
 
-    let mut checks = Vec::new();
+    let mut checks: Vec<CheckMeta> = Vec::new();
+    let mut skipped = Vec::new();
+    // Ids claimed by a check.toml that couldn't be loaded: copies in lower
+    // roots stay shadowed rather than running in its place
+    let mut broken: Vec<(String, String)> = Vec::new();
 
-    let entries = std::fs::read_dir(&checks_dir)
-        .map_err(|e| format!("Failed to read checks/: {}", e))?;
+    for root in check_roots(base) {
+        let mut entries: Vec<PathBuf> = std::fs::read_dir(&root.dir)
+            .map_err(|e| format!("Failed to read {}: {}", root.dir.display(), e))?
+            .map(|entry| {
+                entry
+                    .map(|e| e.path())
+                    .map_err(|e| format!("Failed to read dir entry: {}", e))
+            })
+            .collect::<Result<_, _>>()?;
+        // Sorted so that which of two same-id checks in one root wins
+        // doesn't depend on the filesystem
+        entries.sort();
 
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read dir entry: {}", e))?;
-        let path = entry.path();
-
-        if !path.is_dir() {
-            continue;
-        }
-
-        let toml_path = path.join("check.toml");
-        if !toml_path.exists() {
-            continue;
-        }
+        for path in entries {
+            if !path.is_dir() {
+                continue;
+            }
 
-        let content = std::fs::read_to_string(&toml_path)
-            .map_err(|e| format!("Failed to read {}: {}", toml_path.display(), e))?;
+            let toml_path = path.join("check.toml");
+            if !toml_path.exists() {
+                continue;
+            }
 
-        let meta: CheckMeta = toml::from_str(&content)
-            .map_err(|e| format!("Failed to parse {}: {}", toml_path.display(), e))?;
+            let dir = path.display().to_string();
+            let content = std::fs::read_to_string(&toml_path)
+                .map_err(|e| format!("Failed to read {}: {}", toml_path.display(), e));
+            let meta: Result<CheckMeta, String> =
+                content.as_deref().map_err(Clone::clone).and_then(|content| {
+                    toml::from_str(content)
+                        .map_err(|e| format!("Failed to parse {}: {}", toml_path.display(), e))
+                });
+            let mut meta = match meta {
+                Ok(meta) => meta,
+                Err(message) if lenient => {
+                    let id = claimed_id(content.as_deref().ok(), &path);
+                    // Roots come highest precedence first, so an id already
+                    // loaded or claimed shadows this one
+                    let shadowed = checks.iter().any(|c| c.id == id)
+                        || broken.iter().any(|(other, _)| *other == id);
+                    if !shadowed {
+                        broken.push((id.clone(), dir));
+                    }
+                    skipped.push(CatalogProblem {
+                        path: toml_path.display().to_string(),
+                        check_id: Some(id),
+                        message,
+                    });
+                    continue;
+                }
+                Err(message) => return Err(message),
+            };
 
-        // Filter: only include checks for macOS or "all" platforms
-        if meta.platform == "macos" || meta.platform == "all" {
+            if let Some(winner) = checks.iter_mut().find(|c| c.id == meta.id) {
+                winner.origin.shadows.push(dir);
+                continue;
+            }
+            if let Some((_, broken_dir)) = broken.iter().find(|(id, _)| *id == meta.id) {
+                skipped.push(CatalogProblem {
+                    path: toml_path.display().to_string(),
+                    check_id: Some(meta.id.clone()),
+                    message: format!(
+                        "not loaded: shadowed by {}, which failed to load",
+                        broken_dir
+                    ),
+                });
+                continue;
+            }
+            meta.origin = CheckOrigin {
+                source: root.source.to_string(),
+                dir,
+                shadows: Vec::new(),
+            };
             checks.push(meta);
         }
     }
 
+    // Filter: checks for this host's platform (or "all"), or the rest. Only
+    // after shadowing, so an override for another platform still hides the
+    // check it overrides
+    checks.retain(|meta| platform::matches_platform(&meta.platform, host) == on_host);
+
     // Sort by order field
     checks.sort_by_key(|c| c.order);
 
-    Ok(checks)
+    Ok((checks, skipped))
 }
 
-/// Build ordered list of phases from the catalog.
-pub fn build_phase_list(checks: &[CheckMeta]) -> Vec<PhaseInfo> {
-    let mut seen = std::collections::HashSet::new();
-    let mut phases = Vec::new();
+/// The id a check.toml that failed to load claims: its `id` key if the file
+/// is valid TOML, else the directory name, which bundled checks match.
+fn claimed_id(content: Option<&str>, dir: &Path) -> String {
+    content
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .and_then(|table| table.get("id")?.as_str().map(str::to_string))
+        .unwrap_or_else(|| {
+            let name = dir.file_name().unwrap_or_default();
+            name.to_string_lossy().into_owned()
+        })
+}
+
+/// Build ordered list of phases from the catalog. Phases missing from
+/// `phases` are labelled with their id and run last.
+pub fn build_phase_list(
+    checks: &[CheckMeta],
+    phases: &[PhaseDef],
+    host: &PlatformInfo,
+) -> Vec<PhaseInfo> {
+    let mut seen = HashSet::new();
+    let mut list = Vec::new();
 
     for check in checks {
         if seen.insert(check.phase.clone()) {
-            phases.push(PhaseInfo {
-                id: check.phase.clone(),
-                label: phase_label(&check.phase).to_string(),
-                order: phase_order(&check.phase),
+            list.push(match find_phase(phases, &check.phase, host) {
+                Some(def) => PhaseInfo {
+                    id: def.id.clone(),
+                    label: def.label.clone(),
+                    order: def.order,
+                    description: def.description.clone(),
+                },
+                None => PhaseInfo {
+                    id: check.phase.clone(),
+                    label: check.phase.clone(),
+                    order: u32::MAX,
+                    description: String::new(),
+                },
             });
         }
     }
 
-    phases.sort_by_key(|p| p.order);
-    phases
+    list.sort_by_key(|p| p.order);
+    list
 }
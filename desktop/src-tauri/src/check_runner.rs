@@ -1,228 +1,968 @@
-use std::path::PathBuf;
-
-use tauri::ipc::Channel;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::Command;
-
-use crate::types::{CheckMeta, CheckOutputLine, PhaseInfo, ScanEvent};
-
-/// Compute letter grade from score percentage.
-fn compute_grade(score: f64) -> String {
-    if score >= 90.0 {
-        "A"
-    } else if score >= 80.0 {
-        "B"
-    } else if score >= 70.0 {
-        "C"
-    } else if score >= 60.0 {
-        "D"
-    } else {
-        "F"
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::{Child, ChildStderr, Command};
+use tokio::sync::{Notify, Semaphore};
+use tokio::task::JoinHandle;
+
+use crate::protocol::{OutputParser, ProtocolLine};
+use crate::types::{
+    CheckMessage, CheckMeta, CheckResult, Evidence, Finding, GradingPolicy, HostFacts, PhaseInfo,
+    ProtocolViolation, RemediationHint, ScanEvent, ScriptDiagnostics, Waiver,
+};
+use crate::{facts, plan, platform, scoring, waivers};
+
+/// Options controlling how a scan is executed.
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Maximum number of checks running at once within a phase.
+    pub concurrency: usize,
+    /// Timeout for checks that don't set `timeout_secs` in check.toml.
+    pub default_timeout_secs: u64,
+    /// How the final summary is graded.
+    pub grading: GradingPolicy,
+    /// Report output that breaks the check protocol as violations.
+    pub strict_protocol: bool,
+    /// Accepted risks; failures they cover are reported as WAIVED.
+    pub waivers: Vec<Waiver>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+            default_timeout_secs: DEFAULT_TIMEOUT_SECS,
+            grading: GradingPolicy::default(),
+            strict_protocol: false,
+            waivers: Vec::new(),
+        }
+    }
+}
+
+/// Default worker pool size when the caller doesn't specify one.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Default per-check timeout.
+const DEFAULT_TIMEOUT_SECS: u64 = 120;
+
+/// How long a process group gets to exit after SIGTERM before SIGKILL.
+const KILL_GRACE: Duration = Duration::from_secs(2);
+
+/// Stderr kept per check; anything beyond is drained and dropped.
+const MAX_STDERR_BYTES: usize = 16 * 1024;
+
+/// How long to wait for stderr to close after the script ends; a
+/// backgrounded grandchild can hold it open indefinitely.
+const STDERR_GRACE: Duration = Duration::from_millis(500);
+
+/// Cancellation handle shared between a running scan and `cancel_scan`.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<CancelInner>,
+}
+
+#[derive(Debug, Default)]
+struct CancelInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once `cancel` has been called.
+    pub async fn cancelled(&self) {
+        loop {
+            // Register before checking the flag so a concurrent cancel isn't missed
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Destination for scan events: the Tauri channel in the app, the
+/// terminal in the headless `clawkeeper-scan` binary.
+pub trait EventSink: Sync {
+    fn emit(&self, event: ScanEvent) -> Result<(), String>;
+}
+
+#[cfg(feature = "desktop")]
+impl EventSink for tauri::ipc::Channel<ScanEvent> {
+    fn emit(&self, event: ScanEvent) -> Result<(), String> {
+        self.send(event)
+            .map_err(|e| format!("Channel send error: {}", e))
     }
-    .to_string()
 }
 
-/// Run the full scan: iterate phases and checks, stream events via Channel.
+/// Result of running one check. Events are buffered rather than sent
+/// directly so they can be replayed in catalog order no matter which
+/// check finishes first.
+struct CheckOutcome {
+    result: CheckResult,
+    events: Vec<ScanEvent>,
+}
+
+impl CheckOutcome {
+    /// Build the outcome, taking the detail and evidence from the last
+    /// `CheckCompleted` and collecting findings, diagnostics, protocol violations and
+    /// info/warn/error messages from the buffered events.
+    fn new(check: &CheckMeta, status: String, events: Vec<ScanEvent>, started: Instant) -> Self {
+        let mut detail = String::new();
+        let mut evidence = Vec::new();
+        let mut messages = Vec::new();
+        let mut findings = Vec::new();
+        let mut diagnostics = None;
+        let mut remediation = None;
+        let mut protocol_violations = Vec::new();
+        for event in &events {
+            let (level, message) = match event {
+                ScanEvent::CheckCompleted {
+                    detail: d,
+                    evidence: e,
+                    ..
+                } => {
+                    detail = d.clone();
+                    evidence = e.clone();
+                    continue;
+                }
+                ScanEvent::Finding {
+                    name,
+                    status,
+                    detail,
+                    ..
+                } => {
+                    findings.push(Finding {
+                        name: name.clone(),
+                        status: status.clone(),
+                        detail: detail.clone(),
+                    });
+                    continue;
+                }
+                ScanEvent::Diagnostic {
+                    exit_code,
+                    signal,
+                    stderr,
+                    stderr_truncated,
+                    ..
+                } => {
+                    diagnostics = Some(ScriptDiagnostics {
+                        exit_code: *exit_code,
+                        signal: *signal,
+                        stderr: stderr.clone(),
+                        stderr_truncated: *stderr_truncated,
+                    });
+                    continue;
+                }
+                ScanEvent::ProtocolViolation { line, message, .. } => {
+                    protocol_violations.push(ProtocolViolation {
+                        line: *line,
+                        message: message.clone(),
+                    });
+                    continue;
+                }
+                ScanEvent::Prompt {
+                    message,
+                    remediation_id,
+                    ..
+                } => {
+                    remediation = Some(RemediationHint {
+                        remediation_id: remediation_id.clone(),
+                        message: message.clone(),
+                    });
+                    continue;
+                }
+                ScanEvent::Info { message, .. } => ("info", message),
+                ScanEvent::Warn { message, .. } => ("warn", message),
+                ScanEvent::Error { message, .. } => ("error", message),
+                _ => continue,
+            };
+            messages.push(CheckMessage {
+                level: level.to_string(),
+                message: message.clone(),
+            });
+        }
+
+        Self {
+            result: CheckResult {
+                check_id: check.id.clone(),
+                check_name: check.name.clone(),
+                phase: check.phase.clone(),
+                status,
+                detail,
+                messages,
+                duration_ms: started.elapsed().as_millis() as u64,
+                remediation,
+                severity: check.severity.clone(),
+                weight: scoring::check_weight(check),
+                findings,
+                evidence,
+                diagnostics,
+                protocol_violations,
+                waivers: Vec::new(),
+            },
+            events,
+        }
+    }
+
+    /// Mark failing findings covered by an unexpired waiver as WAIVED, and
+    /// the check too once nothing else in it fails. An expired waiver
+    /// changes nothing but is reported as a warning.
+    fn apply_waivers(&mut self, accepted: &[Waiver], today: &str) {
+        if self.result.status != "FAIL" {
+            return;
+        }
+
+        let mut notes: Vec<(&str, String)> = Vec::new();
+        for finding in self
+            .result
+            .findings
+            .iter_mut()
+            .filter(|f| is_failing(&f.status))
+        {
+            let Some(waiver) = waivers::find(accepted, &self.result.check_id, finding) else {
+                continue;
+            };
+            if !waivers::is_active(waiver, today) {
+                let subject = match &waiver.finding {
+                    Some(name) => format!("Waiver for {}", name),
+                    None => "Waiver".to_string(),
+                };
+                let note = format!(
+                    "{} expired on {} (owner {}); counted as a failure",
+                    subject, waiver.expires, waiver.owner
+                );
+                if !notes.iter().any(|(_, n)| *n == note) {
+                    notes.push(("warn", note));
+                }
+                continue;
+            }
+            finding.status = "WAIVED".to_string();
+            if !self.result.waivers.contains(waiver) {
+                self.result.waivers.push(waiver.clone());
+                notes.push(("info", waivers::describe(waiver)));
+            }
+        }
+        if notes.is_empty() {
+            return;
+        }
+
+        let (status, detail) = aggregate_findings(&self.result.findings);
+        let check_id = self.result.check_id.clone();
+        let mut statuses = self.result.findings.iter().map(|f| f.status.clone());
+        let mut rewritten = Vec::with_capacity(self.events.len() + notes.len());
+        for mut event in self.events.drain(..) {
+            match &mut event {
+                ScanEvent::Finding { status, .. } => {
+                    if let Some(new_status) = statuses.next() {
+                        *status = new_status;
+                    }
+                }
+                ScanEvent::CheckCompleted {
+                    status: completed_status,
+                    detail: completed_detail,
+                    ..
+                } => {
+                    // Notes go just before the verdict, like other messages
+                    for (level, message) in &notes {
+                        rewritten.push(match *level {
+                            "warn" => ScanEvent::Warn {
+                                check_id: check_id.clone(),
+                                message: message.clone(),
+                            },
+                            _ => ScanEvent::Info {
+                                check_id: check_id.clone(),
+                                message: message.clone(),
+                            },
+                        });
+                    }
+                    *completed_status = status.clone();
+                    *completed_detail = detail.clone();
+                }
+                _ => {}
+            }
+            rewritten.push(event);
+        }
+        self.events = rewritten;
+
+        self.result
+            .messages
+            .extend(notes.into_iter().map(|(level, message)| CheckMessage {
+                level: level.to_string(),
+                message,
+            }));
+        self.result.status = status;
+        self.result.detail = detail;
+    }
+}
+
+/// How a check script stopped.
+enum ScriptEnd {
+    /// Exited on its own, with what it reported.
+    Completed {
+        output: ScriptOutput,
+        exit: ExitStatus,
+    },
+    TimedOut,
+    Cancelled,
+}
+
+/// What a check reported on stdout.
+#[derive(Default)]
+struct ScriptOutput {
+    findings: Vec<Finding>,
+    evidence: Vec<Evidence>,
+    /// Evidence waiting for the status line it supports
+    pending: Vec<Evidence>,
+}
+
+impl ScriptOutput {
+    /// Stream a finding and keep it for aggregation, linking any evidence
+    /// emitted since the previous status line to it.
+    fn record(&mut self, check: &CheckMeta, finding: Finding, events: &mut Vec<ScanEvent>) {
+        for mut evidence in self.pending.drain(..) {
+            evidence.finding = Some(finding.name.clone());
+            self.evidence.push(evidence);
+        }
+        events.push(ScanEvent::Finding {
+            check_id: check.id.clone(),
+            name: finding.name.clone(),
+            status: finding.status.clone(),
+            detail: finding.detail.clone(),
+        });
+        self.findings.push(finding);
+    }
+}
+
+/// Exclusive checks (and anything needing sudo) must run alone so that
+/// password prompts and system-wide state changes don't interleave.
+fn is_exclusive(check: &CheckMeta) -> bool {
+    check.exclusive || check.requires_sudo
+}
+
+/// Run the full scan: iterate phases and checks, stream events to the sink.
+/// Checks within a phase run concurrently up to `options.concurrency`,
+/// except that a check waits for those it lists in `after` (see `plan`);
+/// their events are emitted in plan order once each check finishes.
+/// Checks whose `requires` aren't met by `facts` are skipped.
+/// Returns the final result of every check that ran.
 pub async fn run_scan(
     resource_base: PathBuf,
     checks: Vec<CheckMeta>,
     phases: Vec<PhaseInfo>,
-    on_event: &Channel<ScanEvent>,
-) -> Result<(), String> {
-    let helpers_path = resource_base.join("lib").join("helpers.sh");
-    let checks_base = resource_base.join("checks");
+    facts: &HostFacts,
+    options: ScanOptions,
+    cancel: CancelToken,
+    on_event: &dyn EventSink,
+) -> Result<Vec<CheckResult>, String> {
+    let script_env = Arc::new(script_env(&resource_base, facts));
+    let plan = plan::plan_scan(&checks, &phases)?;
+    let today = waivers::today();
+
+    let limit = options.concurrency.max(1) as u32;
+    let semaphore = Arc::new(Semaphore::new(limit as usize));
 
-    let mut passed: u32 = 0;
-    let mut failed: u32 = 0;
-    let mut skipped: u32 = 0;
+    let mut results = Vec::new();
 
     // Iterate phases in order
-    for phase in &phases {
-        on_event
-            .send(ScanEvent::PhaseStarted {
-                phase_id: phase.id.clone(),
-                phase_label: phase.label.clone(),
-            })
-            .map_err(|e| format!("Channel send error: {}", e))?;
-
-        // Get checks for this phase
-        let phase_checks: Vec<&CheckMeta> =
-            checks.iter().filter(|c| c.phase == phase.id).collect();
-
-        for check in phase_checks {
-            on_event
-                .send(ScanEvent::CheckStarted {
-                    check_id: check.id.clone(),
-                })
-                .map_err(|e| format!("Channel send error: {}", e))?;
-
-            let script_path = checks_base.join(&check.id).join("check.sh");
-
-            if !script_path.exists() {
-                on_event
-                    .send(ScanEvent::Error {
-                        check_id: check.id.clone(),
-                        message: format!("check.sh not found at {}", script_path.display()),
-                    })
-                    .map_err(|e| format!("Channel send error: {}", e))?;
-                failed += 1;
-                on_event
-                    .send(ScanEvent::CheckCompleted {
-                        check_id: check.id.clone(),
-                        check_name: check.name.clone(),
-                        status: "FAIL".to_string(),
-                        detail: "check.sh not found".to_string(),
-                    })
-                    .map_err(|e| format!("Channel send error: {}", e))?;
-                continue;
+    for phase_plan in &plan {
+        if cancel.is_cancelled() {
+            break;
+        }
+
+        on_event.emit(ScanEvent::PhaseStarted {
+            phase_id: phase_plan.phase.id.clone(),
+            phase_label: phase_plan.phase.label.clone(),
+        })?;
+
+        for wave in &phase_plan.waves {
+            if cancel.is_cancelled() {
+                break;
             }
 
-            match execute_check_script(&script_path, &helpers_path, check, on_event).await {
-                Ok(status) => match status.as_str() {
-                    "PASS" => passed += 1,
-                    "FAIL" => failed += 1,
-                    "SKIPPED" => skipped += 1,
-                    _ => failed += 1,
-                },
-                Err(e) => {
-                    failed += 1;
-                    let _ = on_event.send(ScanEvent::Error {
-                        check_id: check.id.clone(),
-                        message: e.clone(),
-                    });
-                    let _ = on_event.send(ScanEvent::CheckCompleted {
-                        check_id: check.id.clone(),
-                        check_name: check.name.clone(),
-                        status: "FAIL".to_string(),
-                        detail: e,
-                    });
+            // Spawn every check in this wave; the semaphore bounds how many
+            // actually run at once. Exclusive checks take every permit.
+            let mut handles = Vec::new();
+            for check in wave {
+                let semaphore = semaphore.clone();
+                let permits = if is_exclusive(check) { limit } else { 1 };
+                let timeout =
+                    Duration::from_secs(check.timeout_secs.unwrap_or(options.default_timeout_secs));
+                let strict = options.strict_protocol;
+                let unmet = facts::unmet(facts, &check.requires);
+                let cancel = cancel.clone();
+                let script_env = script_env.clone();
+                let check = check.clone();
+
+                handles.push(tokio::spawn(async move {
+                    if let Some(reason) = unmet {
+                        return Ok(Some(skip_check(&check, reason)));
+                    }
+                    let _permit = tokio::select! {
+                        permit = semaphore.acquire_many_owned(permits) => {
+                            permit.map_err(|e| format!("Worker pool closed: {}", e))?
+                        }
+                        _ = cancel.cancelled() => return Ok(None),
+                    };
+                    Ok::<_, String>(run_check(&script_env, &check, timeout, strict, &cancel).await)
+                }));
+            }
+
+            // Await in spawn order so events reach the frontend deterministically.
+            // Cancelled checks produce no outcome and are left out entirely.
+            for handle in handles {
+                let outcome = handle
+                    .await
+                    .map_err(|e| format!("Check task failed: {}", e))??;
+                let Some(mut outcome) = outcome else {
+                    continue;
+                };
+                outcome.apply_waivers(&options.waivers, &today);
+
+                results.push(outcome.result);
+                for event in outcome.events {
+                    on_event.emit(event)?;
                 }
             }
         }
     }
 
-    let total = passed + failed + skipped;
-    let scoreable = passed + failed;
-    let score = if scoreable > 0 {
-        (passed as f64 / scoreable as f64) * 100.0
-    } else {
-        100.0
+    if cancel.is_cancelled() {
+        on_event.emit(ScanEvent::ScanCancelled {
+            completed: results.len() as u32,
+            total: checks.len() as u32,
+        })?;
+        return Ok(results);
+    }
+
+    on_event.emit(ScanEvent::ScanCompleted(scoring::summarize(
+        &results,
+        &options.grading,
+    )))?;
+
+    Ok(results)
+}
+
+/// Re-run a single check outside of a scan (e.g. to verify a remediation),
+/// streaming its events directly.
+pub async fn rerun_check(
+    resource_base: &Path,
+    check: &CheckMeta,
+    options: &ScanOptions,
+    on_event: &dyn EventSink,
+) -> Result<CheckResult, String> {
+    let timeout = Duration::from_secs(check.timeout_secs.unwrap_or(options.default_timeout_secs));
+
+    // Facts may have changed since the scan, e.g. a remediation installed
+    // Docker
+    let facts = facts::gather(&platform::detect_platform()).await;
+    let mut outcome = match facts::unmet(&facts, &check.requires) {
+        Some(reason) => Some(skip_check(check, reason)),
+        None => {
+            run_check(
+                &script_env(resource_base, &facts),
+                check,
+                timeout,
+                options.strict_protocol,
+                &CancelToken::default(),
+            )
+            .await
+        }
+    }
+    .ok_or("Check was cancelled")?;
+    outcome.apply_waivers(&options.waivers, &waivers::today());
+
+    for event in outcome.events {
+        on_event.emit(event)?;
+    }
+
+    Ok(outcome.result)
+}
+
+/// The outcome of a check that can't run on this host, without running it.
+fn skip_check(check: &CheckMeta, reason: String) -> CheckOutcome {
+    let events = vec![
+        ScanEvent::CheckStarted {
+            check_id: check.id.clone(),
+        },
+        ScanEvent::Finding {
+            check_id: check.id.clone(),
+            name: check.name.clone(),
+            status: "SKIPPED".to_string(),
+            detail: reason.clone(),
+        },
+        ScanEvent::CheckCompleted {
+            check_id: check.id.clone(),
+            check_name: check.name.clone(),
+            status: "SKIPPED".to_string(),
+            detail: reason,
+            evidence: Vec::new(),
+        },
+    ];
+    CheckOutcome::new(check, "SKIPPED".to_string(), events, Instant::now())
+}
+
+/// The environment every check.sh runs with: where helpers.sh is, plus
+/// the host facts.
+fn script_env(resource_base: &Path, facts: &HostFacts) -> Vec<(String, String)> {
+    let helpers_path = resource_base.join("lib").join("helpers.sh");
+    let mut env = vec![(
+        "CLAWKEEPER_HELPERS".to_string(),
+        helpers_path.to_string_lossy().to_string(),
+    )];
+    env.extend(facts::env_vars(facts));
+    env
+}
+
+/// Run a single check and collect its events. In `strict` mode, output
+/// that breaks the check protocol is reported as violations.
+/// Returns `None` if the scan was cancelled while the check was running.
+async fn run_check(
+    script_env: &[(String, String)],
+    check: &CheckMeta,
+    timeout: Duration,
+    strict: bool,
+    cancel: &CancelToken,
+) -> Option<CheckOutcome> {
+    let started = Instant::now();
+    let mut events = vec![ScanEvent::CheckStarted {
+        check_id: check.id.clone(),
+    }];
+
+    let script_path = Path::new(&check.origin.dir).join("check.sh");
+
+    if !script_path.exists() {
+        events.push(ScanEvent::Error {
+            check_id: check.id.clone(),
+            message: format!("check.sh not found at {}", script_path.display()),
+        });
+        events.push(ScanEvent::CheckCompleted {
+            check_id: check.id.clone(),
+            check_name: check.name.clone(),
+            status: "ERROR".to_string(),
+            detail: "check.sh not found".to_string(),
+            evidence: Vec::new(),
+        });
+        return Some(CheckOutcome::new(
+            check,
+            "ERROR".to_string(),
+            events,
+            started,
+        ));
+    }
+
+    let result = execute_check_script(
+        &script_path,
+        script_env,
+        check,
+        timeout,
+        strict,
+        cancel,
+        &mut events,
+    )
+    .await;
+
+    let status = match result {
+        Ok(ScriptEnd::Completed { output, exit }) => {
+            let (status, detail) = if output.findings.is_empty() {
+                // A script that dies without reporting is broken, not a
+                // security failure
+                let detail = no_result_detail(&exit);
+                events.push(ScanEvent::Error {
+                    check_id: check.id.clone(),
+                    message: detail.clone(),
+                });
+                ("ERROR".to_string(), detail)
+            } else {
+                aggregate_findings(&output.findings)
+            };
+            events.push(ScanEvent::CheckCompleted {
+                check_id: check.id.clone(),
+                check_name: check.name.clone(),
+                status: status.clone(),
+                detail,
+                evidence: output.evidence,
+            });
+            status
+        }
+        Ok(ScriptEnd::TimedOut) => {
+            let detail = format!("Check timed out after {}s", timeout.as_secs());
+            events.push(ScanEvent::Error {
+                check_id: check.id.clone(),
+                message: detail.clone(),
+            });
+            events.push(ScanEvent::CheckCompleted {
+                check_id: check.id.clone(),
+                check_name: check.name.clone(),
+                status: "TIMEOUT".to_string(),
+                detail,
+                evidence: Vec::new(),
+            });
+            "TIMEOUT".to_string()
+        }
+        Ok(ScriptEnd::Cancelled) => return None,
+        Err(e) => {
+            events.push(ScanEvent::Error {
+                check_id: check.id.clone(),
+                message: e.clone(),
+            });
+            events.push(ScanEvent::CheckCompleted {
+                check_id: check.id.clone(),
+                check_name: check.name.clone(),
+                status: "ERROR".to_string(),
+                detail: e,
+                evidence: Vec::new(),
+            });
+            "ERROR".to_string()
+        }
     };
-    let grade = compute_grade(score);
 
-    on_event
-        .send(ScanEvent::ScanCompleted {
-            passed,
-            failed,
-            skipped,
-            total,
-            score,
-            grade,
-        })
-        .map_err(|e| format!("Channel send error: {}", e))?;
+    Some(CheckOutcome::new(check, status, events, started))
+}
 
-    Ok(())
+fn no_result_detail(exit: &ExitStatus) -> String {
+    match (exit.code(), exit_signal(exit)) {
+        (Some(0), _) => "check.sh exited without reporting a result".to_string(),
+        (Some(code), _) => format!(
+            "check.sh exited with code {} without reporting a result",
+            code
+        ),
+        (None, Some(signal)) => format!("check.sh was killed by signal {}", signal),
+        (None, None) => "check.sh exited abnormally".to_string(),
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn exit_signal(exit: &ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    exit.signal()
+}
+
+#[cfg(not(unix))]
+pub(crate) fn exit_signal(_exit: &ExitStatus) -> Option<i32> {
+    None
 }
 
 /// Execute a single check script and parse its JSON line output.
-/// Returns the final status string (PASS/FAIL/SKIPPED).
+/// The script runs in its own process group so that a timeout or
+/// cancellation takes down anything it spawned (docker, sudo, ...).
 async fn execute_check_script(
-    script_path: &PathBuf,
-    _helpers_path: &PathBuf,
+    script_path: &Path,
+    script_env: &[(String, String)],
     check: &CheckMeta,
-    on_event: &Channel<ScanEvent>,
-) -> Result<String, String> {
-    let mut child = Command::new("/bin/bash")
+    timeout: Duration,
+    strict: bool,
+    cancel: &CancelToken,
+    events: &mut Vec<ScanEvent>,
+) -> Result<ScriptEnd, String> {
+    let mut command = Command::new("/bin/bash");
+    command
         .arg(script_path)
         .arg("--mode")
         .arg("scan")
+        .envs(script_env.iter().map(|(name, value)| (name, value)))
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    command.process_group(0);
+
+    let mut child = command
         .spawn()
         .map_err(|e| format!("Failed to spawn check {}: {}", check.id, e))?;
 
+    // Drain stderr alongside stdout so a chatty script can't block on a
+    // full pipe
+    let stderr_reader = child
+        .stderr
+        .take()
+        .map(|pipe| tokio::spawn(read_bounded(pipe)));
+
+    let end = tokio::select! {
+        result = async {
+            let output = read_check_output(&mut child, check, strict, events).await?;
+            let exit = child
+                .wait()
+                .await
+                .map_err(|e| format!("Failed to wait for check {}: {}", check.id, e))?;
+            Ok::<_, String>(ScriptEnd::Completed { output, exit })
+        } => result?,
+        _ = tokio::time::sleep(timeout) => ScriptEnd::TimedOut,
+        _ = cancel.cancelled() => ScriptEnd::Cancelled,
+    };
+
+    if !matches!(end, ScriptEnd::Completed { .. }) {
+        terminate_process_group(&mut child).await;
+    }
+
+    let (stderr, stderr_truncated) = collect_stderr(stderr_reader).await;
+
+    // Exit details only mean something if the script ended on its own
+    let (exit_code, signal, failed) = match &end {
+        ScriptEnd::Completed { exit, .. } => (exit.code(), exit_signal(exit), !exit.success()),
+        _ => (None, None, false),
+    };
+    if failed || !stderr.is_empty() {
+        events.push(ScanEvent::Diagnostic {
+            check_id: check.id.clone(),
+            exit_code,
+            signal,
+            stderr,
+            stderr_truncated,
+        });
+    }
+
+    Ok(end)
+}
+
+/// Wait briefly for a `read_bounded` task started on a script's stderr,
+/// giving up on it if something still holds the pipe open.
+pub(crate) async fn collect_stderr(reader: Option<JoinHandle<(String, bool)>>) -> (String, bool) {
+    let Some(handle) = reader else {
+        return (String::new(), false);
+    };
+    let abort = handle.abort_handle();
+    match tokio::time::timeout(STDERR_GRACE, handle).await {
+        Ok(Ok(captured)) => captured,
+        _ => {
+            abort.abort();
+            (String::new(), false)
+        }
+    }
+}
+
+/// Read a pipe to the end, keeping at most `MAX_STDERR_BYTES`.
+/// Returns the text and whether anything was dropped.
+pub(crate) async fn read_bounded(mut pipe: ChildStderr) -> (String, bool) {
+    let mut kept = Vec::new();
+    let mut truncated = false;
+    let mut buf = [0u8; 4096];
+    loop {
+        match pipe.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                let room = MAX_STDERR_BYTES.saturating_sub(kept.len());
+                kept.extend_from_slice(&buf[..n.min(room)]);
+                truncated |= n > room;
+            }
+        }
+    }
+    (
+        String::from_utf8_lossy(&kept).trim_end().to_string(),
+        truncated,
+    )
+}
+
+/// Read protocol lines from a check's stdout until it closes.
+/// Each status line becomes a `Finding`, and each evidence line is linked
+/// to the status line after it.
+async fn read_check_output(
+    child: &mut Child,
+    check: &CheckMeta,
+    strict: bool,
+    events: &mut Vec<ScanEvent>,
+) -> Result<ScriptOutput, String> {
     let stdout = child
         .stdout
         .take()
         .ok_or_else(|| format!("No stdout for check {}", check.id))?;
 
     let mut reader = BufReader::new(stdout).lines();
-    let mut final_status = String::from("FAIL");
+    let mut parser = OutputParser::new();
+    let mut output = ScriptOutput::default();
 
     while let Some(line) = reader
         .next_line()
         .await
         .map_err(|e| format!("IO error reading check {}: {}", check.id, e))?
     {
-        let trimmed = line.trim();
-        if trimmed.is_empty() || !trimmed.starts_with('{') {
-            continue;
+        let (parsed, violation) = parser.parse(&line);
+        if let Some(violation) = violation.filter(|_| strict) {
+            push_violation(check, violation, events);
         }
 
-        let parsed: CheckOutputLine = match serde_json::from_str(trimmed) {
-            Ok(v) => v,
-            Err(_) => continue, // Skip unparseable lines
-        };
-
-        // Determine line type and send appropriate event
-        if let Some(ref action) = parsed.action {
-            if action == "prompt" {
-                // In Phase C, prompts are treated as FAIL (no interactive remediation)
-                let _ = on_event.send(ScanEvent::Prompt {
-                    check_id: check.id.clone(),
-                    message: parsed.message.clone().unwrap_or_default(),
-                    remediation_id: parsed.remediation_id.clone().unwrap_or_default(),
-                    fail_detail: parsed.fail_detail.clone().unwrap_or_default(),
-                    skip_detail: parsed.skip_detail.clone().unwrap_or_default(),
-                });
-                // Treat prompt as FAIL
-                final_status = "FAIL".to_string();
-                let detail = parsed.fail_detail.unwrap_or_default();
-                let _ = on_event.send(ScanEvent::CheckCompleted {
-                    check_id: check.id.clone(),
-                    check_name: check.name.clone(),
+        match parsed {
+            Some(ProtocolLine::Prompt {
+                message,
+                remediation_id,
+                fail_detail,
+                skip_detail,
+            }) => {
+                // Prompts are reported as FAIL; the frontend can offer the fix
+                // afterwards via run_remediation
+                let finding = Finding {
+                    name: check.name.clone(),
                     status: "FAIL".to_string(),
-                    detail,
+                    detail: fail_detail.clone(),
+                };
+                events.push(ScanEvent::Prompt {
+                    check_id: check.id.clone(),
+                    message,
+                    remediation_id,
+                    fail_detail,
+                    skip_detail,
                 });
+                output.record(check, finding, events);
                 // After prompt, the check script typically exits
             }
-        } else if let Some(ref line_type) = parsed.line_type {
-            match line_type.as_str() {
-                "info" => {
-                    let _ = on_event.send(ScanEvent::Info {
-                        check_id: check.id.clone(),
-                        message: parsed.message.unwrap_or_default(),
-                    });
-                }
-                "warn" => {
-                    let _ = on_event.send(ScanEvent::Warn {
-                        check_id: check.id.clone(),
-                        message: parsed.message.unwrap_or_default(),
-                    });
-                }
-                _ => {}
+            Some(ProtocolLine::Info { message }) => events.push(ScanEvent::Info {
+                check_id: check.id.clone(),
+                message,
+            }),
+            Some(ProtocolLine::Warn { message }) => events.push(ScanEvent::Warn {
+                check_id: check.id.clone(),
+                message,
+            }),
+            Some(ProtocolLine::Status {
+                status,
+                check_name,
+                detail,
+            }) => {
+                let finding = Finding {
+                    name: check_name
+                        .filter(|n| !n.is_empty())
+                        .unwrap_or_else(|| check.name.clone()),
+                    status,
+                    detail,
+                };
+                output.record(check, finding, events);
             }
-        } else if let Some(ref status) = parsed.status {
-            final_status = status.clone();
-            let _ = on_event.send(ScanEvent::CheckCompleted {
+            Some(ProtocolLine::Evidence {
+                key,
+                observed,
+                expected,
+                source,
+            }) => output.pending.push(Evidence {
+                finding: None,
+                key,
+                observed,
+                // The shell helpers write "" for arguments left out
+                expected: expected.filter(|e| !e.is_empty()),
+                source: source.filter(|s| !s.is_empty()),
+            }),
+            Some(ProtocolLine::Metric { name, value, unit }) => events.push(ScanEvent::Info {
                 check_id: check.id.clone(),
-                check_name: parsed.check_name.unwrap_or_else(|| check.name.clone()),
-                status: status.clone(),
-                detail: parsed.detail.unwrap_or_default(),
-            });
+                message: match unit {
+                    Some(unit) => format!("{}: {} {}", name, value, unit),
+                    None => format!("{}: {}", name, value),
+                },
+            }),
+            None => {}
         }
     }
 
-    // Wait for process to finish
-    let exit_status = child
-        .wait()
-        .await
-        .map_err(|e| format!("Failed to wait for check {}: {}", check.id, e))?;
+    if let Some(violation) = parser.finish().filter(|_| strict) {
+        push_violation(check, violation, events);
+    }
+
+    // Evidence after the last status line belongs to the check as a whole
+    let pending = std::mem::take(&mut output.pending);
+    output.evidence.extend(pending);
+
+    Ok(output)
+}
+
+fn push_violation(check: &CheckMeta, violation: ProtocolViolation, events: &mut Vec<ScanEvent>) {
+    events.push(ScanEvent::ProtocolViolation {
+        check_id: check.id.clone(),
+        line: violation.line,
+        message: violation.message,
+    });
+}
 
-    // If the script exited non-zero and we haven't gotten a status line, treat as error
-    if !exit_status.success() && final_status == "FAIL" {
-        // Already defaulted to FAIL, which is correct
+/// Whether a finding with this status counts against the check. Anything
+/// unrecognised does.
+fn is_failing(status: &str) -> bool {
+    !matches!(status, "PASS" | "FIXED" | "SKIPPED" | "WAIVED")
+}
+
+/// Combine a check's findings into its overall status and detail.
+/// Status: any FAIL (or unrecognised status) → FAIL, else any WAIVED →
+/// WAIVED, else any FIXED → FIXED, else PASS if anything passed, else
+/// SKIPPED; a check with no findings is an ERROR. Detail: the failing (or
+/// waived) findings' details, or else the last finding's, which is usually
+/// the check's own summary line.
+pub(crate) fn aggregate_findings(findings: &[Finding]) -> (String, String) {
+    let has = |status: &str| findings.iter().any(|f| f.status == status);
+    let is_failing = |f: &&Finding| is_failing(&f.status);
+
+    let Some(last) = findings.last() else {
+        return ("ERROR".to_string(), "Check reported no result".to_string());
+    };
+
+    if findings.iter().any(|f| is_failing(&f)) {
+        let details: Vec<&str> = findings
+            .iter()
+            .filter(is_failing)
+            .map(|f| f.detail.as_str())
+            .collect();
+        return ("FAIL".to_string(), details.join("; "));
+    }
+    if has("WAIVED") {
+        let details: Vec<&str> = findings
+            .iter()
+            .filter(|f| f.status == "WAIVED")
+            .map(|f| f.detail.as_str())
+            .collect();
+        return ("WAIVED".to_string(), details.join("; "));
     }
 
-    Ok(final_status)
+    let status = if has("FIXED") {
+        "FIXED"
+    } else if has("PASS") {
+        "PASS"
+    } else {
+        "SKIPPED"
+    };
+    (status.to_string(), last.detail.clone())
+}
+
+/// Stop a script's whole process group: SIGTERM first, SIGKILL if it
+/// hasn't exited within `KILL_GRACE`. Always reaps the direct child.
+pub(crate) async fn terminate_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        // If the group can't be signalled, fall back to killing the child
+        if signal_process_group(pid, libc::SIGTERM).is_ok() {
+            let exited = tokio::time::timeout(KILL_GRACE, child.wait()).await.is_ok();
+            // Leader gone or not, make sure stragglers in the group are too
+            let _ = signal_process_group(pid, libc::SIGKILL);
+            if exited {
+                return;
+            }
+        }
+    }
+    let _ = child.start_kill();
+    let _ = child.wait().await;
+}
+
+/// Send a signal to every process in the group led by `pid`. A group with
+/// nothing left in it (ESRCH) counts as signalled.
+#[cfg(unix)]
+fn signal_process_group(pid: u32, signal: libc::c_int) -> std::io::Result<()> {
+    let pgid = libc::pid_t::try_from(pid)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    // SAFETY: killpg takes no pointers; it only sends a signal
+    if unsafe { libc::killpg(pgid, signal) } == 0 {
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    if err.raw_os_error() == Some(libc::ESRCH) {
+        Ok(())
+    } else {
+        Err(err)
+    }
 }
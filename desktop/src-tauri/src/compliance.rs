@@ -0,0 +1,392 @@
+//! Compliance mapping: which framework controls (CIS benchmarks, SOC 2)
+//! each check covers, and how a scan stands against every framework,
+//! including the controls no check covers.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::platform;
+use crate::types::{
+    CheckMeta, ControlCoverage, ControlMapping, FrameworkCoverage, FrameworkDef, ScanRecord,
+};
+
+#[derive(Deserialize)]
+struct FrameworkFile {
+    #[serde(default)]
+    framework: Vec<FrameworkDef>,
+}
+
+/// Load the frameworks from checks/frameworks.toml, or none if there isn't
+/// one.
+pub fn load_frameworks(base: &Path) -> Result<Vec<FrameworkDef>, String> {
+    let path = base.join("checks").join("frameworks.toml");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file: FrameworkFile = toml::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    for (i, framework) in file.framework.iter().enumerate() {
+        if file.framework[..i].iter().any(|f| f.id == framework.id) {
+            return Err(format!(
+                "{}: framework \"{}\" is defined more than once",
+                path.display(),
+                framework.id
+            ));
+        }
+        for (j, control) in framework.controls.iter().enumerate() {
+            if framework.controls[..j].iter().any(|c| c.id == control.id) {
+                return Err(format!(
+                    "{}: framework \"{}\": control \"{}\" is listed more than once",
+                    path.display(),
+                    framework.id,
+                    control.id
+                ));
+            }
+        }
+    }
+
+    Ok(file.framework)
+}
+
+/// Each control in `mapping`, with the finding that settles it, or None
+/// when the check's own status does.
+pub fn mapped_controls(mapping: &ControlMapping) -> Vec<(&str, Option<&str>)> {
+    match mapping {
+        ControlMapping::Check(ids) => ids.iter().map(|id| (id.as_str(), None)).collect(),
+        ControlMapping::Findings(findings) => findings
+            .iter()
+            .map(|(id, finding)| (id.as_str(), Some(finding.as_str())))
+            .collect(),
+    }
+}
+
+/// How `record` covers each framework that applies to its host. `checks` is
+/// the catalog the scan ran with, which carries the control mappings.
+pub fn coverage(
+    record: &ScanRecord,
+    checks: &[CheckMeta],
+    frameworks: &[FrameworkDef],
+) -> Vec<FrameworkCoverage> {
+    frameworks
+        .iter()
+        .filter(|f| {
+            f.platform
+                .as_deref()
+                .is_none_or(|p| platform::matches_platform(p, &record.platform))
+        })
+        .map(|framework| {
+            let mut summary = FrameworkCoverage {
+                framework: framework.id.clone(),
+                label: framework.label.clone(),
+                controls: Vec::new(),
+                mapped: 0,
+                passed: 0,
+                failed: 0,
+                waived: 0,
+                not_assessed: 0,
+                unmapped: 0,
+            };
+            for control in &framework.controls {
+                let mapped: Vec<(&str, Option<&str>)> = checks
+                    .iter()
+                    .filter_map(|c| {
+                        let mapping = c.controls.get(&framework.id)?;
+                        mapped_controls(mapping)
+                            .into_iter()
+                            .find(|(id, _)| *id == control.id)
+                            .map(|(_, finding)| (c.id.as_str(), finding))
+                    })
+                    .collect();
+                let status = control_status(record, &mapped);
+                match status {
+                    "PASS" => summary.passed += 1,
+                    "FAIL" => summary.failed += 1,
+                    "WAIVED" => summary.waived += 1,
+                    "NOT_ASSESSED" => summary.not_assessed += 1,
+                    _ => summary.unmapped += 1,
+                }
+                if !mapped.is_empty() {
+                    summary.mapped += 1;
+                }
+                summary.controls.push(ControlCoverage {
+                    id: control.id.clone(),
+                    title: control.title.clone(),
+                    status: status.to_string(),
+                    checks: mapped
+                        .iter()
+                        .map(|(check_id, finding)| match finding {
+                            Some(finding) => format!("{}/{}", check_id, finding),
+                            None => check_id.to_string(),
+                        })
+                        .collect(),
+                });
+            }
+            summary
+        })
+        .collect()
+}
+
+/// A control fails if any mapped check or finding failed; otherwise a
+/// waiver or a pass settles it. Skipped, errored and unselected checks,
+/// and findings a check didn't report, give no verdict.
+fn control_status(record: &ScanRecord, mapped: &[(&str, Option<&str>)]) -> &'static str {
+    if mapped.is_empty() {
+        return "UNMAPPED";
+    }
+    let mut statuses: Vec<&str> = Vec::new();
+    for (check_id, finding) in mapped {
+        for result in record.checks.iter().filter(|r| r.check_id == *check_id) {
+            match finding {
+                None => statuses.push(&result.status),
+                Some(name) => statuses.extend(
+                    result
+                        .findings
+                        .iter()
+                        .filter(|f| f.name == *name)
+                        .map(|f| f.status.as_str()),
+                ),
+            }
+        }
+    }
+    if statuses.contains(&"FAIL") {
+        "FAIL"
+    } else if statuses.contains(&"WAIVED") {
+        "WAIVED"
+    } else if statuses.iter().any(|s| *s == "PASS" || *s == "FIXED") {
+        "PASS"
+    } else {
+        "NOT_ASSESSED"
+    }
+}
+
+/// One line per framework, e.g. "CIS Ubuntu 22.04: 12/20 controls mapped,
+/// 9 pass, 2 fail, 1 not assessed".
+pub fn describe(coverage: &FrameworkCoverage) -> String {
+    let mut parts = vec![format!(
+        "{}/{} controls mapped",
+        coverage.mapped,
+        coverage.controls.len()
+    )];
+    for (count, label) in [
+        (coverage.passed, "pass"),
+        (coverage.failed, "fail"),
+        (coverage.waived, "waived"),
+        (coverage.not_assessed, "not assessed"),
+    ] {
+        if count > 0 {
+            parts.push(format!("{} {}", count, label));
+        }
+    }
+    format!("{}: {}", coverage.label, parts.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ControlDef;
+
+    fn check(id: &str, controls: serde_json::Value) -> CheckMeta {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "phase": "hardening",
+            "platform": "linux",
+            "description": "",
+            "controls": { "cis": controls },
+        }))
+        .unwrap()
+    }
+
+    fn record(results: serde_json::Value) -> ScanRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": "scan",
+            "started_at": 0,
+            "finished_at": 0,
+            "hostname": "host",
+            "platform": {
+                "os": "linux",
+                "arch": "x86_64",
+                "distro": "ubuntu",
+                "distro_family": "debian",
+                "distro_version": "24.04",
+                "distro_name": "Ubuntu 24.04",
+            },
+            "summary": {
+                "passed": 0, "failed": 0, "fixed": 0, "skipped": 0, "errors": 0,
+                "total": 0, "score": 0.0, "grade": "F",
+            },
+            "checks": results,
+        }))
+        .unwrap()
+    }
+
+    fn result(id: &str, status: &str, findings: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "check_id": id,
+            "check_name": id,
+            "phase": "hardening",
+            "status": status,
+            "detail": "",
+            "findings": findings,
+        })
+    }
+
+    fn framework(platform: Option<&str>, controls: &[&str]) -> FrameworkDef {
+        FrameworkDef {
+            id: "cis".to_string(),
+            label: "CIS".to_string(),
+            platform: platform.map(str::to_string),
+            controls: controls
+                .iter()
+                .map(|id| ControlDef {
+                    id: id.to_string(),
+                    title: format!("Control {}", id),
+                })
+                .collect(),
+        }
+    }
+
+    fn statuses(coverage: &FrameworkCoverage) -> Vec<(&str, &str)> {
+        coverage
+            .controls
+            .iter()
+            .map(|c| (c.id.as_str(), c.status.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn mapped_controls_from_either_form() {
+        let whole: ControlMapping =
+            serde_json::from_value(serde_json::json!(["1.1", "1.2"])).unwrap();
+        assert_eq!(mapped_controls(&whole), [("1.1", None), ("1.2", None)]);
+
+        let by_finding: ControlMapping =
+            serde_json::from_value(serde_json::json!({ "5.2.7": "PermitRootLogin" })).unwrap();
+        assert_eq!(
+            mapped_controls(&by_finding),
+            [("5.2.7", Some("PermitRootLogin"))]
+        );
+    }
+
+    #[test]
+    fn control_status_from_checks() {
+        let checks = [
+            check("failing", serde_json::json!(["1"])),
+            check("passing", serde_json::json!(["1", "2"])),
+            check("waived", serde_json::json!(["3"])),
+            check("fixed", serde_json::json!(["3", "4"])),
+            check("skipped", serde_json::json!(["5"])),
+            check("errored", serde_json::json!(["5"])),
+            check("unselected", serde_json::json!(["6"])),
+        ];
+        let record = record(serde_json::json!([
+            result("failing", "FAIL", serde_json::json!([])),
+            result("passing", "PASS", serde_json::json!([])),
+            result("waived", "WAIVED", serde_json::json!([])),
+            result("fixed", "FIXED", serde_json::json!([])),
+            result("skipped", "SKIPPED", serde_json::json!([])),
+            result("errored", "ERROR", serde_json::json!([])),
+        ]));
+        let coverage = coverage(
+            &record,
+            &checks,
+            &[framework(None, &["1", "2", "3", "4", "5", "6", "7"])],
+        );
+        assert_eq!(
+            statuses(&coverage[0]),
+            [
+                ("1", "FAIL"),
+                ("2", "PASS"),
+                ("3", "WAIVED"),
+                ("4", "PASS"),
+                ("5", "NOT_ASSESSED"),
+                ("6", "NOT_ASSESSED"),
+                ("7", "UNMAPPED"),
+            ]
+        );
+        let c = &coverage[0];
+        assert_eq!(
+            (
+                c.mapped,
+                c.passed,
+                c.failed,
+                c.waived,
+                c.not_assessed,
+                c.unmapped
+            ),
+            (6, 2, 1, 1, 2, 1)
+        );
+        assert_eq!(c.controls[0].checks, ["failing", "passing"]);
+        assert_eq!(
+            describe(c),
+            "CIS: 6/7 controls mapped, 2 pass, 1 fail, 1 waived, 2 not assessed"
+        );
+    }
+
+    #[test]
+    fn control_status_from_findings() {
+        let checks = [check(
+            "ssh",
+            serde_json::json!({
+                "5.2.7": "PermitRootLogin",
+                "5.2.12": "X11Forwarding",
+                "5.2.13": "MaxAuthTries",
+            }),
+        )];
+        // The check as a whole failed, but each control follows its finding
+        let record = record(serde_json::json!([result(
+            "ssh",
+            "FAIL",
+            serde_json::json!([
+                { "name": "PermitRootLogin", "status": "PASS", "detail": "" },
+                { "name": "X11Forwarding", "status": "FAIL", "detail": "" },
+            ])
+        )]));
+        let coverage = coverage(
+            &record,
+            &checks,
+            &[framework(None, &["5.2.7", "5.2.12", "5.2.13"])],
+        );
+        assert_eq!(
+            statuses(&coverage[0]),
+            [
+                ("5.2.7", "PASS"),
+                ("5.2.12", "FAIL"),
+                ("5.2.13", "NOT_ASSESSED")
+            ]
+        );
+        assert_eq!(coverage[0].controls[0].checks, ["ssh/PermitRootLogin"]);
+    }
+
+    #[test]
+    fn frameworks_for_other_platforms_are_left_out() {
+        let record = record(serde_json::json!([]));
+        assert!(coverage(&record, &[], &[framework(Some("macos"), &["1"])]).is_empty());
+        assert_eq!(
+            coverage(&record, &[], &[framework(Some("linux"), &["1"])]).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn duplicate_controls_are_rejected() {
+        let base =
+            std::env::temp_dir().join(format!("clawkeeper-frameworks-{}", std::process::id()));
+        std::fs::create_dir_all(base.join("checks")).unwrap();
+        std::fs::write(
+            base.join("checks").join("frameworks.toml"),
+            "[[framework]]\nid = \"cis\"\nlabel = \"CIS\"\n\n[[framework.controls]]\nid = \"1.1\"\ntitle = \"A\"\n\n[[framework.controls]]\nid = \"1.1\"\ntitle = \"B\"\n",
+        )
+        .unwrap();
+        let result = load_frameworks(&base);
+        std::fs::remove_dir_all(&base).unwrap();
+        assert!(result
+            .unwrap_err()
+            .ends_with("framework \"cis\": control \"1.1\" is listed more than once"));
+    }
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use tauri::ipc::Channel;
 use tokio::process::Command;
 
+use crate::facts;
+
 /// Events streamed during the deploy/setup process
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "event")]
@@ -40,90 +42,20 @@ pub struct OpenClawStatus {
 
 /// Detect current OpenClaw installation status
 pub async fn detect_openclaw() -> OpenClawStatus {
-    let docker_available = Command::new("command")
-        .arg("-v")
-        .arg("docker")
-        .output()
-        .await
-        .map(|o| o.status.success())
-        .unwrap_or(false)
-        || Command::new("which")
-            .arg("docker")
-            .output()
-            .await
-            .map(|o| o.status.success())
-            .unwrap_or(false);
-
-    let node_available = Command::new("which")
-        .arg("node")
-        .output()
-        .await
-        .map(|o| o.status.success())
-        .unwrap_or(false);
-
-    let homebrew_available = Command::new("which")
-        .arg("brew")
-        .output()
-        .await
-        .map(|o| o.status.success())
-        .unwrap_or(false);
-
-    // Check for running Docker container
-    let docker_running = if docker_available {
-        Command::new("docker")
-            .args(["ps", "--format", "{{.Names}}"])
-            .output()
-            .await
-            .map(|o| {
-                String::from_utf8_lossy(&o.stdout)
-                    .lines()
-                    .any(|l| l.contains("openclaw"))
-            })
-            .unwrap_or(false)
-    } else {
-        false
-    };
-
-    // Check for native openclaw process
-    let native_running = Command::new("pgrep")
-        .args(["-fl", "openclaw"])
-        .output()
-        .await
-        .map(|o| o.status.success())
-        .unwrap_or(false);
-
-    // Check for docker-compose file
-    let compose_exists = home_dir()
-        .map(|h| h.join("openclaw-docker/docker-compose.yml").exists())
-        .unwrap_or(false);
-
-    // Check for native install
-    let native_installed = Command::new("which")
-        .arg("openclaw")
-        .output()
-        .await
-        .map(|o| o.status.success())
-        .unwrap_or(false);
-
-    let (installed, install_type, running) = if docker_running {
-        (true, Some("docker".to_string()), true)
-    } else if native_running {
-        (true, Some("native".to_string()), true)
-    } else if compose_exists {
-        (true, Some("docker".to_string()), false)
-    } else if native_installed {
-        (true, Some("native".to_string()), false)
-    } else {
-        (false, None, false)
-    };
+    // The OpenClaw probes a scan uses, so the two never disagree, but not
+    // the rest of the facts (port listing, versions): the deploy view
+    // waits on this every time it refreshes
+    let tools = facts::tools_on_path();
+    let has = |tool: &str| tools.iter().any(|t| t == tool);
+    let openclaw = facts::probe_openclaw(has("docker"), has("openclaw")).await;
 
     OpenClawStatus {
-        installed,
-        install_type,
-        running,
-        docker_available,
-        node_available,
-        homebrew_available,
+        installed: openclaw.install.is_some(),
+        running: openclaw.running,
+        docker_available: has("docker"),
+        node_available: has("node"),
+        homebrew_available: has("brew"),
+        install_type: openclaw.install.map(str::to_string),
     }
 }
 
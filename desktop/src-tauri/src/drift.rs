@@ -0,0 +1,380 @@
+use std::collections::HashMap;
+
+use crate::types::{CheckChange, CheckResult, Finding, FindingChange, ScanDiff, ScanRecord};
+
+/// Statuses that count as "passing" when classifying a change.
+fn is_passing(status: &str) -> bool {
+    status == "PASS" || status == "FIXED"
+}
+
+/// A check that didn't finish tells nothing about the host, which is as
+/// bad as a failure once it used to pass.
+fn is_unfinished(status: &str) -> bool {
+    status == "TIMEOUT" || status == "ERROR"
+}
+
+/// Whether moving from `old` to `new` makes things worse: starting to fail
+/// from any other status, or no longer finishing after passing.
+fn is_regression(old: &str, new: &str) -> bool {
+    (new == "FAIL" && old != "FAIL") || (is_passing(old) && is_unfinished(new))
+}
+
+/// Whether moving from `old` to `new` makes things better: passing after
+/// failing or not finishing.
+fn is_fix(old: &str, new: &str) -> bool {
+    (old == "FAIL" || is_unfinished(old)) && is_passing(new)
+}
+
+/// Compare two scans of (usually) the same host. `from` is the older
+/// baseline, `to` the newer one.
+pub fn diff_scans(from: &ScanRecord, to: &ScanRecord) -> ScanDiff {
+    let before: HashMap<&str, &CheckResult> = from
+        .checks
+        .iter()
+        .map(|c| (c.check_id.as_str(), c))
+        .collect();
+    let after: HashMap<&str, &CheckResult> =
+        to.checks.iter().map(|c| (c.check_id.as_str(), c)).collect();
+
+    let mut diff = ScanDiff {
+        from_id: from.id.clone(),
+        to_id: to.id.clone(),
+        score_from: from.summary.score,
+        score_to: to.summary.score,
+        score_delta: to.summary.score - from.summary.score,
+        grade_from: from.summary.grade.clone(),
+        grade_to: to.summary.grade.clone(),
+        regressions: Vec::new(),
+        fixes: Vec::new(),
+        changed: Vec::new(),
+        added: Vec::new(),
+        removed: Vec::new(),
+        unchanged: 0,
+        new_findings: Vec::new(),
+        resolved_findings: Vec::new(),
+        changed_findings: Vec::new(),
+    };
+
+    // Walk the newer scan in its own order so the report reads like the scan
+    for new in &to.checks {
+        let Some(old) = before.get(new.check_id.as_str()) else {
+            diff.added.push(change(None, Some(new)));
+            continue;
+        };
+        diff_findings(old, new, &mut diff);
+
+        if old.status == new.status {
+            diff.unchanged += 1;
+        } else if is_regression(&old.status, &new.status) {
+            diff.regressions.push(change(Some(old), Some(new)));
+        } else if is_fix(&old.status, &new.status) {
+            diff.fixes.push(change(Some(old), Some(new)));
+        } else {
+            diff.changed.push(change(Some(old), Some(new)));
+        }
+    }
+
+    for old in &from.checks {
+        if !after.contains_key(old.check_id.as_str()) {
+            diff.removed.push(change(Some(old), None));
+        }
+    }
+
+    diff
+}
+
+/// Compare the findings of a check present in both scans. Added and
+/// removed checks are already reported whole.
+fn diff_findings(old: &CheckResult, new: &CheckResult, diff: &mut ScanDiff) {
+    let before = keyed(&old.findings);
+    let after = keyed(&new.findings);
+
+    for (key, to) in &after {
+        let from = before.iter().find(|(k, _)| k == key).map(|(_, f)| *f);
+        if from.is_some_and(|f| f.status == to.status) {
+            continue;
+        }
+        let finding = finding_change(new, from, Some(to));
+        if to.status == "FAIL" {
+            diff.new_findings.push(finding);
+        } else if from.is_some_and(|f| f.status == "FAIL") {
+            diff.resolved_findings.push(finding);
+        } else {
+            diff.changed_findings.push(finding);
+        }
+    }
+
+    for (key, from) in &before {
+        if after.iter().any(|(k, _)| k == key) {
+            continue;
+        }
+        let finding = finding_change(new, Some(from), None);
+        if from.status == "FAIL" {
+            diff.resolved_findings.push(finding);
+        } else {
+            diff.changed_findings.push(finding);
+        }
+    }
+}
+
+/// Key each finding by its name and which occurrence of that name it is.
+fn keyed(findings: &[Finding]) -> Vec<((&str, usize), &Finding)> {
+    let mut keyed: Vec<((&str, usize), &Finding)> = Vec::new();
+    for finding in findings {
+        let nth = keyed
+            .iter()
+            .filter(|((name, _), _)| *name == finding.name)
+            .count();
+        keyed.push(((finding.name.as_str(), nth), finding));
+    }
+    keyed
+}
+
+fn finding_change(
+    check: &CheckResult,
+    old: Option<&Finding>,
+    new: Option<&Finding>,
+) -> FindingChange {
+    let named = new.or(old).expect("change needs at least one side");
+    FindingChange {
+        check_id: check.check_id.clone(),
+        check_name: check.check_name.clone(),
+        finding: named.name.clone(),
+        from_status: old.map(|f| f.status.clone()),
+        to_status: new.map(|f| f.status.clone()),
+        from_detail: old.map(|f| f.detail.clone()),
+        to_detail: new.map(|f| f.detail.clone()),
+    }
+}
+
+fn change(old: Option<&CheckResult>, new: Option<&CheckResult>) -> CheckChange {
+    let named = new.or(old).expect("change needs at least one side");
+    CheckChange {
+        check_id: named.check_id.clone(),
+        check_name: named.check_name.clone(),
+        from_status: old.map(|c| c.status.clone()),
+        to_status: new.map(|c| c.status.clone()),
+        from_detail: old.map(|c| c.detail.clone()),
+        to_detail: new.map(|c| c.detail.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scoring;
+    use crate::types::{GradingPolicy, PlatformInfo};
+
+    /// A check result with findings given as (name, status).
+    fn check(id: &str, status: &str, findings: &[(&str, &str)]) -> CheckResult {
+        let findings: Vec<_> = findings
+            .iter()
+            .map(|(name, status)| serde_json::json!({"name": name, "status": status, "detail": ""}))
+            .collect();
+        serde_json::from_value(serde_json::json!({
+            "check_id": id,
+            "check_name": id,
+            "phase": "p",
+            "status": status,
+            "detail": status,
+            "findings": findings,
+        }))
+        .unwrap()
+    }
+
+    fn record(id: &str, checks: Vec<CheckResult>) -> ScanRecord {
+        ScanRecord {
+            id: id.to_string(),
+            started_at: 0,
+            finished_at: 0,
+            hostname: "host".to_string(),
+            platform: PlatformInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                distro: None,
+                distro_family: None,
+                distro_version: None,
+                distro_name: None,
+            },
+            summary: scoring::summarize(&checks, &GradingPolicy::default()),
+            checks,
+            catalog: Vec::new(),
+            phases: Vec::new(),
+        }
+    }
+
+    /// Diff a single check's move from `old` to `new`.
+    fn diff_status(old: &str, new: &str) -> ScanDiff {
+        diff_scans(
+            &record("a", vec![check("c", old, &[])]),
+            &record("b", vec![check("c", new, &[])]),
+        )
+    }
+
+    fn ids(changes: &[CheckChange]) -> Vec<&str> {
+        changes.iter().map(|c| c.check_id.as_str()).collect()
+    }
+
+    #[test]
+    fn any_move_to_fail_is_a_regression() {
+        for old in ["PASS", "FIXED", "SKIPPED", "WAIVED", "TIMEOUT", "ERROR"] {
+            let diff = diff_status(old, "FAIL");
+            assert_eq!(ids(&diff.regressions), ["c"], "{} → FAIL", old);
+        }
+    }
+
+    #[test]
+    fn passing_to_unfinished_is_a_regression() {
+        for old in ["PASS", "FIXED"] {
+            for new in ["TIMEOUT", "ERROR"] {
+                let diff = diff_status(old, new);
+                assert_eq!(ids(&diff.regressions), ["c"], "{} → {}", old, new);
+            }
+        }
+    }
+
+    #[test]
+    fn passing_after_failing_or_not_finishing_is_a_fix() {
+        for old in ["FAIL", "TIMEOUT", "ERROR"] {
+            let diff = diff_status(old, "PASS");
+            assert_eq!(ids(&diff.fixes), ["c"], "{} → PASS", old);
+        }
+    }
+
+    #[test]
+    fn other_moves_are_changes() {
+        for (old, new) in [
+            ("PASS", "SKIPPED"),
+            ("SKIPPED", "PASS"),
+            ("FAIL", "TIMEOUT"),
+            ("FAIL", "WAIVED"),
+            ("PASS", "FIXED"),
+        ] {
+            let diff = diff_status(old, new);
+            assert!(diff.regressions.is_empty() && diff.fixes.is_empty());
+            assert_eq!(ids(&diff.changed), ["c"], "{} → {}", old, new);
+        }
+    }
+
+    #[test]
+    fn same_status_is_unchanged() {
+        let diff = diff_status("FAIL", "FAIL");
+        assert_eq!(diff.unchanged, 1);
+        assert!(diff.regressions.is_empty() && diff.changed.is_empty());
+    }
+
+    #[test]
+    fn added_and_removed_checks() {
+        let diff = diff_scans(
+            &record(
+                "a",
+                vec![check("old", "PASS", &[]), check("both", "PASS", &[])],
+            ),
+            &record(
+                "b",
+                vec![check("both", "PASS", &[]), check("new", "FAIL", &[])],
+            ),
+        );
+        assert_eq!(ids(&diff.added), ["new"]);
+        assert_eq!(ids(&diff.removed), ["old"]);
+        assert_eq!(diff.unchanged, 1);
+        assert!(
+            diff.regressions.is_empty(),
+            "an added check is not a regression"
+        );
+    }
+
+    #[test]
+    fn score_and_grade_deltas() {
+        let diff = diff_status("PASS", "FAIL");
+        assert_eq!((diff.score_from, diff.score_to), (100.0, 0.0));
+        assert_eq!(diff.score_delta, -100.0);
+        assert_eq!(
+            (diff.grade_from.as_str(), diff.grade_to.as_str()),
+            ("A", "F")
+        );
+    }
+
+    #[test]
+    fn new_finding_within_a_failing_check() {
+        let diff = diff_scans(
+            &record(
+                "a",
+                vec![check(
+                    "ssh",
+                    "FAIL",
+                    &[("PermitRootLogin", "FAIL"), ("X11Forwarding", "PASS")],
+                )],
+            ),
+            &record(
+                "b",
+                vec![check(
+                    "ssh",
+                    "FAIL",
+                    &[("PermitRootLogin", "FAIL"), ("X11Forwarding", "FAIL")],
+                )],
+            ),
+        );
+        assert_eq!(diff.unchanged, 1);
+        let new: Vec<&str> = diff
+            .new_findings
+            .iter()
+            .map(|f| f.finding.as_str())
+            .collect();
+        assert_eq!(new, ["X11Forwarding"]);
+        assert_eq!(diff.new_findings[0].from_status.as_deref(), Some("PASS"));
+        assert!(diff.resolved_findings.is_empty());
+    }
+
+    #[test]
+    fn resolved_and_vanished_findings() {
+        let diff = diff_scans(
+            &record(
+                "a",
+                vec![check(
+                    "ssh",
+                    "FAIL",
+                    &[("A", "FAIL"), ("B", "FAIL"), ("C", "PASS")],
+                )],
+            ),
+            &record("b", vec![check("ssh", "PASS", &[("A", "PASS")])]),
+        );
+        let resolved: Vec<&str> = diff
+            .resolved_findings
+            .iter()
+            .map(|f| f.finding.as_str())
+            .collect();
+        assert_eq!(resolved, ["A", "B"]);
+        assert_eq!(diff.resolved_findings[1].to_status, None);
+        let changed: Vec<&str> = diff
+            .changed_findings
+            .iter()
+            .map(|f| f.finding.as_str())
+            .collect();
+        assert_eq!(changed, ["C"]);
+    }
+
+    #[test]
+    fn repeated_finding_names_match_by_occurrence() {
+        let diff = diff_scans(
+            &record(
+                "a",
+                vec![check(
+                    "ports",
+                    "FAIL",
+                    &[("Port", "PASS"), ("Port", "FAIL")],
+                )],
+            ),
+            &record(
+                "b",
+                vec![check(
+                    "ports",
+                    "FAIL",
+                    &[("Port", "FAIL"), ("Port", "FAIL")],
+                )],
+            ),
+        );
+        assert_eq!(diff.new_findings.len(), 1);
+        assert!(diff.resolved_findings.is_empty() && diff.changed_findings.is_empty());
+    }
+}
@@ -0,0 +1,355 @@
+//! Standalone HTML report for attaching to audit tickets. Everything is
+//! inline so the file renders offline and from a mail attachment.
+
+use std::fmt::Write;
+
+use super::{escape, rfc3339};
+use crate::check_catalog::phase_label;
+use crate::types::{CheckMeta, CheckResult, PhaseInfo, ScanRecord, Waiver};
+
+const STYLE: &str = "
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; color: #1f2328; background: #f6f8fa; margin: 0; padding: 32px; }
+main { max-width: 960px; margin: 0 auto; }
+h1 { font-size: 24px; margin: 0 0 4px; }
+h2 { font-size: 18px; margin: 32px 0 12px; }
+.meta { color: #59636e; font-size: 14px; }
+.card { background: #fff; border: 1px solid #d1d9e0; border-radius: 8px; padding: 20px; margin-top: 20px; }
+.grade-card { display: flex; align-items: center; gap: 28px; }
+.grade { font-size: 64px; font-weight: 700; width: 96px; height: 96px; line-height: 96px; text-align: center; border-radius: 12px; color: #fff; }
+.grade-A { background: #1a7f37; } .grade-B { background: #4c9a2a; } .grade-C { background: #bf8700; }
+.grade-D { background: #d1570f; } .grade-F { background: #cf222e; }
+.score { font-size: 28px; font-weight: 600; }
+.counts span { margin-right: 16px; font-size: 14px; }
+table { width: 100%; border-collapse: collapse; font-size: 14px; }
+th, td { text-align: left; padding: 8px; border-bottom: 1px solid #d1d9e0; vertical-align: top; }
+th { color: #59636e; font-weight: 600; }
+.status { display: inline-block; min-width: 64px; text-align: center; font-size: 12px; font-weight: 600; padding: 2px 6px; border-radius: 10px; }
+.status-PASS, .status-FIXED { background: #dafbe1; color: #1a7f37; }
+.status-FAIL { background: #ffebe9; color: #cf222e; }
+.status-SKIPPED { background: #eaeef2; color: #59636e; }
+.status-WAIVED { background: #fbefff; color: #8250df; }
+.status-TIMEOUT, .status-ERROR { background: #fff8c5; color: #9a6700; }
+.description { color: #59636e; font-size: 13px; }
+ul.messages { margin: 6px 0 0; padding-left: 18px; font-size: 13px; }
+ul.findings { list-style: none; margin: 6px 0 0; padding: 0; font-size: 13px; }
+ul.findings li { margin: 3px 0; }
+.msg-warn { color: #9a6700; } .msg-error { color: #cf222e; } .msg-info { color: #59636e; }
+table.evidence { margin-top: 6px; font-size: 12px; }
+table.evidence td, table.evidence th { padding: 2px 8px 2px 0; border: none; }
+.hint { margin-top: 6px; font-size: 13px; background: #ddf4ff; border-radius: 6px; padding: 6px 8px; }
+pre.stderr { margin: 6px 0 0; padding: 6px 8px; background: #f6f8fa; border-radius: 6px; font-size: 12px; white-space: pre-wrap; }
+code, pre { font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 12px; }
+footer { margin-top: 32px; color: #59636e; font-size: 12px; }
+";
+
+/// Render the report. `checks` supplies descriptions; checks missing from
+/// it are still listed, just without one. `phase_info` supplies labels.
+pub fn render(record: &ScanRecord, checks: &[CheckMeta], phase_info: &[PhaseInfo]) -> String {
+    let mut phases: Vec<(&str, Vec<&CheckResult>)> = Vec::new();
+    for result in &record.checks {
+        match phases.iter_mut().find(|(phase, _)| *phase == result.phase) {
+            Some((_, results)) => results.push(result),
+            None => phases.push((&result.phase, vec![result])),
+        }
+    }
+
+    let os = record
+        .platform
+        .distro_name
+        .clone()
+        .unwrap_or_else(|| record.platform.os.clone());
+    let summary = &record.summary;
+
+    // Writing to a String cannot fail, so the fmt::Results are ignored
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Clawkeeper Security Report — {host}</title>\n<style>{style}</style>\n</head>\n\
+         <body>\n<main>\n<h1>Clawkeeper Security Report</h1>\n\
+         <div class=\"meta\">{host} · {os} ({arch}) · {started} → {finished}</div>\n",
+        host = escape(&record.hostname),
+        style = STYLE,
+        os = escape(&os),
+        arch = escape(&record.platform.arch),
+        started = rfc3339(record.started_at),
+        finished = rfc3339(record.finished_at),
+    );
+
+    let _ = write!(
+        html,
+        "<section class=\"card grade-card\">\n\
+         <div class=\"grade grade-{grade}\">{grade}</div>\n\
+         <div><div class=\"score\">{score:.0}%</div>\n<div class=\"counts\">\
+         <span>Passed: {passed}</span><span>Fixed: {fixed}</span><span>Failed: {failed}</span>\
+         <span>Skipped: {skipped}</span><span>Waived: {waived}</span><span>Errors: {errors}</span></div>\
+         {capped}</div>\n</section>\n",
+        grade = escape(&summary.grade),
+        score = summary.score,
+        passed = summary.passed,
+        fixed = summary.fixed,
+        failed = summary.failed,
+        skipped = summary.skipped,
+        waived = summary.waived,
+        errors = summary.errors,
+        capped = critical_note(summary.critical_failures.len()),
+    );
+
+    html.push_str(
+        "<h2>Phases</h2>\n<section class=\"card\">\n<table>\n\
+         <tr><th>Phase</th><th>Grade</th><th>Score</th><th>Passed</th><th>Failed</th>\
+         <th>Skipped</th><th>Waived</th><th>Errors</th></tr>\n",
+    );
+    for phase in &summary.phases {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{:.0}%</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(phase_label(phase_info, &phase.phase)),
+            escape(&phase.grade),
+            phase.score,
+            phase.passed,
+            phase.failed,
+            phase.skipped,
+            phase.waived,
+            phase.errors
+        );
+    }
+    html.push_str("</table>\n</section>\n");
+
+    for (phase, results) in &phases {
+        let _ = write!(
+            html,
+            "<h2>{}</h2>\n<section class=\"card\">\n<table>\n\
+             <tr><th>Status</th><th>Check</th><th>Result</th></tr>\n",
+            escape(phase_label(phase_info, phase))
+        );
+        for result in results {
+            let meta = checks.iter().find(|c| c.id == result.check_id);
+            write_check(&mut html, result, meta);
+        }
+        html.push_str("</table>\n</section>\n");
+    }
+
+    write_waivers(&mut html, &record.checks);
+
+    let _ = write!(
+        html,
+        "<footer>Scan {} · Generated by Clawkeeper {}. This is a point-in-time snapshot; \
+         settings drift over time.</footer>\n</main>\n</body>\n</html>\n",
+        escape(&record.id),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    html
+}
+
+fn write_check(html: &mut String, result: &CheckResult, meta: Option<&CheckMeta>) {
+    let _ = write!(
+        html,
+        "<tr><td><span class=\"status status-{status}\">{status}</span></td>\
+         <td><strong>{name}</strong>",
+        status = escape(&result.status),
+        name = escape(&result.check_name),
+    );
+    if let Some(meta) = meta {
+        let _ = write!(
+            html,
+            "<div class=\"description\">{}</div>",
+            escape(&meta.description)
+        );
+    }
+    let _ = write!(html, "</td><td>{}", escape(&result.detail));
+
+    // A single finding is already the check's own detail
+    if result.findings.len() > 1 {
+        html.push_str("<ul class=\"findings\">");
+        for finding in &result.findings {
+            let _ = write!(
+                html,
+                "<li><span class=\"status status-{status}\">{status}</span> <strong>{name}</strong> {detail}</li>",
+                status = escape(&finding.status),
+                name = escape(&finding.name),
+                detail = escape(&finding.detail),
+            );
+        }
+        html.push_str("</ul>");
+    }
+
+    if !result.evidence.is_empty() {
+        html.push_str(
+            "<table class=\"evidence\"><tr><th>Evidence</th><th>Observed</th>\
+             <th>Expected</th><th>Source</th></tr>",
+        );
+        for evidence in &result.evidence {
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td><code>{}</code></td><td><code>{}</code></td><td>{}</td></tr>",
+                escape(&evidence.key),
+                escape(&evidence.observed),
+                escape(evidence.expected.as_deref().unwrap_or("")),
+                escape(evidence.source.as_deref().unwrap_or(""))
+            );
+        }
+        html.push_str("</table>");
+    }
+
+    if !result.messages.is_empty() {
+        html.push_str("<ul class=\"messages\">");
+        for message in &result.messages {
+            let _ = write!(
+                html,
+                "<li class=\"msg-{}\">{}</li>",
+                escape(&message.level),
+                escape(&message.message)
+            );
+        }
+        html.push_str("</ul>");
+    }
+
+    if let Some(ref diagnostics) = result.diagnostics {
+        let exit = match (diagnostics.exit_code, diagnostics.signal) {
+            (Some(code), _) => format!("check.sh exited with code {}", code),
+            (None, Some(signal)) => format!("check.sh was killed by signal {}", signal),
+            (None, None) => "check.sh output on stderr".to_string(),
+        };
+        let _ = write!(html, "<div class=\"description\">{}</div>", escape(&exit));
+        if !diagnostics.stderr.is_empty() {
+            let _ = write!(
+                html,
+                "<pre class=\"stderr\">{}{}</pre>",
+                escape(&diagnostics.stderr),
+                if diagnostics.stderr_truncated {
+                    "\n…"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+
+    // Only worth suggesting while the check is still failing
+    if let Some(ref hint) = result.remediation {
+        if result.status == "FAIL" {
+            let _ = write!(
+                html,
+                "<div class=\"hint\">Suggested fix: {}. Clawkeeper can apply it for you: \
+                 re-run the scan in the desktop app or clawkeeper.sh and accept the fix \
+                 when offered.</div>",
+                escape(&fix_action(&hint.message))
+            );
+        }
+    }
+
+    html.push_str("</td></tr>\n");
+}
+
+/// A remediation prompt's question as the action it takes, e.g.
+/// "Disable Bluetooth? (skip if you need wireless peripherals)" becomes
+/// "Disable Bluetooth (skip if you need wireless peripherals)".
+fn fix_action(prompt: &str) -> String {
+    prompt.replace('?', "").trim().to_string()
+}
+
+/// Accepted risks get their own table so a reviewer sees every exception,
+/// who owns it and when it lapses, apart from the results.
+fn write_waivers(html: &mut String, results: &[CheckResult]) {
+    let waived: Vec<(&CheckResult, &Waiver)> = results
+        .iter()
+        .flat_map(|result| result.waivers.iter().map(move |waiver| (result, waiver)))
+        .collect();
+    if waived.is_empty() {
+        return;
+    }
+
+    html.push_str(
+        "<h2>Accepted risks</h2>\n<section class=\"card\">\n<table>\n\
+         <tr><th>Check</th><th>Finding</th><th>Justification</th><th>Owner</th><th>Expires</th></tr>\n",
+    );
+    for (result, waiver) in waived {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(&result.check_name),
+            escape(waiver.finding.as_deref().unwrap_or("All")),
+            escape(&waiver.justification),
+            escape(&waiver.owner),
+            escape(&waiver.expires)
+        );
+    }
+    html.push_str("</table>\n</section>\n");
+}
+
+fn critical_note(count: usize) -> String {
+    match count {
+        0 => String::new(),
+        1 => "<div class=\"meta\">1 critical check failed</div>".to_string(),
+        n => format!("<div class=\"meta\">{} critical checks failed</div>", n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::record;
+    use serde_json::json;
+
+    #[test]
+    fn prompts_read_as_actions() {
+        assert_eq!(
+            fix_action("Disable Bluetooth? (skip if you need wireless peripherals)"),
+            "Disable Bluetooth (skip if you need wireless peripherals)"
+        );
+        assert_eq!(fix_action("Enable the firewall?"), "Enable the firewall");
+    }
+
+    #[test]
+    fn check_output_is_escaped() {
+        let html = render(
+            &record(json!([
+                { "check_id": "x", "check_name": "<script>alert(1)</script>", "phase": "hardening",
+                  "status": "FAIL", "detail": "\"quoted\" & 'single'",
+                  "messages": [ { "level": "warn", "message": "<img src=x>" } ] },
+            ])),
+            &[],
+            &[],
+        );
+        assert!(!html.contains("<script>alert"));
+        assert!(!html.contains("<img src=x>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(html.contains("&quot;quoted&quot; &amp; &apos;single&apos;"));
+    }
+
+    #[test]
+    fn suggested_fix_only_while_failing() {
+        let check = |status: &str| {
+            json!({ "check_id": "bt", "check_name": "Bluetooth", "phase": "hardening",
+                    "status": status, "detail": "on",
+                    "remediation": { "remediation_id": "disable_bt",
+                                     "message": "Disable Bluetooth?" } })
+        };
+        let html = render(&record(json!([check("FAIL")])), &[], &[]);
+        assert!(html.contains("Suggested fix: Disable Bluetooth. Clawkeeper can apply it"));
+        assert!(!html.contains("disable_bt"));
+
+        let html = render(&record(json!([check("FIXED")])), &[], &[]);
+        assert!(!html.contains("Suggested fix"));
+    }
+
+    #[test]
+    fn waivers_are_listed_with_their_owner() {
+        let html = render(
+            &record(json!([
+                { "check_id": "ssh", "check_name": "SSH", "phase": "hardening",
+                  "status": "WAIVED", "detail": "",
+                  "waivers": [ { "check": "ssh", "finding": "X11Forwarding",
+                                 "justification": "GUI <tools>", "owner": "alice",
+                                 "expires": "2026-12-31" } ] },
+            ])),
+            &[],
+            &[],
+        );
+        assert!(html.contains(
+            "<tr><td>SSH</td><td>X11Forwarding</td><td>GUI &lt;tools&gt;</td><td>alice</td><td>2026-12-31</td></tr>"
+        ));
+    }
+}
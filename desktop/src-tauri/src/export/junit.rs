@@ -0,0 +1,253 @@
+//! JUnit XML export, so scans show up as test reports in CI.
+//! Each phase is a testsuite and each check a testcase.
+
+use std::fmt::Write;
+
+use super::{describe_evidence, escape, rfc3339};
+use crate::check_catalog::phase_label;
+use crate::types::{CheckResult, PhaseInfo, ScanRecord};
+use crate::waivers;
+
+pub fn render(record: &ScanRecord, phases: &[PhaseInfo]) -> Result<String, String> {
+    // Group by phase, keeping the order phases ran in
+    let mut suites: Vec<(&str, Vec<&CheckResult>)> = Vec::new();
+    for result in &record.checks {
+        match suites.iter_mut().find(|(phase, _)| *phase == result.phase) {
+            Some((_, cases)) => cases.push(result),
+            None => suites.push((&result.phase, vec![result])),
+        }
+    }
+
+    let all: Vec<&CheckResult> = record.checks.iter().collect();
+    let timestamp = rfc3339(record.started_at);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(xml, "<testsuites name=\"Clawkeeper\" {}>", counts(&all)).map_err(fmt_err)?;
+
+    for (index, (phase, cases)) in suites.iter().enumerate() {
+        writeln!(
+            xml,
+            "  <testsuite name=\"{}\" id=\"{}\" package=\"clawkeeper.{}\" hostname=\"{}\" timestamp=\"{}\" {}>",
+            escape(phase_label(phases, phase)),
+            index,
+            escape(phase),
+            escape(&record.hostname),
+            timestamp,
+            counts(cases)
+        )
+        .map_err(fmt_err)?;
+        for case in cases {
+            write_testcase(&mut xml, case).map_err(fmt_err)?;
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    Ok(xml)
+}
+
+/// tests/failures/errors/skipped/time attributes shared by both levels.
+fn counts(cases: &[&CheckResult]) -> String {
+    let count = |status: &str| cases.iter().filter(|c| c.status == status).count();
+    let time: u64 = cases.iter().map(|c| c.duration_ms).sum();
+    format!(
+        "tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{}\"",
+        cases.len(),
+        count("FAIL"),
+        count("TIMEOUT") + count("ERROR"),
+        count("SKIPPED") + count("WAIVED"),
+        seconds(time)
+    )
+}
+
+fn write_testcase(xml: &mut String, case: &CheckResult) -> std::fmt::Result {
+    write!(
+        xml,
+        "    <testcase name=\"{}\" classname=\"clawkeeper.{}.{}\" time=\"{}\"",
+        escape(&case.check_name),
+        escape(&case.phase),
+        escape(&case.check_id),
+        seconds(case.duration_ms)
+    )?;
+
+    let outcome = match case.status.as_str() {
+        "FAIL" => Some(("failure", "FAIL")),
+        "TIMEOUT" => Some(("error", "TIMEOUT")),
+        "ERROR" => Some(("error", "ERROR")),
+        "SKIPPED" | "WAIVED" => Some(("skipped", "")),
+        _ => None,
+    };
+    if outcome.is_none() && case.messages.is_empty() && case.diagnostics.is_none() {
+        return writeln!(xml, "/>");
+    }
+    writeln!(xml, ">")?;
+
+    match outcome {
+        Some(("skipped", _)) if case.status == "WAIVED" => {
+            let reasons: Vec<String> = case.waivers.iter().map(waivers::describe).collect();
+            writeln!(
+                xml,
+                "      <skipped message=\"{}\"/>",
+                escape(&reasons.join("; "))
+            )?;
+        }
+        Some(("skipped", _)) => {
+            writeln!(xml, "      <skipped message=\"{}\"/>", escape(&case.detail))?;
+        }
+        Some((element, kind)) => {
+            // List each failing finding on its own line in the body,
+            // followed by the evidence for it
+            let failing: Vec<String> = case
+                .findings
+                .iter()
+                .filter(|f| f.status == "FAIL")
+                .map(|f| format!("{}: {}", f.name, f.detail))
+                .collect();
+            let mut body = if failing.is_empty() {
+                case.detail.clone()
+            } else {
+                failing.join("\n")
+            };
+            for evidence in &case.evidence {
+                body.push_str(&format!("\n  {}", describe_evidence(evidence)));
+            }
+            writeln!(
+                xml,
+                "      <{} message=\"{}\" type=\"{}\">{}</{}>",
+                element,
+                escape(&case.detail),
+                kind,
+                escape(&body),
+                element
+            )?;
+        }
+        None => {}
+    }
+
+    if !case.messages.is_empty() {
+        let output: Vec<String> = case
+            .messages
+            .iter()
+            .map(|m| format!("[{}] {}", m.level, m.message))
+            .collect();
+        writeln!(
+            xml,
+            "      <system-out>{}</system-out>",
+            escape(&output.join("\n"))
+        )?;
+    }
+
+    if let Some(ref diagnostics) = case.diagnostics {
+        let mut err = match (diagnostics.exit_code, diagnostics.signal) {
+            (Some(code), _) => format!("exit code {}", code),
+            (None, Some(signal)) => format!("killed by signal {}", signal),
+            (None, None) => String::new(),
+        };
+        if !diagnostics.stderr.is_empty() {
+            if !err.is_empty() {
+                err.push('\n');
+            }
+            err.push_str(&diagnostics.stderr);
+        }
+        writeln!(xml, "      <system-err>{}</system-err>", escape(&err))?;
+    }
+
+    writeln!(xml, "    </testcase>")
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+fn fmt_err(e: std::fmt::Error) -> String {
+    format!("Failed to write JUnit XML: {}", e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::record;
+    use serde_json::json;
+
+    fn phases() -> Vec<PhaseInfo> {
+        vec![PhaseInfo {
+            id: "hardening".to_string(),
+            label: "Hardening & <Network>".to_string(),
+            order: 1,
+            description: String::new(),
+        }]
+    }
+
+    #[test]
+    fn suites_follow_phases_and_count_outcomes() {
+        let xml = render(
+            &record(json!([
+                { "check_id": "a", "check_name": "A", "phase": "prerequisites",
+                  "status": "PASS", "detail": "", "duration_ms": 1500 },
+                { "check_id": "b", "check_name": "B", "phase": "hardening",
+                  "status": "FAIL", "detail": "bad" },
+                { "check_id": "c", "check_name": "C", "phase": "hardening",
+                  "status": "TIMEOUT", "detail": "slow" },
+                { "check_id": "d", "check_name": "D", "phase": "hardening",
+                  "status": "SKIPPED", "detail": "n/a" },
+            ])),
+            &phases(),
+        )
+        .unwrap();
+        assert!(xml.contains(
+            "<testsuites name=\"Clawkeeper\" tests=\"4\" failures=\"1\" errors=\"1\" skipped=\"1\" time=\"1.500\">"
+        ));
+        assert!(xml.contains("<testsuite name=\"prerequisites\" id=\"0\""));
+        assert!(xml.contains("<testsuite name=\"Hardening &amp; &lt;Network&gt;\" id=\"1\""));
+        assert!(xml.contains(
+            "<testcase name=\"A\" classname=\"clawkeeper.prerequisites.a\" time=\"1.500\"/>"
+        ));
+        assert!(xml.contains("<error message=\"slow\" type=\"TIMEOUT\">slow</error>"));
+        assert!(xml.contains("<skipped message=\"n/a\"/>"));
+    }
+
+    #[test]
+    fn failure_text_is_escaped() {
+        let xml = render(
+            &record(json!([
+                { "check_id": "ssh", "check_name": "SSH \"strict\" <mode>", "phase": "hardening",
+                  "status": "FAIL", "detail": "a & b\u{1b}[0m",
+                  "findings": [
+                      { "name": "PermitRootLogin", "status": "FAIL", "detail": "'yes'" },
+                      { "name": "Ciphers", "status": "PASS", "detail": "" },
+                  ],
+                  "evidence": [
+                      { "key": "PermitRootLogin", "observed": "yes", "expected": "no" },
+                  ],
+                  "messages": [ { "level": "warn", "message": "</system-out>" } ] },
+            ])),
+            &phases(),
+        )
+        .unwrap();
+        assert!(xml.contains("name=\"SSH &quot;strict&quot; &lt;mode&gt;\""));
+        assert!(xml.contains(
+            "<failure message=\"a &amp; b[0m\" type=\"FAIL\">PermitRootLogin: &apos;yes&apos;\n  PermitRootLogin = yes (expected no)</failure>"
+        ));
+        assert!(xml.contains("<system-out>[warn] &lt;/system-out&gt;</system-out>"));
+        assert!(!xml.contains('\u{1b}'));
+    }
+
+    #[test]
+    fn waived_checks_are_skipped_with_the_waiver() {
+        let xml = render(
+            &record(json!([
+                { "check_id": "bt", "check_name": "Bluetooth", "phase": "hardening",
+                  "status": "WAIVED", "detail": "on",
+                  "waivers": [
+                      { "check": "bt", "justification": "headset <required>",
+                        "owner": "alice", "expires": "2026-12-31" },
+                  ] },
+            ])),
+            &phases(),
+        )
+        .unwrap();
+        assert!(xml.contains(
+            "<skipped message=\"Waived by alice until 2026-12-31: headset &lt;required&gt;\"/>"
+        ));
+    }
+}
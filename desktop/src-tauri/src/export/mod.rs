@@ -0,0 +1,161 @@
+//! Render a completed scan into formats other tools understand.
+
+pub mod html;
+pub mod junit;
+pub mod sarif;
+
+use crate::types::{CheckMeta, Evidence, PhaseInfo, ScanRecord};
+
+/// Render `record` in the named format ("sarif", "junit" or "html"). `checks`
+/// supplies rule metadata (descriptions, tags) and `phases` the phase
+/// labels; anything missing from them falls back to its name or id.
+pub fn render(
+    format: &str,
+    record: &ScanRecord,
+    checks: &[CheckMeta],
+    phases: &[PhaseInfo],
+) -> Result<String, String> {
+    match format {
+        "sarif" => sarif::render(record, checks),
+        "junit" => junit::render(record, phases),
+        "html" => Ok(html::render(record, checks, phases)),
+        other => Err(format!("Unknown export format: {}", other)),
+    }
+}
+
+/// One line per piece of evidence, e.g.
+/// "PermitRootLogin = yes (expected no) in /etc/ssh/sshd_config".
+pub fn describe_evidence(evidence: &Evidence) -> String {
+    let mut line = format!("{} = {}", evidence.key, evidence.observed);
+    if let Some(ref expected) = evidence.expected {
+        line.push_str(&format!(" (expected {})", expected));
+    }
+    if let Some(ref source) = evidence.source {
+        line.push_str(&format!(" in {}", source));
+    }
+    line
+}
+
+/// Format Unix seconds as an RFC 3339 UTC timestamp.
+pub fn rfc3339(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    )
+}
+
+/// Days since 1970-01-01 → (year, month, day), per Howard Hinnant's
+/// `civil_from_days`. Avoids pulling in chrono for one conversion.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Escape text for XML/HTML attributes and element content.
+pub(crate) fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // XML 1.0 forbids most control characters even when escaped
+            c if c.is_control() && !matches!(c, '\n' | '\t' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scan of host "web-1" with `checks` as its results.
+    pub(super) fn record(checks: serde_json::Value) -> ScanRecord {
+        serde_json::from_value(serde_json::json!({
+            "id": "scan-1",
+            "started_at": 1_700_000_000,
+            "finished_at": 1_700_000_060,
+            "hostname": "web-1",
+            "platform": {
+                "os": "linux",
+                "arch": "x86_64",
+                "distro": null,
+                "distro_family": null,
+                "distro_version": null,
+                "distro_name": null,
+            },
+            "summary": {
+                "passed": 0, "failed": 0, "fixed": 0, "skipped": 0, "errors": 0,
+                "total": 0, "score": 50.0, "grade": "F",
+            },
+            "checks": checks,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn escape_covers_markup_and_drops_control_characters() {
+        assert_eq!(
+            escape(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &apos;Jerry&apos;&lt;/a&gt;"
+        );
+        assert_eq!(escape("a\u{1b}[31mb\u{0}c"), "a[31mbc");
+        assert_eq!(escape("line\n\tnext\r"), "line\n\tnext\r");
+        assert_eq!(escape("ünïcode ✓"), "ünïcode ✓");
+    }
+
+    #[test]
+    fn rfc3339_dates() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1_709_251_199), "2024-02-29T23:59:59Z");
+        assert_eq!(rfc3339(1_735_689_600), "2025-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn evidence_lines() {
+        let evidence = |expected: Option<&str>, source: Option<&str>| Evidence {
+            finding: None,
+            key: "PermitRootLogin".to_string(),
+            observed: "yes".to_string(),
+            expected: expected.map(str::to_string),
+            source: source.map(str::to_string),
+        };
+        assert_eq!(
+            describe_evidence(&evidence(None, None)),
+            "PermitRootLogin = yes"
+        );
+        assert_eq!(
+            describe_evidence(&evidence(Some("no"), Some("/etc/ssh/sshd_config"))),
+            "PermitRootLogin = yes (expected no) in /etc/ssh/sshd_config"
+        );
+    }
+
+    #[test]
+    fn unknown_format_is_an_error() {
+        assert_eq!(
+            render("csv", &record(serde_json::json!([])), &[], &[]).unwrap_err(),
+            "Unknown export format: csv"
+        );
+    }
+}
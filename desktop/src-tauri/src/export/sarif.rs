@@ -0,0 +1,275 @@
+//! SARIF 2.1.0 export, so findings land in code-scanning dashboards
+//! alongside other scanners.
+
+use serde_json::{json, Value};
+
+use super::rfc3339;
+use crate::types::{CheckMeta, CheckResult, Evidence, Finding, ScanRecord};
+use crate::waivers;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// One rule per check in the scan, one result per failing finding. Waived
+/// findings are results too, suppressed with the waiver's justification.
+pub fn render(record: &ScanRecord, checks: &[CheckMeta]) -> Result<String, String> {
+    let rules: Vec<Value> = record
+        .checks
+        .iter()
+        .map(|result| rule(result, checks.iter().find(|c| c.id == result.check_id)))
+        .collect();
+
+    let results: Vec<Value> = record
+        .checks
+        .iter()
+        .enumerate()
+        .filter(|(_, result)| result.status == "FAIL" || result.status == "WAIVED")
+        .flat_map(|(index, result)| findings(index, result, &record.hostname))
+        .collect();
+
+    // Checks that never produced a verdict are tool problems, not findings
+    let notifications: Vec<Value> = record
+        .checks
+        .iter()
+        .filter(|result| result.status == "TIMEOUT" || result.status == "ERROR")
+        .map(notification)
+        .collect();
+
+    let sarif = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "Clawkeeper",
+                    "informationUri": "https://clawkeeper.dev",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "invocations": [{
+                "executionSuccessful": notifications.is_empty(),
+                "toolExecutionNotifications": notifications,
+                "startTimeUtc": rfc3339(record.started_at),
+                "endTimeUtc": rfc3339(record.finished_at),
+                "machine": record.hostname,
+            }],
+            "automationDetails": { "id": format!("clawkeeper/{}/{}", record.hostname, record.id) },
+            "results": results,
+            "properties": {
+                "score": record.summary.score,
+                "grade": record.summary.grade,
+                "platform": record.platform.os,
+            },
+        }]
+    });
+
+    serde_json::to_string_pretty(&sarif).map_err(|e| format!("Failed to serialize SARIF: {}", e))
+}
+
+fn rule(result: &CheckResult, meta: Option<&CheckMeta>) -> Value {
+    let description = meta
+        .map(|m| m.description.clone())
+        .unwrap_or_else(|| result.check_name.clone());
+    let mut tags = vec![result.phase.clone()];
+    if let Some(meta) = meta {
+        tags.extend(meta.tags.iter().cloned());
+    }
+
+    json!({
+        "id": result.check_id,
+        "name": result.check_name,
+        "shortDescription": { "text": result.check_name },
+        "fullDescription": { "text": description },
+        "defaultConfiguration": { "level": "error" },
+        "properties": { "tags": tags },
+    })
+}
+
+fn notification(result: &CheckResult) -> Value {
+    let mut text = format!(
+        "{} ({}): {}",
+        result.check_name, result.status, result.detail
+    );
+    if let Some(ref diagnostics) = result.diagnostics {
+        if !diagnostics.stderr.is_empty() {
+            text.push_str(&format!("\n{}", diagnostics.stderr));
+        }
+    }
+
+    json!({
+        "level": "error",
+        "message": { "text": text },
+        "associatedRule": { "id": result.check_id },
+    })
+}
+
+/// One SARIF result per failing finding, so each misconfiguration (e.g.
+/// PermitRootLogin, X11Forwarding) is tracked separately. Results saved
+/// before findings existed fall back to the check's detail.
+/// `index` is the check's position in the scan, which is also its rule index.
+fn findings(index: usize, result: &CheckResult, hostname: &str) -> Vec<Value> {
+    let failing: Vec<&Finding> = result
+        .findings
+        .iter()
+        .filter(|f| f.status == "FAIL" || f.status == "WAIVED")
+        .collect();
+
+    if failing.is_empty() {
+        let text = if result.detail.is_empty() {
+            format!("{} failed", result.check_name)
+        } else {
+            result.detail.clone()
+        };
+        let evidence: Vec<&Evidence> = result.evidence.iter().collect();
+        return vec![sarif_result(
+            index,
+            result,
+            hostname,
+            &result.check_name,
+            text,
+            &evidence,
+        )];
+    }
+
+    failing
+        .into_iter()
+        .map(|f| {
+            let text = if f.name == result.check_name {
+                f.detail.clone()
+            } else {
+                format!("{}: {}", f.name, f.detail)
+            };
+            let evidence: Vec<&Evidence> = result
+                .evidence
+                .iter()
+                .filter(|e| e.finding.as_deref() == Some(f.name.as_str()))
+                .collect();
+            let mut sarif = sarif_result(index, result, hostname, &f.name, text, &evidence);
+            if f.status == "WAIVED" {
+                if let Some(waiver) = waivers::find(&result.waivers, &result.check_id, f) {
+                    sarif["suppressions"] = json!([{
+                        "kind": "external",
+                        "status": "accepted",
+                        "justification": waiver.justification,
+                        "properties": { "owner": waiver.owner, "expires": waiver.expires },
+                    }]);
+                }
+            }
+            sarif
+        })
+        .collect()
+}
+
+fn sarif_result(
+    index: usize,
+    result: &CheckResult,
+    hostname: &str,
+    finding_name: &str,
+    text: String,
+    evidence: &[&Evidence],
+) -> Value {
+    json!({
+        "ruleId": result.check_id,
+        "ruleIndex": index,
+        "level": "error",
+        "message": { "text": text },
+        // Host findings have no source file; identify the machine instead
+        "locations": [{
+            "logicalLocations": [{ "name": hostname, "kind": "module" }]
+        }],
+        "partialFingerprints": {
+            "clawkeeperCheck/v1": format!("{}/{}/{}", hostname, result.check_id, finding_name)
+        },
+        "properties": { "evidence": evidence },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::record;
+
+    fn render_json(checks: Value) -> Value {
+        serde_json::from_str(&render(&record(checks), &[]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn one_result_per_failing_or_waived_finding() {
+        let sarif = render_json(json!([
+            { "check_id": "node", "check_name": "Node", "phase": "prerequisites",
+              "status": "PASS", "detail": "" },
+            { "check_id": "ssh", "check_name": "SSH", "phase": "hardening",
+              "status": "FAIL", "detail": "2 settings",
+              "findings": [
+                  { "name": "PermitRootLogin", "status": "FAIL", "detail": "\"yes\" <root>" },
+                  { "name": "PasswordAuthentication", "status": "PASS", "detail": "" },
+                  { "name": "X11Forwarding", "status": "WAIVED", "detail": "yes" },
+              ],
+              "evidence": [
+                  { "finding": "PermitRootLogin", "key": "PermitRootLogin", "observed": "yes" },
+                  { "finding": "X11Forwarding", "key": "X11Forwarding", "observed": "yes" },
+              ],
+              "waivers": [
+                  { "check": "ssh", "finding": "X11Forwarding", "justification": "GUI tools",
+                    "owner": "alice", "expires": "2026-12-31" },
+              ] },
+        ]));
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["invocations"][0]["executionSuccessful"], true);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleIndex"], 1);
+        // Quotes and angle brackets survive as plain JSON strings
+        assert_eq!(
+            results[0]["message"]["text"],
+            "PermitRootLogin: \"yes\" <root>"
+        );
+        assert_eq!(
+            results[0]["partialFingerprints"]["clawkeeperCheck/v1"],
+            "web-1/ssh/PermitRootLogin"
+        );
+        assert_eq!(
+            results[0]["properties"]["evidence"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(results[0].get("suppressions").is_none());
+        assert_eq!(results[1]["suppressions"][0]["justification"], "GUI tools");
+        assert_eq!(
+            results[1]["suppressions"][0]["properties"]["owner"],
+            "alice"
+        );
+    }
+
+    #[test]
+    fn checks_without_findings_fall_back_to_their_detail() {
+        let sarif = render_json(json!([
+            { "check_id": "fw", "check_name": "Firewall", "phase": "hardening",
+              "status": "FAIL", "detail": "" },
+        ]));
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["message"]["text"], "Firewall failed");
+    }
+
+    #[test]
+    fn unfinished_checks_are_notifications_not_results() {
+        let sarif = render_json(json!([
+            { "check_id": "slow", "check_name": "Slow", "phase": "hardening",
+              "status": "TIMEOUT", "detail": "Timed out after 30s",
+              "diagnostics": { "exit_code": null, "signal": 9, "stderr": "partial",
+                               "stderr_truncated": false } },
+        ]));
+        let invocation = &sarif["runs"][0]["invocations"][0];
+        assert_eq!(invocation["executionSuccessful"], false);
+        assert_eq!(
+            invocation["toolExecutionNotifications"][0]["message"]["text"],
+            "Slow (TIMEOUT): Timed out after 30s\npartial"
+        );
+        assert!(sarif["runs"][0]["results"].as_array().unwrap().is_empty());
+    }
+}
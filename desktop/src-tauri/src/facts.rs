@@ -0,0 +1,456 @@
+//! Host facts shared by every check in a scan. They are gathered once,
+//! rather than each check.sh re-detecting Docker or OpenClaw for itself,
+//! and reach checks as CLAWKEEPER_FACT_* environment variables. Checks can
+//! also name the facts they need in check.toml `requires`.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use tokio::process::Command;
+
+use crate::types::{HostFacts, PlatformInfo};
+
+/// How long any one probe (`docker info`, `openclaw --version`, ...) gets.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The port the OpenClaw gateway listens on.
+const GATEWAY_PORT: u16 = 18789;
+
+/// Tools whose presence is recorded in `HostFacts::tools`.
+pub const TOOLS: [&str; 14] = [
+    "brew",
+    "curl",
+    "docker",
+    "jq",
+    "lsof",
+    "netstat",
+    "node",
+    "npm",
+    "npx",
+    "openclaw",
+    "python3",
+    "ss",
+    "systemctl",
+    "ufw",
+];
+
+/// A fact a check may require: its name in check.toml `requires`, the
+/// reason given when it's missing, and how to tell from the facts.
+pub struct Requirement {
+    pub fact: &'static str,
+    pub missing: &'static str,
+    pub holds: fn(&HostFacts) -> bool,
+}
+
+/// Every fact a check may require.
+pub const REQUIREMENTS: [Requirement; 6] = [
+    Requirement {
+        fact: "docker",
+        missing: "Docker is not installed",
+        holds: |facts| facts.docker,
+    },
+    Requirement {
+        fact: "docker_running",
+        missing: "Docker is not running",
+        holds: |facts| facts.docker_running,
+    },
+    Requirement {
+        fact: "openclaw",
+        missing: "OpenClaw is not installed",
+        holds: |facts| facts.openclaw,
+    },
+    Requirement {
+        fact: "node",
+        missing: "Node.js is not installed",
+        holds: |facts| has_tool(facts, "node"),
+    },
+    Requirement {
+        fact: "homebrew",
+        missing: "Homebrew is not installed",
+        holds: |facts| has_tool(facts, "brew"),
+    },
+    Requirement {
+        fact: "python3",
+        missing: "python3 is not installed",
+        holds: |facts| has_tool(facts, "python3"),
+    },
+];
+
+/// How OpenClaw is installed ("docker" or "native"), if at all, and
+/// whether it is running.
+pub struct OpenClawProbe {
+    pub install: Option<&'static str>,
+    pub running: bool,
+    pub docker_running: bool,
+}
+
+/// Gather the facts for this host.
+pub async fn gather(host: &PlatformInfo) -> HostFacts {
+    let tools = tools_on_path();
+    let has = |tool: &str| tools.iter().any(|t| t == tool);
+
+    let docker = has("docker");
+    let native_installed = has("openclaw");
+    let openclaw = probe_openclaw(docker, native_installed).await;
+    let config_exists = home_dir().is_some_and(|h| h.join(".openclaw").is_dir());
+
+    let openclaw_version = if native_installed {
+        probe("openclaw", &["--version"])
+            .await
+            .and_then(|output| parse_version(&output))
+    } else {
+        None
+    };
+
+    let os_version = if host.os == "macos" {
+        probe("sw_vers", &["-productVersion"])
+            .await
+            .map(|version| version.trim().to_string())
+    } else {
+        host.distro_version.clone()
+    };
+
+    HostFacts {
+        os_version,
+        docker,
+        docker_running: openclaw.docker_running,
+        openclaw: openclaw.install.is_some() || config_exists,
+        openclaw_install: openclaw.install.map(str::to_string),
+        openclaw_running: openclaw.running,
+        openclaw_version,
+        listening_ports: listening_ports(host).await,
+        tools,
+    }
+}
+
+/// Tools from `TOOLS` that are on $PATH.
+pub fn tools_on_path() -> Vec<String> {
+    TOOLS
+        .iter()
+        .filter(|tool| on_path(tool))
+        .map(|tool| tool.to_string())
+        .collect()
+}
+
+/// Just the OpenClaw probes from `gather`, for callers that need nothing
+/// else (e.g. the deploy view).
+/// `docker` and `native_installed` are whether docker and openclaw are on
+/// $PATH.
+pub async fn probe_openclaw(docker: bool, native_installed: bool) -> OpenClawProbe {
+    let docker_running = docker && probe("docker", &["info"]).await.is_some();
+    let container_running = docker_running
+        && probe("docker", &["ps", "--format", "{{.Names}}"])
+            .await
+            .is_some_and(|names| names.lines().any(|l| l.contains("openclaw")));
+    // The process itself, not anything with "openclaw" in its command line
+    // (an editor, this app, a grep), or else the gateway's port
+    let native_running = !container_running
+        && (probe("pgrep", &["-x", "openclaw"]).await.is_some() || gateway_listening().await);
+
+    let compose_exists =
+        home_dir().is_some_and(|h| h.join("openclaw-docker/docker-compose.yml").exists());
+
+    // A running install wins over one that is merely present
+    let (install, running) = if container_running {
+        (Some("docker"), true)
+    } else if native_running {
+        (Some("native"), true)
+    } else if compose_exists {
+        (Some("docker"), false)
+    } else if native_installed {
+        (Some("native"), false)
+    } else {
+        (None, false)
+    };
+    OpenClawProbe {
+        install,
+        running,
+        docker_running,
+    }
+}
+
+/// Why a check with these `requires` can't run here, if it can't.
+pub fn unmet(facts: &HostFacts, requires: &[String]) -> Option<String> {
+    requires
+        .iter()
+        .find_map(|name| match REQUIREMENTS.iter().find(|r| r.fact == name) {
+            Some(r) if (r.holds)(facts) => None,
+            Some(r) => Some(format!("{} (requires {})", r.missing, name)),
+            None => Some(format!("Unknown requirement \"{}\"", name)),
+        })
+}
+
+fn has_tool(facts: &HostFacts, tool: &str) -> bool {
+    facts.tools.iter().any(|t| t == tool)
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(PathBuf::from)
+}
+
+/// Whether something accepts connections on the gateway port locally.
+/// Connecting to loopback is refused at once when nothing listens.
+async fn gateway_listening() -> bool {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, GATEWAY_PORT));
+    let connect = TcpStream::connect(addr);
+    matches!(
+        tokio::time::timeout(Duration::from_millis(500), connect).await,
+        Ok(Ok(_))
+    )
+}
+
+/// The facts as environment variables for check scripts. Flags are "1"
+/// or "0", lists are space-separated and unknown values are empty.
+/// `CLAWKEEPER_FACTS=1` tells a script the rest are set; the bash CLI
+/// doesn't set them.
+pub fn env_vars(facts: &HostFacts) -> Vec<(String, String)> {
+    let flag = |value: bool| if value { "1" } else { "0" }.to_string();
+    let join = |items: Vec<String>| items.join(" ");
+    [
+        ("FACTS", "1".to_string()),
+        (
+            "FACT_OS_VERSION",
+            facts.os_version.clone().unwrap_or_default(),
+        ),
+        ("FACT_TOOLS", join(facts.tools.clone())),
+        ("FACT_DOCKER", flag(facts.docker)),
+        ("FACT_DOCKER_RUNNING", flag(facts.docker_running)),
+        ("FACT_OPENCLAW", flag(facts.openclaw)),
+        (
+            "FACT_OPENCLAW_INSTALL",
+            facts.openclaw_install.clone().unwrap_or_default(),
+        ),
+        ("FACT_OPENCLAW_RUNNING", flag(facts.openclaw_running)),
+        (
+            "FACT_OPENCLAW_VERSION",
+            facts.openclaw_version.clone().unwrap_or_default(),
+        ),
+        (
+            "FACT_LISTENING_PORTS",
+            join(facts.listening_ports.iter().map(u16::to_string).collect()),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, value)| (format!("CLAWKEEPER_{}", name), value))
+    .collect()
+}
+
+/// Whether an executable of this name is on $PATH.
+fn on_path(tool: &str) -> bool {
+    std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|dir| is_executable(&dir.join(tool)))
+    })
+}
+
+/// A file with any execute bit set, as a shell would run it.
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Run a command and return its stdout if it exits successfully in time.
+async fn probe(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(PROBE_TIMEOUT, output).await {
+        Ok(Ok(output)) if output.status.success() => {
+            Some(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        _ => None,
+    }
+}
+
+/// The first x.y.z version number in `output`.
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find_map(|word| {
+            let parts: Vec<&str> = word.split('.').take(3).collect();
+            let valid = parts.len() == 3 && parts.iter().all(|p| !p.is_empty());
+            valid.then(|| parts.join("."))
+        })
+}
+
+/// TCP ports in the LISTEN state, sorted.
+async fn listening_ports(host: &PlatformInfo) -> Vec<u16> {
+    let mut ports = if host.os == "linux" {
+        ["/proc/net/tcp", "/proc/net/tcp6"]
+            .iter()
+            .filter_map(|path| std::fs::read_to_string(path).ok())
+            .flat_map(|table| proc_net_listeners(&table))
+            .collect()
+    } else {
+        probe("lsof", &["-nP", "-iTCP", "-sTCP:LISTEN"])
+            .await
+            .map(|output| lsof_listeners(&output))
+            .unwrap_or_default()
+    };
+    ports.sort_unstable();
+    ports.dedup();
+    ports
+}
+
+/// Ports from a /proc/net/tcp table, e.g. local address "0100007F:1F90"
+/// with state "0A" (LISTEN).
+fn proc_net_listeners(table: &str) -> Vec<u16> {
+    table
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.get(3) != Some(&"0A") {
+                return None;
+            }
+            let (_, port) = fields.get(1)?.rsplit_once(':')?;
+            u16::from_str_radix(port, 16).ok()
+        })
+        .collect()
+}
+
+/// Ports from `lsof -iTCP -sTCP:LISTEN`, whose NAME column reads e.g.
+/// "*:18789" or "127.0.0.1:631".
+fn lsof_listeners(output: &str) -> Vec<u16> {
+    output
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let name = line.split_whitespace().nth(8)?;
+            let (_, port) = name.rsplit_once(':')?;
+            port.parse().ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proc_net_listeners_reads_listen_sockets_only() {
+        let table = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:4965 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 1 1
+   1: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 2 1
+   2: 0100007F:A1B2 0100007F:4965 01 00000000:00000000 00:00000000 00000000  1000        0 3 1
+";
+        assert_eq!(proc_net_listeners(table), [18789, 22]);
+    }
+
+    #[test]
+    fn proc_net_listeners_reads_ipv6() {
+        let table = "\
+  sl  local_address                         remote_address                        st
+   0: 00000000000000000000000000000000:1F90 00000000000000000000000000000000:0000 0A
+";
+        assert_eq!(proc_net_listeners(table), [8080]);
+    }
+
+    #[test]
+    fn proc_net_listeners_skips_malformed_lines() {
+        assert!(proc_net_listeners("header\n   0: nonsense 0A\n   1:\n").is_empty());
+        assert!(proc_net_listeners("").is_empty());
+    }
+
+    #[test]
+    fn lsof_listeners_reads_the_name_column() {
+        let output = "\
+COMMAND   PID USER   FD   TYPE             DEVICE SIZE/OFF NODE NAME
+node    41234 me     23u  IPv4 0x1234567890abcdef      0t0  TCP *:18789 (LISTEN)
+cupsd     512 root    5u  IPv6 0x1234567890abcdf0      0t0  TCP [::1]:631 (LISTEN)
+cupsd     512 root    6u  IPv4 0x1234567890abcdf1      0t0  TCP 127.0.0.1:631 (LISTEN)
+";
+        assert_eq!(lsof_listeners(output), [18789, 631, 631]);
+    }
+
+    #[test]
+    fn lsof_listeners_skips_short_lines() {
+        assert!(lsof_listeners("COMMAND PID\nnode 1 me 3u IPv4\n").is_empty());
+    }
+
+    #[test]
+    fn parse_version_finds_the_first_full_version() {
+        assert_eq!(parse_version("2026.1.29").as_deref(), Some("2026.1.29"));
+        assert_eq!(
+            parse_version("openclaw v1.2.3 (abc)").as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(parse_version("v22.11.0\n").as_deref(), Some("22.11.0"));
+        assert_eq!(parse_version("1.2.3.4").as_deref(), Some("1.2.3"));
+        assert_eq!(parse_version("version 1.2 build 7").as_deref(), None);
+        assert_eq!(parse_version("").as_deref(), None);
+    }
+
+    #[test]
+    fn unmet_names_the_first_missing_requirement() {
+        let facts = HostFacts {
+            docker: true,
+            tools: vec!["node".to_string()],
+            ..Default::default()
+        };
+        let requires =
+            |names: &[&str]| -> Vec<String> { names.iter().map(|n| n.to_string()).collect() };
+        assert_eq!(unmet(&facts, &requires(&[])), None);
+        assert_eq!(unmet(&facts, &requires(&["docker", "node"])), None);
+        assert_eq!(
+            unmet(&facts, &requires(&["docker", "docker_running", "python3"])).as_deref(),
+            Some("Docker is not running (requires docker_running)")
+        );
+        assert_eq!(
+            unmet(&facts, &requires(&["gpu"])).as_deref(),
+            Some("Unknown requirement \"gpu\"")
+        );
+    }
+
+    #[test]
+    fn env_vars_flags_and_lists() {
+        let facts = HostFacts {
+            tools: vec!["curl".to_string(), "jq".to_string()],
+            docker: true,
+            listening_ports: vec![22, 18789],
+            ..Default::default()
+        };
+        let vars = env_vars(&facts);
+        let var = |name: &str| {
+            vars.iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(var("CLAWKEEPER_FACTS"), Some("1"));
+        assert_eq!(var("CLAWKEEPER_FACT_TOOLS"), Some("curl jq"));
+        assert_eq!(var("CLAWKEEPER_FACT_DOCKER"), Some("1"));
+        assert_eq!(var("CLAWKEEPER_FACT_DOCKER_RUNNING"), Some("0"));
+        assert_eq!(var("CLAWKEEPER_FACT_OS_VERSION"), Some(""));
+        assert_eq!(var("CLAWKEEPER_FACT_LISTENING_PORTS"), Some("22 18789"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn is_executable_needs_an_execute_bit() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("clawkeeper-facts-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let tool = dir.join("tool");
+        std::fs::write(&tool, "#!/bin/sh\n").unwrap();
+
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(!is_executable(&tool));
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(is_executable(&tool));
+        assert!(!is_executable(&dir), "a directory is not a tool");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
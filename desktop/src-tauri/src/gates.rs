@@ -0,0 +1,275 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::scoring::{self, grade_rank};
+use crate::types::{CheckMeta, CheckResult, GateViolation, ScanSummary};
+
+/// Pass/fail policy applied to a finished scan, for CI pipelines.
+/// Loaded from a TOML file and/or set from command-line flags.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ScanGates {
+    /// Fail if the score (0–100) is below this
+    pub min_score: Option<f64>,
+    /// Worst acceptable grade, e.g. "B" fails on C, D or F
+    pub min_grade: Option<String>,
+    /// Fail if any check carrying one of these tags (or with this id) fails
+    #[serde(default)]
+    pub fail_on_tags: Vec<String>,
+    /// Fail if any check with one of these severities fails
+    #[serde(default)]
+    pub fail_on_severities: Vec<String>,
+}
+
+impl ScanGates {
+    /// Load gates from a TOML file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min_score.is_none()
+            && self.min_grade.is_none()
+            && self.fail_on_tags.is_empty()
+            && self.fail_on_severities.is_empty()
+    }
+
+    /// Check a finished scan against every configured gate.
+    /// Returns all violations; an empty list means the scan passes.
+    pub fn evaluate(
+        &self,
+        summary: &ScanSummary,
+        results: &[CheckResult],
+        checks: &[CheckMeta],
+    ) -> Result<Vec<GateViolation>, String> {
+        let mut violations = Vec::new();
+
+        if let Some(min_score) = self.min_score {
+            if summary.score < min_score {
+                violations.push(GateViolation {
+                    gate: "min_score".into(),
+                    message: format!("Score {:.0}% is below {:.0}%", summary.score, min_score),
+                });
+            }
+        }
+
+        if let Some(ref min_grade) = self.min_grade {
+            let allowed =
+                grade_rank(min_grade).ok_or_else(|| format!("Unknown grade: {}", min_grade))?;
+            let actual = grade_rank(&summary.grade).unwrap_or(u8::MAX);
+            if actual > allowed {
+                violations.push(GateViolation {
+                    gate: "min_grade".into(),
+                    message: format!("Grade {} is worse than {}", summary.grade, min_grade),
+                });
+            }
+        }
+
+        if !self.fail_on_tags.is_empty() {
+            // A gated check that timed out or errored counts too: CI can't
+            // assume it passed
+            for result in results.iter().filter(|r| scoring::is_failing(&r.status)) {
+                let tags = checks
+                    .iter()
+                    .find(|c| c.id == result.check_id)
+                    .map(|c| c.tags.as_slice())
+                    .unwrap_or_default();
+                let matched = self
+                    .fail_on_tags
+                    .iter()
+                    .find(|t| **t == result.check_id || tags.contains(t));
+                if let Some(tag) = matched {
+                    violations.push(GateViolation {
+                        gate: "fail_on_tags".into(),
+                        message: format!(
+                            "{} ({}) is {}: {}",
+                            result.check_name, tag, result.status, result.detail
+                        ),
+                    });
+                }
+            }
+        }
+
+        // Same rule as tags: a gated check that didn't finish can't be
+        // assumed to pass
+        for result in results
+            .iter()
+            .filter(|r| scoring::is_failing(&r.status))
+            .filter(|r| self.fail_on_severities.contains(&r.severity))
+        {
+            violations.push(GateViolation {
+                gate: "fail_on_severities".into(),
+                message: format!(
+                    "{} ({}) is {}: {}",
+                    result.check_name, result.severity, result.status, result.detail
+                ),
+            });
+        }
+
+        Ok(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(score: f64, grade: &str) -> ScanSummary {
+        ScanSummary {
+            passed: 0,
+            failed: 0,
+            fixed: 0,
+            skipped: 0,
+            waived: 0,
+            errors: 0,
+            total: 0,
+            score,
+            grade: grade.to_string(),
+            phases: Vec::new(),
+            critical_failures: Vec::new(),
+        }
+    }
+
+    fn result(id: &str, status: &str, severity: &str) -> CheckResult {
+        serde_json::from_value(serde_json::json!({
+            "check_id": id,
+            "check_name": id,
+            "phase": "hardening",
+            "status": status,
+            "detail": "detail",
+            "severity": severity,
+        }))
+        .unwrap()
+    }
+
+    fn check(id: &str, tags: &[&str]) -> CheckMeta {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "phase": "hardening",
+            "platform": "all",
+            "description": "",
+            "tags": tags,
+        }))
+        .unwrap()
+    }
+
+    fn gate_names(violations: &[GateViolation]) -> Vec<&str> {
+        violations.iter().map(|v| v.gate.as_str()).collect()
+    }
+
+    #[test]
+    fn min_score_is_inclusive() {
+        let gates = ScanGates {
+            min_score: Some(80.0),
+            ..Default::default()
+        };
+        assert!(gates
+            .evaluate(&summary(80.0, "B"), &[], &[])
+            .unwrap()
+            .is_empty());
+        let violations = gates.evaluate(&summary(75.0, "C"), &[], &[]).unwrap();
+        assert_eq!(violations[0].message, "Score 75% is below 80%");
+    }
+
+    #[test]
+    fn min_grade_allows_that_grade_and_better() {
+        let gates = ScanGates {
+            min_grade: Some("B".to_string()),
+            ..Default::default()
+        };
+        for grade in ["A", "B"] {
+            assert!(gates
+                .evaluate(&summary(90.0, grade), &[], &[])
+                .unwrap()
+                .is_empty());
+        }
+        for grade in ["C", "F"] {
+            let violations = gates.evaluate(&summary(50.0, grade), &[], &[]).unwrap();
+            assert_eq!(gate_names(&violations), ["min_grade"]);
+        }
+    }
+
+    #[test]
+    fn unknown_min_grade_is_an_error() {
+        let gates = ScanGates {
+            min_grade: Some("E".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            gates.evaluate(&summary(90.0, "A"), &[], &[]).unwrap_err(),
+            "Unknown grade: E"
+        );
+    }
+
+    #[test]
+    fn fail_on_tags_matches_tags_or_ids_and_counts_unfinished_checks() {
+        let gates = ScanGates {
+            fail_on_tags: vec!["network".to_string(), "node".to_string()],
+            ..Default::default()
+        };
+        let checks = [
+            check("ssh", &["network"]),
+            check("firewall", &["network"]),
+            check("node", &[]),
+            check("disk", &["storage"]),
+        ];
+        let results = [
+            result("ssh", "TIMEOUT", "high"),
+            result("firewall", "PASS", "high"),
+            result("node", "ERROR", "low"),
+            result("disk", "FAIL", "high"),
+        ];
+        let violations = gates
+            .evaluate(&summary(50.0, "F"), &results, &checks)
+            .unwrap();
+        let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "ssh (network) is TIMEOUT: detail",
+                "node (node) is ERROR: detail"
+            ]
+        );
+    }
+
+    #[test]
+    fn fail_on_severities_counts_unfinished_but_not_waived() {
+        let gates = ScanGates {
+            fail_on_severities: vec!["critical".to_string()],
+            ..Default::default()
+        };
+        let results = [
+            result("a", "FAIL", "critical"),
+            result("b", "ERROR", "critical"),
+            result("c", "TIMEOUT", "critical"),
+            result("d", "WAIVED", "critical"),
+            result("e", "SKIPPED", "critical"),
+            result("f", "FAIL", "high"),
+        ];
+        let violations = gates.evaluate(&summary(50.0, "F"), &results, &[]).unwrap();
+        assert_eq!(gate_names(&violations), ["fail_on_severities"; 3]);
+        assert!(violations[2].message.starts_with("c (critical) is TIMEOUT"));
+    }
+
+    #[test]
+    fn every_violation_is_reported() {
+        let gates = ScanGates {
+            min_score: Some(90.0),
+            min_grade: Some("A".to_string()),
+            fail_on_severities: vec!["high".to_string()],
+            ..Default::default()
+        };
+        assert!(!gates.is_empty());
+        assert!(ScanGates::default().is_empty());
+        let violations = gates
+            .evaluate(&summary(60.0, "D"), &[result("a", "FAIL", "high")], &[])
+            .unwrap();
+        assert_eq!(
+            gate_names(&violations),
+            ["min_score", "min_grade", "fail_on_severities"]
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::scoring;
+use crate::types::{
+    CheckMeta, CheckResult, GradingPolicy, PhaseInfo, PlatformInfo, ScanListing, ScanRecord,
+};
+
+/// Resolve the history directory under the app data dir.
+/// Each completed scan is stored there as `<id>.json`, next to its
+/// listing as `<id>.listing.json`.
+#[cfg(feature = "desktop")]
+pub fn history_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    use tauri::Manager;
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Could not resolve app data dir: {}", e))?;
+    Ok(data_dir.join("history"))
+}
+
+/// Current time since the Unix epoch.
+pub fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Current time as Unix seconds.
+pub fn now_secs() -> u64 {
+    now().as_secs()
+}
+
+/// Id for a scan started at `started` (see `now`): its start time to the
+/// nanosecond, so ids sort by when scans started, then the process id, so
+/// scans started at once by the app and clawkeeper-scan don't collide.
+fn scan_id(started: Duration) -> String {
+    format!(
+        "scan-{}-{:09}-{}",
+        started.as_secs(),
+        started.subsec_nanos(),
+        std::process::id()
+    )
+}
+
+/// Assemble a record for a scan that just finished. `catalog` is every
+/// check for the host, before selection.
+pub fn build_record(
+    started: Duration,
+    platform: PlatformInfo,
+    checks: Vec<CheckResult>,
+    catalog: Vec<CheckMeta>,
+    phases: Vec<PhaseInfo>,
+    policy: &GradingPolicy,
+) -> ScanRecord {
+    ScanRecord {
+        id: scan_id(started),
+        started_at: started.as_secs(),
+        finished_at: now_secs(),
+        hostname: hostname(),
+        platform,
+        summary: scoring::summarize(&checks, policy),
+        checks,
+        catalog,
+        phases,
+    }
+}
+
+fn hostname() -> String {
+    std::process::Command::new("hostname")
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Scan IDs become file names, so only allow a safe character set.
+fn record_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid scan id: {}", id));
+    }
+    Ok(dir.join(format!("{}.json", id)))
+}
+
+/// Where the listing for the record at `record_path` is kept.
+fn listing_path(record_path: &Path) -> PathBuf {
+    record_path.with_extension("listing.json")
+}
+
+fn to_listing(record: &ScanRecord) -> ScanListing {
+    ScanListing {
+        id: record.id.clone(),
+        started_at: record.started_at,
+        finished_at: record.finished_at,
+        hostname: record.hostname.clone(),
+        summary: record.summary.clone(),
+    }
+}
+
+/// Write (or overwrite) a scan record and its listing.
+pub fn save_scan(dir: &Path, record: &ScanRecord) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let path = record_path(dir, &record.id)?;
+    let json = serde_json::to_string_pretty(record)
+        .map_err(|e| format!("Failed to serialize scan {}: {}", record.id, e))?;
+    write_private(&path, json)?;
+    save_listing(&path, &to_listing(record))
+}
+
+/// Save the listing `list_scans` reads instead of the whole record.
+fn save_listing(record_path: &Path, listing: &ScanListing) -> Result<(), String> {
+    let json = serde_json::to_string(listing)
+        .map_err(|e| format!("Failed to serialize scan {}: {}", listing.id, e))?;
+    write_private(&listing_path(record_path), json)
+}
+
+fn write_private(path: &Path, content: String) -> Result<(), String> {
+    std::fs::write(path, content)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    // Scan results describe the host's weak spots; keep them private
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(())
+}
+
+/// List stored scans, newest first, from their listings. A record saved
+/// without one is read in full once and given one. Unreadable files are
+/// skipped.
+pub fn list_scans(dir: &Path) -> Result<Vec<ScanListing>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    let mut listings = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // Records only: ids have no dots, listings end in .listing.json
+        let is_record = path.extension().and_then(|e| e.to_str()) == Some("json")
+            && path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|stem| !stem.contains('.'));
+        if !is_record {
+            continue;
+        }
+        let listing = match read_listing(&listing_path(&path)) {
+            Ok(listing) => listing,
+            Err(_) => {
+                let Ok(record) = read_record(&path) else {
+                    continue;
+                };
+                let listing = to_listing(&record);
+                // Only saves reading the record next time; listing it now
+                // doesn't depend on it
+                let _ = save_listing(&path, &listing);
+                listing
+            }
+        };
+        listings.push(listing);
+    }
+
+    listings.sort_by_key(|l| std::cmp::Reverse(l.started_at));
+    Ok(listings)
+}
+
+/// Load one stored scan by id.
+pub fn load_scan(dir: &Path, id: &str) -> Result<ScanRecord, String> {
+    let path = record_path(dir, id)?;
+    if !path.exists() {
+        return Err(format!("Scan not found: {}", id));
+    }
+    read_record(&path)
+}
+
+/// Delete one stored scan by id.
+pub fn delete_scan(dir: &Path, id: &str) -> Result<(), String> {
+    let path = record_path(dir, id)?;
+    if !path.exists() {
+        return Err(format!("Scan not found: {}", id));
+    }
+    std::fs::remove_file(&path)
+        .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+    let listing = listing_path(&path);
+    if listing.exists() {
+        std::fs::remove_file(&listing)
+            .map_err(|e| format!("Failed to delete {}: {}", listing.display(), e))?;
+    }
+    Ok(())
+}
+
+fn read_listing(path: &Path) -> Result<ScanListing, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn read_record(path: &Path) -> Result<ScanRecord, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
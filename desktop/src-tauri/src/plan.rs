@@ -0,0 +1,253 @@
+//! The order a scan runs in: phases in order and, within a phase, every
+//! check after the checks it lists in `after`. Waves are barriers: a wave
+//! waits for all of the previous one, not only the checks it depends on.
+
+use std::collections::HashMap;
+
+use crate::types::{CheckMeta, PhaseInfo};
+
+/// One phase of a scan. The checks in a wave run concurrently; a wave
+/// starts once the one before it has finished.
+#[derive(Debug, Clone)]
+pub struct PhasePlan {
+    pub phase: PhaseInfo,
+    pub waves: Vec<Vec<CheckMeta>>,
+}
+
+/// Plan a scan of `checks`, which must be in catalog order. `after` may
+/// name checks that aren't being run (e.g. ones for another platform);
+/// those are ignored. Fails on a cycle, or on a check that waits for one
+/// in a later phase.
+pub fn plan_scan(checks: &[CheckMeta], phases: &[PhaseInfo]) -> Result<Vec<PhasePlan>, String> {
+    let phase_of: HashMap<&str, usize> = checks
+        .iter()
+        .filter_map(|c| {
+            let index = phases.iter().position(|p| p.id == c.phase)?;
+            Some((c.id.as_str(), index))
+        })
+        .collect();
+
+    for check in checks {
+        for dep in &check.after {
+            if let (Some(own), Some(theirs)) =
+                (phase_of.get(check.id.as_str()), phase_of.get(dep.as_str()))
+            {
+                if theirs > own {
+                    return Err(format!(
+                        "{} runs after {}, which is in a later phase",
+                        check.id, dep
+                    ));
+                }
+            }
+        }
+    }
+
+    phases
+        .iter()
+        .map(|phase| {
+            let members: Vec<&CheckMeta> = checks.iter().filter(|c| c.phase == phase.id).collect();
+            if let Some(cycle) = find_cycle(&members) {
+                return Err(format!("Dependency cycle: {}", cycle.join(" → ")));
+            }
+
+            let mut waves: Vec<Vec<CheckMeta>> = Vec::new();
+            let mut wave_of: HashMap<&str, usize> = HashMap::new();
+            for check in &members {
+                let wave = wave_index(check, &members, &mut wave_of);
+                if waves.len() <= wave {
+                    waves.resize(wave + 1, Vec::new());
+                }
+                waves[wave].push((*check).clone());
+            }
+            Ok(PhasePlan {
+                phase: phase.clone(),
+                waves,
+            })
+        })
+        .collect()
+}
+
+/// One more than the latest wave among the check's dependencies in
+/// `members`. Assumes there is no cycle.
+fn wave_index<'a>(
+    check: &'a CheckMeta,
+    members: &[&'a CheckMeta],
+    wave_of: &mut HashMap<&'a str, usize>,
+) -> usize {
+    if let Some(&wave) = wave_of.get(check.id.as_str()) {
+        return wave;
+    }
+    let wave = check
+        .after
+        .iter()
+        .filter_map(|dep| members.iter().find(|c| &c.id == dep))
+        .map(|dep| wave_index(dep, members, wave_of) + 1)
+        .max()
+        .unwrap_or(0);
+    wave_of.insert(&check.id, wave);
+    wave
+}
+
+/// A chain of `after` edges among `checks` that leads back to where it
+/// started, if there is one, e.g. ["a", "b", "a"].
+pub(crate) fn find_cycle(checks: &[&CheckMeta]) -> Option<Vec<String>> {
+    fn visit<'a>(
+        check: &'a CheckMeta,
+        checks: &[&'a CheckMeta],
+        path: &mut Vec<&'a str>,
+        done: &mut Vec<&'a str>,
+    ) -> Option<Vec<String>> {
+        if let Some(start) = path.iter().position(|id| *id == check.id) {
+            let mut cycle: Vec<String> = path[start..].iter().map(|id| id.to_string()).collect();
+            cycle.push(check.id.clone());
+            return Some(cycle);
+        }
+        if done.contains(&check.id.as_str()) {
+            return None;
+        }
+        path.push(&check.id);
+        for dep in &check.after {
+            if let Some(next) = checks.iter().find(|c| &c.id == dep) {
+                if let Some(cycle) = visit(next, checks, path, done) {
+                    return Some(cycle);
+                }
+            }
+        }
+        path.pop();
+        done.push(&check.id);
+        None
+    }
+
+    let mut done = Vec::new();
+    checks
+        .iter()
+        .find_map(|check| visit(check, checks, &mut Vec::new(), &mut done))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(id: &str, phase: &str, after: &[&str]) -> CheckMeta {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "phase": phase,
+            "platform": "all",
+            "description": "",
+            "after": after,
+        }))
+        .unwrap()
+    }
+
+    fn phase(id: &str, order: u32) -> PhaseInfo {
+        PhaseInfo {
+            id: id.to_string(),
+            label: id.to_string(),
+            order,
+            description: String::new(),
+        }
+    }
+
+    /// Each phase's waves as check ids.
+    fn wave_ids(plan: &[PhasePlan]) -> Vec<Vec<Vec<&str>>> {
+        plan.iter()
+            .map(|p| {
+                p.waves
+                    .iter()
+                    .map(|wave| wave.iter().map(|c| c.id.as_str()).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn independent_checks_share_one_wave() {
+        let checks = [check("a", "p", &[]), check("b", "p", &[])];
+        let plan = plan_scan(&checks, &[phase("p", 1)]).unwrap();
+        assert_eq!(wave_ids(&plan), [[["a", "b"]]]);
+    }
+
+    #[test]
+    fn after_pushes_a_check_into_a_later_wave() {
+        let checks = [
+            check("c", "p", &["b"]),
+            check("b", "p", &["a"]),
+            check("a", "p", &[]),
+            check("d", "p", &["a"]),
+        ];
+        let plan = plan_scan(&checks, &[phase("p", 1)]).unwrap();
+        assert_eq!(
+            wave_ids(&plan),
+            [vec![vec!["a"], vec!["b", "d"], vec!["c"]]]
+        );
+    }
+
+    #[test]
+    fn wave_follows_the_latest_dependency() {
+        let checks = [
+            check("a", "p", &[]),
+            check("b", "p", &["a"]),
+            check("c", "p", &["a", "b"]),
+        ];
+        let plan = plan_scan(&checks, &[phase("p", 1)]).unwrap();
+        assert_eq!(wave_ids(&plan), [vec![vec!["a"], vec!["b"], vec!["c"]]]);
+    }
+
+    #[test]
+    fn unknown_dependencies_are_ignored() {
+        let checks = [check("a", "p", &["not_running"])];
+        let plan = plan_scan(&checks, &[phase("p", 1)]).unwrap();
+        assert_eq!(wave_ids(&plan), [[["a"]]]);
+    }
+
+    #[test]
+    fn earlier_phase_dependency_needs_no_wave() {
+        let checks = [check("a", "one", &[]), check("b", "two", &["a"])];
+        let plan = plan_scan(&checks, &[phase("one", 1), phase("two", 2)]).unwrap();
+        assert_eq!(wave_ids(&plan), [[["a"]], [["b"]]]);
+    }
+
+    #[test]
+    fn later_phase_dependency_is_an_error() {
+        let checks = [check("a", "one", &["b"]), check("b", "two", &[])];
+        let err = plan_scan(&checks, &[phase("one", 1), phase("two", 2)]).unwrap_err();
+        assert_eq!(err, "a runs after b, which is in a later phase");
+    }
+
+    #[test]
+    fn cycle_is_an_error() {
+        let checks = [
+            check("a", "p", &["c"]),
+            check("b", "p", &["a"]),
+            check("c", "p", &["b"]),
+        ];
+        let err = plan_scan(&checks, &[phase("p", 1)]).unwrap_err();
+        assert_eq!(err, "Dependency cycle: a → c → b → a");
+    }
+
+    #[test]
+    fn self_dependency_is_a_cycle() {
+        let checks = [check("a", "p", &["a"])];
+        let members: Vec<&CheckMeta> = checks.iter().collect();
+        assert_eq!(find_cycle(&members).unwrap(), ["a", "a"]);
+    }
+
+    #[test]
+    fn shared_dependency_is_not_a_cycle() {
+        let checks = [
+            check("a", "p", &[]),
+            check("b", "p", &["a"]),
+            check("c", "p", &["a", "b"]),
+        ];
+        let members: Vec<&CheckMeta> = checks.iter().collect();
+        assert_eq!(find_cycle(&members), None);
+    }
+
+    #[test]
+    fn phase_without_checks_has_no_waves() {
+        let checks = [check("a", "one", &[])];
+        let plan = plan_scan(&checks, &[phase("one", 1), phase("two", 2)]).unwrap();
+        assert!(plan[1].waves.is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use crate::types::PlatformInfo;
+
+/// Detect the host platform at runtime.
+/// Mirrors `detect_platform` in lib/orchestrator.sh.
+pub fn detect_platform() -> PlatformInfo {
+    let os = match std::env::consts::OS {
+        "macos" => "macos",
+        "linux" => "linux",
+        other => other,
+    }
+    .to_string();
+
+    let arch = match std::env::consts::ARCH {
+        "aarch64" => "arm64",
+        "x86_64" => "x86_64",
+        other => other,
+    }
+    .to_string();
+
+    let mut info = PlatformInfo {
+        os,
+        arch,
+        distro: None,
+        distro_family: None,
+        distro_version: None,
+        distro_name: None,
+    };
+
+    if info.os == "linux" {
+        detect_linux_distro(&mut info);
+    }
+
+    info
+}
+
+/// Fill in distro fields from /etc/os-release, falling back to the
+/// legacy debian_version / redhat-release files.
+fn detect_linux_distro(info: &mut PlatformInfo) {
+    if let Ok(content) = std::fs::read_to_string("/etc/os-release") {
+        let fields = parse_os_release(&content);
+        let get = |key: &str| {
+            fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.clone())
+        };
+
+        let id = get("ID").unwrap_or_else(|| "unknown".to_string());
+        let id_like = get("ID_LIKE").unwrap_or_default();
+        info.distro_family = Some(distro_family(&id, &id_like));
+        info.distro_version = get("VERSION_ID");
+        info.distro_name = get("PRETTY_NAME").or_else(|| Some(id.clone()));
+        info.distro = Some(id);
+    } else if Path::new("/etc/debian_version").exists() {
+        let version = std::fs::read_to_string("/etc/debian_version")
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        info.distro = Some("debian".to_string());
+        info.distro_family = Some("debian".to_string());
+        info.distro_name = Some(format!("Debian {}", version));
+        info.distro_version = Some(version);
+    } else if Path::new("/etc/redhat-release").exists() {
+        let release = std::fs::read_to_string("/etc/redhat-release")
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default();
+        let version: String = release
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|s| !s.is_empty())
+            .unwrap_or("unknown")
+            .to_string();
+        info.distro = Some("rhel".to_string());
+        info.distro_family = Some("rhel".to_string());
+        info.distro_version = Some(version);
+        info.distro_name = Some(release);
+    } else {
+        info.distro = Some("unknown".to_string());
+        info.distro_family = Some("unknown".to_string());
+        info.distro_version = Some("unknown".to_string());
+        info.distro_name = Some("Linux (unknown distro)".to_string());
+    }
+}
+
+/// Parse KEY=value lines from os-release, stripping optional quotes.
+fn parse_os_release(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim().trim_matches('"').trim_matches('\'');
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Map a distro ID (and its ID_LIKE list) onto a family name.
+fn distro_family(id: &str, id_like: &str) -> String {
+    let candidates = std::iter::once(id).chain(id_like.split_whitespace());
+    for candidate in candidates {
+        match candidate {
+            "debian" | "ubuntu" => return "debian".to_string(),
+            "rhel" | "fedora" | "centos" => return "rhel".to_string(),
+            "arch" => return "arch".to_string(),
+            "suse" | "opensuse" => return "suse".to_string(),
+            "alpine" => return "alpine".to_string(),
+            _ => {}
+        }
+    }
+    id.to_string()
+}
+
+/// Whether a check's `platform` field applies to this host.
+/// Accepts "all", the OS name, the distro ID, or the distro family.
+pub fn matches_platform(check_platform: &str, host: &PlatformInfo) -> bool {
+    check_platform == "all"
+        || check_platform == host.os
+        || host.distro.as_deref() == Some(check_platform)
+        || host.distro_family.as_deref() == Some(check_platform)
+}
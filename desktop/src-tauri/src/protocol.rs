@@ -0,0 +1,391 @@
+//! The check output protocol: what check.sh and remediate.sh print on
+//! stdout. Version 1 opens with a `{"protocol":1}` header, then one JSON
+//! object per line tagged by `type`. Scripts without a header are read
+//! as the older untagged format.
+
+use serde::Deserialize;
+
+use crate::types::{CheckOutputLine, ProtocolViolation};
+
+/// The protocol version emitted by lib/helpers.sh.
+pub const PROTOCOL_VERSION: u64 = 1;
+
+/// One line of check output.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ProtocolLine {
+    Status {
+        /// PASS, FAIL, FIXED or SKIPPED
+        status: String,
+        #[serde(default)]
+        check_name: Option<String>,
+        #[serde(default)]
+        detail: String,
+    },
+    Info {
+        message: String,
+    },
+    Warn {
+        message: String,
+    },
+    /// Offer a remediation; reported as FAIL unless it is accepted
+    Prompt {
+        message: String,
+        remediation_id: String,
+        #[serde(default)]
+        fail_detail: String,
+        #[serde(default)]
+        skip_detail: String,
+    },
+    /// Supporting data for the next status line, e.g. the setting that
+    /// made it fail
+    Evidence {
+        key: String,
+        #[serde(default)]
+        observed: String,
+        #[serde(default)]
+        expected: Option<String>,
+        /// The file or command the value came from
+        #[serde(default)]
+        source: Option<String>,
+    },
+    /// A measured value, e.g. days since the last update
+    Metric {
+        name: String,
+        value: f64,
+        #[serde(default)]
+        unit: Option<String>,
+    },
+}
+
+const STATUSES: [&str; 4] = ["PASS", "FAIL", "FIXED", "SKIPPED"];
+
+/// Parses one script's stdout a line at a time. Every line is parsed as
+/// leniently as possible; anything that breaks the protocol is also
+/// reported as a violation, which the caller keeps in strict mode.
+#[derive(Debug, Default)]
+pub struct OutputParser {
+    /// Version from the header, if one has been seen
+    version: Option<u64>,
+    line: usize,
+    seen_output: bool,
+    seen_status: bool,
+}
+
+impl OutputParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the next line of output.
+    pub fn parse(&mut self, raw: &str) -> (Option<ProtocolLine>, Option<ProtocolViolation>) {
+        self.line += 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return (None, None);
+        }
+
+        let value: serde_json::Value = match serde_json::from_str(trimmed) {
+            Ok(serde_json::Value::Object(map)) => serde_json::Value::Object(map),
+            Ok(_) => return (None, self.violation("expected a JSON object")),
+            Err(_) => {
+                return (
+                    None,
+                    self.violation("not JSON; stray output belongs on stderr"),
+                )
+            }
+        };
+
+        if let Some(version) = value.get("protocol") {
+            return (None, self.header(version));
+        }
+
+        let first_output = !self.seen_output;
+        self.seen_output = true;
+
+        let Some(version) = self.version else {
+            let violation = if first_output {
+                self.violation("missing {\"protocol\":1} header")
+            } else {
+                None
+            };
+            return (self.legacy(value), violation);
+        };
+        if version != PROTOCOL_VERSION {
+            // Already reported at the header; read the lines as best we can
+            let line = match serde_json::from_value(value.clone()) {
+                Ok(line) => Some(line),
+                Err(_) => self.legacy(value),
+            };
+            if matches!(
+                line,
+                Some(ProtocolLine::Status { .. } | ProtocolLine::Prompt { .. })
+            ) {
+                self.seen_status = true;
+            }
+            return (line, None);
+        }
+
+        match serde_json::from_value::<ProtocolLine>(value.clone()) {
+            Ok(line) => {
+                let violation = match line {
+                    ProtocolLine::Status { ref status, .. } => {
+                        self.seen_status = true;
+                        if STATUSES.contains(&status.as_str()) {
+                            None
+                        } else {
+                            self.violation(&format!("unknown status \"{}\"", status))
+                        }
+                    }
+                    ProtocolLine::Prompt { .. } => {
+                        self.seen_status = true;
+                        None
+                    }
+                    _ => None,
+                };
+                (Some(line), violation)
+            }
+            // Still a violation, but keep what the line says if the older
+            // format can make sense of it, e.g. a status line without a type
+            Err(e) => (self.legacy(value), self.violation(&e.to_string())),
+        }
+    }
+
+    /// Call once stdout has closed: a check must report at least one
+    /// status (or prompt).
+    pub fn finish(&self) -> Option<ProtocolViolation> {
+        if self.seen_status {
+            return None;
+        }
+        Some(ProtocolViolation {
+            line: self.line,
+            message: "no status line".to_string(),
+        })
+    }
+
+    fn header(&mut self, version: &serde_json::Value) -> Option<ProtocolViolation> {
+        let Some(version) = version.as_u64() else {
+            return self.violation("protocol version must be a number");
+        };
+        // A check that emits from a subshell repeats the header; harmless
+        // as long as it agrees
+        match self.version {
+            Some(previous) if previous != version => {
+                return self.violation(&format!(
+                    "protocol version changed from {} to {}",
+                    previous, version
+                ));
+            }
+            Some(_) => return None,
+            None => self.version = Some(version),
+        }
+        if version != PROTOCOL_VERSION {
+            return self.violation(&format!(
+                "unsupported protocol version {} (expected {})",
+                version, PROTOCOL_VERSION
+            ));
+        }
+        if self.seen_output {
+            return self.violation("header must come before any other output");
+        }
+        None
+    }
+
+    /// Read a line in the untagged format used before the header existed.
+    fn legacy(&mut self, value: serde_json::Value) -> Option<ProtocolLine> {
+        let parsed: CheckOutputLine = serde_json::from_value(value).ok()?;
+        if parsed.action.as_deref() == Some("prompt") {
+            self.seen_status = true;
+            return Some(ProtocolLine::Prompt {
+                message: parsed.message.unwrap_or_default(),
+                remediation_id: parsed.remediation_id.unwrap_or_default(),
+                fail_detail: parsed.fail_detail.unwrap_or_default(),
+                skip_detail: parsed.skip_detail.unwrap_or_default(),
+            });
+        }
+        match parsed.line_type.as_deref() {
+            Some("info") => {
+                return Some(ProtocolLine::Info {
+                    message: parsed.message.unwrap_or_default(),
+                })
+            }
+            Some("warn") => {
+                return Some(ProtocolLine::Warn {
+                    message: parsed.message.unwrap_or_default(),
+                })
+            }
+            _ => {}
+        }
+        let status = parsed.status?;
+        self.seen_status = true;
+        Some(ProtocolLine::Status {
+            status,
+            check_name: parsed.check_name,
+            detail: parsed.detail.unwrap_or_default(),
+        })
+    }
+
+    fn violation(&self, message: &str) -> Option<ProtocolViolation> {
+        Some(ProtocolViolation {
+            line: self.line,
+            message: message.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse every line, returning what was read and the violation
+    /// messages, then `finish`'s.
+    fn parse_all(lines: &[&str]) -> (Vec<ProtocolLine>, Vec<String>) {
+        let mut parser = OutputParser::new();
+        let mut parsed = Vec::new();
+        let mut violations = Vec::new();
+        for line in lines {
+            let (line, violation) = parser.parse(line);
+            parsed.extend(line);
+            violations.extend(violation.map(|v| v.message));
+        }
+        violations.extend(parser.finish().map(|v| v.message));
+        (parsed, violations)
+    }
+
+    fn statuses(lines: &[ProtocolLine]) -> Vec<&str> {
+        lines
+            .iter()
+            .filter_map(|line| match line {
+                ProtocolLine::Status { status, .. } => Some(status.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    const HEADER: &str = r#"{"protocol":1}"#;
+
+    #[test]
+    fn clean_v1_output() {
+        let (lines, violations) = parse_all(&[
+            HEADER,
+            r#"{"type":"info","message":"hi"}"#,
+            r#"{"type":"evidence","key":"k","observed":"v"}"#,
+            r#"{"type":"status","status":"PASS","check_name":"A","detail":"ok"}"#,
+        ]);
+        assert!(violations.is_empty(), "{:?}", violations);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(statuses(&lines), ["PASS"]);
+    }
+
+    #[test]
+    fn prompt_counts_as_a_status() {
+        let (_, violations) = parse_all(&[
+            HEADER,
+            r#"{"type":"prompt","message":"Fix?","remediation_id":"fix"}"#,
+        ]);
+        assert!(violations.is_empty(), "{:?}", violations);
+    }
+
+    #[test]
+    fn no_status_line() {
+        let (_, violations) = parse_all(&[HEADER, r#"{"type":"info","message":"hi"}"#]);
+        assert_eq!(violations, ["no status line"]);
+    }
+
+    #[test]
+    fn stray_output_is_a_violation_and_skipped() {
+        let (lines, violations) = parse_all(&[
+            HEADER,
+            "Checking things...",
+            "[1, 2]",
+            "",
+            r#"{"type":"status","status":"PASS"}"#,
+        ]);
+        assert_eq!(statuses(&lines), ["PASS"]);
+        assert_eq!(
+            violations,
+            [
+                "not JSON; stray output belongs on stderr",
+                "expected a JSON object"
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_status_is_kept_but_reported() {
+        let (lines, violations) = parse_all(&[HEADER, r#"{"type":"status","status":"MAYBE"}"#]);
+        assert_eq!(statuses(&lines), ["MAYBE"]);
+        assert_eq!(violations, [r#"unknown status "MAYBE""#]);
+    }
+
+    #[test]
+    fn untyped_v1_line_falls_back_to_legacy() {
+        let (lines, violations) = parse_all(&[HEADER, r#"{"status":"FAIL","detail":"x"}"#]);
+        assert_eq!(statuses(&lines), ["FAIL"]);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].contains("type"), "{:?}", violations);
+    }
+
+    #[test]
+    fn legacy_output_without_header() {
+        let (lines, violations) = parse_all(&[
+            r#"{"type":"info","message":"hi"}"#,
+            r#"{"action":"prompt","message":"Fix?","remediation_id":"fix"}"#,
+            r#"{"status":"PASS","check_name":"A","detail":"ok"}"#,
+        ]);
+        assert_eq!(lines.len(), 3);
+        assert!(
+            matches!(lines[1], ProtocolLine::Prompt { ref remediation_id, .. } if remediation_id == "fix")
+        );
+        assert_eq!(statuses(&lines), ["PASS"]);
+        // Reported once, not per line
+        assert_eq!(violations, [r#"missing {"protocol":1} header"#]);
+    }
+
+    #[test]
+    fn header_after_output() {
+        let (_, violations) = parse_all(&[
+            r#"{"status":"PASS"}"#,
+            HEADER,
+            r#"{"type":"status","status":"PASS"}"#,
+        ]);
+        assert!(violations.contains(&"header must come before any other output".to_string()));
+    }
+
+    #[test]
+    fn repeated_header_must_agree() {
+        let (_, violations) = parse_all(&[HEADER, HEADER, r#"{"type":"status","status":"PASS"}"#]);
+        assert!(violations.is_empty(), "{:?}", violations);
+
+        let (_, violations) = parse_all(&[
+            HEADER,
+            r#"{"protocol":2}"#,
+            r#"{"type":"status","status":"PASS"}"#,
+        ]);
+        assert_eq!(violations, ["protocol version changed from 1 to 2"]);
+    }
+
+    #[test]
+    fn unsupported_version_is_read_as_best_we_can() {
+        let (lines, violations) = parse_all(&[
+            r#"{"protocol":2}"#,
+            r#"{"type":"status","status":"PASS"}"#,
+            r#"{"status":"FAIL"}"#,
+        ]);
+        assert_eq!(statuses(&lines), ["PASS", "FAIL"]);
+        assert_eq!(violations, ["unsupported protocol version 2 (expected 1)"]);
+
+        let (_, violations) =
+            parse_all(&[r#"{"protocol":2}"#, r#"{"type":"status","status":"PASS"}"#]);
+        assert_eq!(violations, ["unsupported protocol version 2 (expected 1)"]);
+    }
+
+    #[test]
+    fn violations_carry_line_numbers() {
+        let mut parser = OutputParser::new();
+        parser.parse(HEADER);
+        parser.parse("");
+        let (_, violation) = parser.parse("oops");
+        assert_eq!(violation.unwrap().line, 3);
+    }
+}
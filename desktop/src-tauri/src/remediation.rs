@@ -0,0 +1,206 @@
+use std::path::Path;
+use std::process::ExitStatus;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::{Child, Command};
+
+use crate::check_runner::{self, EventSink, ScanOptions};
+use crate::protocol::{OutputParser, ProtocolLine};
+use crate::types::{CheckMeta, CheckResult, Finding, ScanEvent};
+use crate::waivers;
+
+/// Remediations install packages and reload services, so they get far
+/// longer than a check before being killed.
+const REMEDIATION_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Run a check's remediate.sh, then re-run the check to verify the fix.
+/// Mirrors the prompt-accepted branch of `run_check` in lib/scanner.sh.
+/// Returns the verified result: FIXED if the remediation reported success
+/// and the check now passes, otherwise whatever the check reports.
+pub async fn remediate(
+    resource_base: &Path,
+    check: &CheckMeta,
+    remediation_id: &str,
+    on_event: &dyn EventSink,
+) -> Result<CheckResult, String> {
+    let script_path = Path::new(&check.origin.dir).join("remediate.sh");
+
+    if !script_path.exists() {
+        return Err(format!("{} has no remediate.sh", check.id));
+    }
+
+    on_event.emit(ScanEvent::RemediationStarted {
+        check_id: check.id.clone(),
+        remediation_id: remediation_id.to_string(),
+    })?;
+
+    let helpers_path = resource_base.join("lib").join("helpers.sh");
+    let (status, detail) =
+        execute_remediation_script(&script_path, &helpers_path, check, remediation_id, on_event)
+            .await
+            .unwrap_or_else(|e| ("FAIL".to_string(), e));
+
+    on_event.emit(ScanEvent::RemediationCompleted {
+        check_id: check.id.clone(),
+        remediation_id: remediation_id.to_string(),
+        status: status.clone(),
+        detail,
+    })?;
+
+    // Verify by re-running the check, as the CLI would on its next pass
+    let options = ScanOptions {
+        waivers: waivers::load_default()?,
+        ..Default::default()
+    };
+    let mut verified = check_runner::rerun_check(resource_base, check, &options, on_event).await?;
+    if status == "FIXED" && verified.status == "PASS" {
+        verified.status = "FIXED".to_string();
+    }
+
+    Ok(verified)
+}
+
+/// Execute remediate.sh and stream its JSON lines, followed by a
+/// `Diagnostic` if it failed or wrote to stderr.
+/// Returns the (status, detail) its findings aggregate to, as a check's
+/// would; FIXED if that is PASS or FIXED, else FAIL.
+async fn execute_remediation_script(
+    script_path: &Path,
+    helpers_path: &Path,
+    check: &CheckMeta,
+    remediation_id: &str,
+    on_event: &dyn EventSink,
+) -> Result<(String, String), String> {
+    let mut command = Command::new("/bin/bash");
+    command
+        .arg(script_path)
+        .arg(remediation_id)
+        .env("CLAWKEEPER_HELPERS", helpers_path)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    command.process_group(0);
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to spawn remediation for {}: {}", check.id, e))?;
+
+    let stderr_reader = child
+        .stderr
+        .take()
+        .map(|pipe| tokio::spawn(check_runner::read_bounded(pipe)));
+
+    let result = tokio::time::timeout(
+        REMEDIATION_TIMEOUT,
+        read_remediation_output(&mut child, check, on_event),
+    )
+    .await;
+
+    let result = match result {
+        Ok(result) => result,
+        Err(_) => {
+            check_runner::terminate_process_group(&mut child).await;
+            Err(format!(
+                "Remediation timed out after {}s",
+                REMEDIATION_TIMEOUT.as_secs()
+            ))
+        }
+    };
+
+    let (stderr, stderr_truncated) = check_runner::collect_stderr(stderr_reader).await;
+    let (exit_code, signal, failed) = match &result {
+        Ok((_, _, exit)) => (
+            exit.code(),
+            check_runner::exit_signal(exit),
+            !exit.success(),
+        ),
+        Err(_) => (None, None, false),
+    };
+    if failed || !stderr.is_empty() {
+        on_event.emit(ScanEvent::Diagnostic {
+            check_id: check.id.clone(),
+            exit_code,
+            signal,
+            stderr,
+            stderr_truncated,
+        })?;
+    }
+
+    result.map(|(status, detail, _)| (status, detail))
+}
+
+async fn read_remediation_output(
+    child: &mut Child,
+    check: &CheckMeta,
+    on_event: &dyn EventSink,
+) -> Result<(String, String, ExitStatus), String> {
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| format!("No stdout for remediation {}", check.id))?;
+
+    let mut reader = BufReader::new(stdout).lines();
+    let mut parser = OutputParser::new();
+    let mut findings = Vec::new();
+
+    while let Some(line) = reader
+        .next_line()
+        .await
+        .map_err(|e| format!("IO error reading remediation {}: {}", check.id, e))?
+    {
+        match parser.parse(&line).0 {
+            Some(ProtocolLine::Info { message }) => on_event.emit(ScanEvent::Info {
+                check_id: check.id.clone(),
+                message,
+            })?,
+            Some(ProtocolLine::Warn { message }) => on_event.emit(ScanEvent::Warn {
+                check_id: check.id.clone(),
+                message,
+            })?,
+            Some(ProtocolLine::Status {
+                status,
+                check_name,
+                detail,
+            }) => {
+                let finding = Finding {
+                    name: check_name.unwrap_or_else(|| check.name.clone()),
+                    status,
+                    detail,
+                };
+                // Progress only: the re-run that follows starts the
+                // check's findings over
+                on_event.emit(ScanEvent::Finding {
+                    check_id: check.id.clone(),
+                    name: finding.name.clone(),
+                    status: finding.status.clone(),
+                    detail: finding.detail.clone(),
+                })?;
+                findings.push(finding);
+            }
+            _ => {}
+        }
+    }
+
+    let (final_status, final_detail) = if findings.is_empty() {
+        (
+            "FAIL".to_string(),
+            "Remediation reported no result".to_string(),
+        )
+    } else {
+        let (status, detail) = check_runner::aggregate_findings(&findings);
+        let status = match status.as_str() {
+            "PASS" | "FIXED" => "FIXED",
+            _ => "FAIL",
+        };
+        (status.to_string(), detail)
+    };
+
+    let exit = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait for remediation {}: {}", check.id, e))?;
+
+    Ok((final_status, final_detail, exit))
+}
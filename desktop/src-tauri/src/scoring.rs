@@ -0,0 +1,424 @@
+use std::path::Path;
+
+use crate::types::{CheckMeta, CheckResult, GradingPolicy, PhaseScore, ScanSummary};
+
+/// Default weight for each severity level.
+pub fn severity_weight(severity: &str) -> f64 {
+    match severity {
+        "critical" => 10.0,
+        "high" => 5.0,
+        "low" => 1.0,
+        _ => 3.0,
+    }
+}
+
+/// A check's weight: its explicit `weight`, else its severity's.
+pub fn check_weight(check: &CheckMeta) -> f64 {
+    check
+        .weight
+        .unwrap_or_else(|| severity_weight(&check.severity))
+}
+
+impl Default for GradingPolicy {
+    /// Matches the shipped checks/grading.toml, for bases that lack one.
+    fn default() -> Self {
+        Self {
+            fixed: "pass".into(),
+            skipped: "ignore".into(),
+            timeout: "ignore".into(),
+            error: "ignore".into(),
+            critical_failure_cap: Some("C".into()),
+            thresholds: [("A", 95.0), ("B", 85.0), ("C", 70.0), ("D", 50.0)]
+                .into_iter()
+                .map(|(grade, min)| (grade.to_string(), min))
+                .collect(),
+        }
+    }
+}
+
+/// Load checks/grading.toml from the resource base, falling back to the
+/// default policy if there is none.
+pub fn load_policy(resource_base: &Path) -> Result<GradingPolicy, String> {
+    let path = resource_base.join("checks").join("grading.toml");
+    if !path.exists() {
+        return Ok(GradingPolicy::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let policy: GradingPolicy = toml::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    for (key, value) in [
+        ("fixed", &policy.fixed),
+        ("skipped", &policy.skipped),
+        ("timeout", &policy.timeout),
+        ("error", &policy.error),
+    ] {
+        if !matches!(value.as_str(), "pass" | "fail" | "ignore") {
+            return Err(format!(
+                "{}: {} must be \"pass\", \"fail\" or \"ignore\", not \"{}\"",
+                path.display(),
+                key,
+                value
+            ));
+        }
+    }
+    check_grades(&policy).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    Ok(policy)
+}
+
+/// Every grade the policy names must be known, and each threshold must be
+/// below the one for the grade above it, or scores would grade out of order.
+pub fn check_grades(policy: &GradingPolicy) -> Result<(), String> {
+    let grades = policy.thresholds.keys().chain(&policy.critical_failure_cap);
+    for grade in grades {
+        if grade_rank(grade).is_none() {
+            return Err(format!("unknown grade {}", grade));
+        }
+    }
+
+    let mut thresholds: Vec<(&String, f64)> = policy
+        .thresholds
+        .iter()
+        .map(|(grade, min)| (grade, *min))
+        .collect();
+    thresholds.sort_by_key(|(grade, _)| grade_rank(grade));
+    for pair in thresholds.windows(2) {
+        let ((better, better_min), (worse, worse_min)) = (pair[0], pair[1]);
+        if worse_min >= better_min {
+            return Err(format!(
+                "threshold for {} ({}) must be below the one for {} ({})",
+                worse, worse_min, better, better_min
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Letter grade for a score: the best grade whose threshold it meets.
+fn compute_grade(score: f64, policy: &GradingPolicy) -> String {
+    // Highest threshold first, whatever grades it is keyed by
+    let mut thresholds: Vec<(&String, &f64)> = policy.thresholds.iter().collect();
+    thresholds.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    thresholds
+        .into_iter()
+        .find(|(_, min)| score >= **min)
+        .map(|(grade, _)| grade.clone())
+        .unwrap_or_else(|| "F".to_string())
+}
+
+/// Whether a check result counts as failing for the critical-failure cap
+/// and CI gates: a check that timed out or errored can't be assumed to
+/// pass.
+pub fn is_failing(status: &str) -> bool {
+    matches!(status, "FAIL" | "TIMEOUT" | "ERROR")
+}
+
+/// Grades ordered best to worst.
+pub fn grade_rank(grade: &str) -> Option<u8> {
+    match grade {
+        "A" => Some(0),
+        "B" => Some(1),
+        "C" => Some(2),
+        "D" => Some(3),
+        "F" => Some(4),
+        _ => None,
+    }
+}
+
+/// Tally check results into a scan summary with a weighted score,
+/// graded by `policy`.
+pub fn summarize(results: &[CheckResult], policy: &GradingPolicy) -> ScanSummary {
+    let all: Vec<&CheckResult> = results.iter().collect();
+    let mut summary = tally(&all, policy);
+
+    let mut by_phase: Vec<(&str, Vec<&CheckResult>)> = Vec::new();
+    for result in results {
+        match by_phase
+            .iter_mut()
+            .find(|(phase, _)| *phase == result.phase)
+        {
+            Some((_, phase_results)) => phase_results.push(result),
+            None => by_phase.push((&result.phase, vec![result])),
+        }
+    }
+
+    summary.phases = by_phase
+        .into_iter()
+        .map(|(phase, phase_results)| {
+            let phase_summary = tally(&phase_results, policy);
+            PhaseScore {
+                phase: phase.to_string(),
+                passed: phase_summary.passed + phase_summary.fixed,
+                failed: phase_summary.failed,
+                skipped: phase_summary.skipped,
+                waived: phase_summary.waived,
+                errors: phase_summary.errors,
+                score: phase_summary.score,
+                grade: phase_summary.grade,
+            }
+        })
+        .collect();
+
+    summary
+}
+
+/// Counts, weighted score and (capped) grade for a set of results,
+/// without the phase breakdown.
+fn tally(results: &[&CheckResult], policy: &GradingPolicy) -> ScanSummary {
+    let mut passed: u32 = 0;
+    let mut failed: u32 = 0;
+    let mut fixed: u32 = 0;
+    let mut skipped: u32 = 0;
+    let mut waived: u32 = 0;
+    let mut errors: u32 = 0;
+    let mut passing_weight = 0.0;
+    let mut scored_weight = 0.0;
+    let mut critical_failures = Vec::new();
+
+    for result in results {
+        match result.status.as_str() {
+            "PASS" => passed += 1,
+            "FIXED" => fixed += 1,
+            "SKIPPED" => skipped += 1,
+            "WAIVED" => waived += 1,
+            "TIMEOUT" | "ERROR" => errors += 1,
+            _ => failed += 1,
+        }
+        let counts_as = match result.status.as_str() {
+            "PASS" => "pass",
+            "FIXED" => policy.fixed.as_str(),
+            "SKIPPED" => policy.skipped.as_str(),
+            "TIMEOUT" => policy.timeout.as_str(),
+            "ERROR" => policy.error.as_str(),
+            // An accepted risk neither helps nor hurts the score
+            "WAIVED" => "ignore",
+            _ => "fail",
+        };
+        match counts_as {
+            "pass" => {
+                passing_weight += result.weight;
+                scored_weight += result.weight;
+            }
+            "fail" => scored_weight += result.weight,
+            _ => {}
+        }
+        if is_failing(&result.status) && result.severity == "critical" {
+            critical_failures.push(result.check_id.clone());
+        }
+    }
+
+    let score = if scored_weight > 0.0 {
+        (passing_weight / scored_weight) * 100.0
+    } else {
+        100.0
+    };
+
+    let mut grade = compute_grade(score, policy);
+    if let Some(ref cap) = policy.critical_failure_cap {
+        if !critical_failures.is_empty() && grade_rank(&grade) < grade_rank(cap) {
+            grade = cap.clone();
+        }
+    }
+
+    ScanSummary {
+        passed,
+        failed,
+        fixed,
+        skipped,
+        waived,
+        errors,
+        total: passed + fixed + failed + skipped + waived + errors,
+        score,
+        grade,
+        phases: Vec::new(),
+        critical_failures,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(id: &str, phase: &str, status: &str, severity: &str) -> CheckResult {
+        serde_json::from_value(serde_json::json!({
+            "check_id": id,
+            "check_name": id,
+            "phase": phase,
+            "status": status,
+            "detail": "",
+            "severity": severity,
+            "weight": severity_weight(severity),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn grade_boundaries_are_inclusive() {
+        let policy = GradingPolicy::default();
+        assert_eq!(compute_grade(100.0, &policy), "A");
+        assert_eq!(compute_grade(95.0, &policy), "A");
+        assert_eq!(compute_grade(94.99, &policy), "B");
+        assert_eq!(compute_grade(85.0, &policy), "B");
+        assert_eq!(compute_grade(70.0, &policy), "C");
+        assert_eq!(compute_grade(50.0, &policy), "D");
+        assert_eq!(compute_grade(49.99, &policy), "F");
+        assert_eq!(compute_grade(0.0, &policy), "F");
+    }
+
+    #[test]
+    fn grades_by_threshold_value_not_key() {
+        let mut policy = GradingPolicy::default();
+        policy.thresholds.remove("A");
+        policy.thresholds.insert("B".into(), 90.0);
+        assert_eq!(compute_grade(92.0, &policy), "B");
+        assert_eq!(compute_grade(80.0, &policy), "C");
+    }
+
+    #[test]
+    fn rejects_thresholds_out_of_order() {
+        let mut policy = GradingPolicy::default();
+        assert!(check_grades(&policy).is_ok());
+
+        policy.thresholds.insert("B".into(), 97.0);
+        let err = check_grades(&policy).unwrap_err();
+        assert!(err.contains("threshold for B (97)"), "{}", err);
+
+        policy.thresholds.insert("B".into(), 95.0);
+        assert!(check_grades(&policy).is_err(), "equal thresholds");
+    }
+
+    #[test]
+    fn rejects_unknown_grades() {
+        let policy = GradingPolicy {
+            critical_failure_cap: Some("E".into()),
+            ..GradingPolicy::default()
+        };
+        assert_eq!(check_grades(&policy).unwrap_err(), "unknown grade E");
+    }
+
+    #[test]
+    fn weights_by_severity() {
+        // Critical (10) passes, low (1) fails
+        let results = [
+            result("a", "p", "PASS", "critical"),
+            result("b", "p", "FAIL", "low"),
+        ];
+        let summary = summarize(&results, &GradingPolicy::default());
+        assert!((summary.score - 100.0 * 10.0 / 11.0).abs() < 1e-9);
+        assert_eq!(summary.grade, "B");
+    }
+
+    #[test]
+    fn nothing_scored_is_100() {
+        let results = [result("a", "p", "SKIPPED", "high")];
+        let summary = summarize(&results, &GradingPolicy::default());
+        assert_eq!(summary.score, 100.0);
+        assert_eq!(summary.grade, "A");
+    }
+
+    #[test]
+    fn policy_decides_how_statuses_count() {
+        let results = [
+            result("a", "p", "PASS", "medium"),
+            result("b", "p", "ERROR", "medium"),
+        ];
+        let mut policy = GradingPolicy::default();
+        assert_eq!(summarize(&results, &policy).score, 100.0);
+        policy.error = "fail".into();
+        assert_eq!(summarize(&results, &policy).score, 50.0);
+        policy.error = "pass".into();
+        assert_eq!(summarize(&results, &policy).score, 100.0);
+    }
+
+    #[test]
+    fn waived_checks_are_left_out_of_the_score() {
+        let results = [
+            result("a", "p", "PASS", "medium"),
+            result("b", "p", "WAIVED", "critical"),
+        ];
+        let summary = summarize(&results, &GradingPolicy::default());
+        assert_eq!(summary.score, 100.0);
+        assert_eq!(summary.waived, 1);
+        assert!(summary.critical_failures.is_empty());
+    }
+
+    #[test]
+    fn critical_failure_caps_the_grade() {
+        let mut results: Vec<CheckResult> = (0..50)
+            .map(|i| result(&format!("ok{}", i), "p", "PASS", "critical"))
+            .collect();
+        results.push(result("bad", "p", "FAIL", "critical"));
+        let summary = summarize(&results, &GradingPolicy::default());
+        assert!(summary.score >= 95.0);
+        assert_eq!(summary.grade, "C");
+        assert_eq!(summary.critical_failures, ["bad"]);
+    }
+
+    #[test]
+    fn critical_timeout_and_error_cap_the_grade_too() {
+        for status in ["TIMEOUT", "ERROR"] {
+            let results = [
+                result("ok", "p", "PASS", "critical"),
+                result("bad", "p", status, "critical"),
+            ];
+            let summary = summarize(&results, &GradingPolicy::default());
+            assert_eq!(summary.score, 100.0, "{} is ignored by default", status);
+            assert_eq!(summary.grade, "C", "{}", status);
+            assert_eq!(summary.critical_failures, ["bad"]);
+        }
+    }
+
+    #[test]
+    fn cap_never_raises_a_grade() {
+        let results = [
+            result("a", "p", "FAIL", "critical"),
+            result("b", "p", "FAIL", "high"),
+        ];
+        let summary = summarize(&results, &GradingPolicy::default());
+        assert_eq!(summary.grade, "F");
+    }
+
+    #[test]
+    fn no_cap_without_a_policy_cap() {
+        let mut results: Vec<CheckResult> = (0..50)
+            .map(|i| result(&format!("ok{}", i), "p", "PASS", "critical"))
+            .collect();
+        results.push(result("bad", "p", "FAIL", "critical"));
+        let policy = GradingPolicy {
+            critical_failure_cap: None,
+            ..GradingPolicy::default()
+        };
+        assert_eq!(summarize(&results, &policy).grade, "A");
+    }
+
+    #[test]
+    fn phase_counts_add_up_to_the_phase_total() {
+        let results = [
+            result("a", "one", "PASS", "medium"),
+            result("b", "one", "FIXED", "medium"),
+            result("c", "one", "WAIVED", "medium"),
+            result("d", "one", "SKIPPED", "medium"),
+            result("e", "one", "TIMEOUT", "medium"),
+            result("f", "two", "FAIL", "high"),
+        ];
+        let summary = summarize(&results, &GradingPolicy::default());
+        assert_eq!(summary.phases.len(), 2);
+
+        let one = &summary.phases[0];
+        assert_eq!(one.phase, "one");
+        assert_eq!(
+            (one.passed, one.failed, one.skipped, one.waived, one.errors),
+            (2, 0, 1, 1, 1)
+        );
+        assert_eq!(
+            one.passed + one.failed + one.skipped + one.waived + one.errors,
+            5
+        );
+
+        let two = &summary.phases[1];
+        assert_eq!((two.failed, two.score, two.grade.as_str()), (1, 0.0, "F"));
+    }
+}
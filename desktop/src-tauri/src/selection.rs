@@ -0,0 +1,256 @@
+//! Choosing which checks a scan runs: named profiles from
+//! checks/profiles.toml and ad-hoc filters by id, phase, tag, severity or
+//! platform.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::types::{CheckFilter, CheckMeta, ProfileDef, ScanSelection};
+
+#[derive(Deserialize)]
+struct ProfileFile {
+    #[serde(default)]
+    profile: Vec<ProfileDef>,
+}
+
+/// Load the profiles from checks/profiles.toml, or none if there isn't one.
+pub fn load_profiles(base: &Path) -> Result<Vec<ProfileDef>, String> {
+    let path = base.join("checks").join("profiles.toml");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file: ProfileFile = toml::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    for (i, profile) in file.profile.iter().enumerate() {
+        if file.profile[..i].iter().any(|p| p.id == profile.id) {
+            return Err(format!(
+                "{}: profile \"{}\" is defined more than once",
+                path.display(),
+                profile.id
+            ));
+        }
+    }
+
+    Ok(file.profile)
+}
+
+/// The checks `selection` picks out of `checks`, in catalog order. Fails on
+/// an unknown profile, or if nothing is left to run.
+pub fn select_checks(
+    checks: Vec<CheckMeta>,
+    selection: &ScanSelection,
+    profiles: &[ProfileDef],
+) -> Result<Vec<CheckMeta>, String> {
+    let profile = match &selection.profile {
+        Some(id) => Some(profiles.iter().find(|p| &p.id == id).ok_or_else(|| {
+            let known: Vec<&str> = profiles.iter().map(|p| p.id.as_str()).collect();
+            format!("Unknown profile \"{}\" (have: {})", id, known.join(", "))
+        })?),
+        None => None,
+    };
+
+    let total = checks.len();
+    let selected: Vec<CheckMeta> = checks
+        .into_iter()
+        .filter(|c| profile.is_none_or(|p| matches(&p.filter, c)))
+        .filter(|c| matches(&selection.filter, c))
+        .collect();
+
+    if selected.is_empty() && total > 0 {
+        return Err("No checks match the selection".to_string());
+    }
+    Ok(selected)
+}
+
+/// Whether `check` passes every part of `filter`.
+pub fn matches(filter: &CheckFilter, check: &CheckMeta) -> bool {
+    let any =
+        |wanted: &[String], value: &str| wanted.is_empty() || wanted.iter().any(|w| w == value);
+
+    any(&filter.checks, &check.id)
+        && any(&filter.phases, &check.phase)
+        && any(&filter.platforms, &check.platform)
+        && any(&filter.severities, &check.severity)
+        && (filter.tags.is_empty() || filter.tags.iter().any(|t| check.tags.contains(t)))
+        && !filter
+            .exclude
+            .iter()
+            .any(|e| *e == check.id || check.tags.contains(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(id: &str, phase: &str, platform: &str, severity: &str, tags: &[&str]) -> CheckMeta {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "name": id,
+            "phase": phase,
+            "platform": platform,
+            "description": "",
+            "severity": severity,
+            "tags": tags,
+        }))
+        .unwrap()
+    }
+
+    fn catalog() -> Vec<CheckMeta> {
+        vec![
+            check("ssh", "hardening", "linux", "high", &["network", "cis"]),
+            check("firewall", "hardening", "all", "critical", &["network"]),
+            check("node", "prerequisites", "all", "low", &[]),
+            check("filevault", "hardening", "macos", "medium", &["disk"]),
+        ]
+    }
+
+    fn ids(checks: &[CheckMeta]) -> Vec<&str> {
+        checks.iter().map(|c| c.id.as_str()).collect()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = CheckFilter::default();
+        assert!(catalog().iter().all(|c| matches(&filter, c)));
+    }
+
+    #[test]
+    fn lists_are_anded_and_entries_ored() {
+        let filter = CheckFilter {
+            phases: strings(&["hardening"]),
+            severities: strings(&["high", "critical"]),
+            ..Default::default()
+        };
+        let picked: Vec<CheckMeta> = catalog()
+            .into_iter()
+            .filter(|c| matches(&filter, c))
+            .collect();
+        assert_eq!(ids(&picked), ["ssh", "firewall"]);
+    }
+
+    #[test]
+    fn tags_match_any_and_exclude_takes_ids_or_tags() {
+        let filter = CheckFilter {
+            tags: strings(&["network", "disk"]),
+            exclude: strings(&["cis"]),
+            ..Default::default()
+        };
+        let picked: Vec<CheckMeta> = catalog()
+            .into_iter()
+            .filter(|c| matches(&filter, c))
+            .collect();
+        assert_eq!(ids(&picked), ["firewall", "filevault"]);
+
+        let filter = CheckFilter {
+            exclude: strings(&["firewall"]),
+            ..Default::default()
+        };
+        let picked: Vec<CheckMeta> = catalog()
+            .into_iter()
+            .filter(|c| matches(&filter, c))
+            .collect();
+        assert_eq!(ids(&picked), ["ssh", "node", "filevault"]);
+    }
+
+    #[test]
+    fn platform_filter_uses_the_declared_platform() {
+        let filter = CheckFilter {
+            platforms: strings(&["all"]),
+            ..Default::default()
+        };
+        let picked: Vec<CheckMeta> = catalog()
+            .into_iter()
+            .filter(|c| matches(&filter, c))
+            .collect();
+        assert_eq!(ids(&picked), ["firewall", "node"]);
+    }
+
+    #[test]
+    fn profile_and_filter_both_apply_in_catalog_order() {
+        let profiles = [ProfileDef {
+            id: "network".to_string(),
+            label: "Network".to_string(),
+            description: String::new(),
+            filter: CheckFilter {
+                tags: strings(&["network"]),
+                ..Default::default()
+            },
+        }];
+        let selection = ScanSelection {
+            profile: Some("network".to_string()),
+            filter: CheckFilter {
+                checks: strings(&["firewall", "node", "ssh"]),
+                ..Default::default()
+            },
+        };
+        let picked = select_checks(catalog(), &selection, &profiles).unwrap();
+        assert_eq!(ids(&picked), ["ssh", "firewall"]);
+    }
+
+    #[test]
+    fn unknown_profile_lists_the_known_ones() {
+        let profiles = [ProfileDef {
+            id: "quick".to_string(),
+            label: "Quick".to_string(),
+            description: String::new(),
+            filter: CheckFilter::default(),
+        }];
+        let selection = ScanSelection {
+            profile: Some("full".to_string()),
+            ..Default::default()
+        };
+        let err = select_checks(catalog(), &selection, &profiles).unwrap_err();
+        assert_eq!(err, "Unknown profile \"full\" (have: quick)");
+    }
+
+    #[test]
+    fn an_empty_selection_is_an_error_but_an_empty_catalog_is_not() {
+        let selection = ScanSelection {
+            filter: CheckFilter {
+                checks: strings(&["nope"]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = select_checks(catalog(), &selection, &[]).unwrap_err();
+        assert_eq!(err, "No checks match the selection");
+        assert!(select_checks(Vec::new(), &selection, &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn duplicate_profile_ids_are_rejected() {
+        let base = std::env::temp_dir().join(format!("clawkeeper-profiles-{}", std::process::id()));
+        std::fs::create_dir_all(base.join("checks")).unwrap();
+        std::fs::write(
+            base.join("checks").join("profiles.toml"),
+            "[[profile]]\nid = \"quick\"\nlabel = \"Quick\"\n\n[[profile]]\nid = \"quick\"\nlabel = \"Again\"\n",
+        )
+        .unwrap();
+        let result = load_profiles(&base);
+        std::fs::remove_dir_all(&base).unwrap();
+        assert!(result
+            .unwrap_err()
+            .ends_with("profile \"quick\" is defined more than once"));
+
+        let missing = std::env::temp_dir().join("clawkeeper-profiles-missing");
+        assert!(load_profiles(&missing).unwrap().is_empty());
+    }
+
+    #[test]
+    fn bundled_profiles_load() {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let profiles = load_profiles(&base).unwrap();
+        assert!(!profiles.is_empty());
+    }
+}
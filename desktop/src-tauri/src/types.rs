@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 /// Metadata from check.toml
@@ -12,14 +14,508 @@ pub struct CheckMeta {
     pub requires_sudo: bool,
     #[serde(default)]
     pub order: u32,
+    /// Run this check alone, never alongside other checks
+    #[serde(default)]
+    pub exclusive: bool,
+    /// Per-check timeout; falls back to the scan-wide default
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// Free-form labels (e.g. "ssh", "docker") used by CI gates and profiles
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// critical, high, medium or low; sets the check's weight in the score
+    #[serde(default = "default_severity")]
+    pub severity: String,
+    /// Overrides the weight implied by `severity`
+    #[serde(default)]
+    pub weight: Option<f64>,
+    /// Host facts the check needs (e.g. "docker", "openclaw"); without
+    /// them it is skipped rather than run
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Ids of checks in the same phase that must finish before this one
+    /// starts
+    #[serde(default)]
+    pub after: Vec<String>,
+    /// Compliance controls the check covers, by framework id from
+    /// checks/frameworks.toml
+    #[serde(default)]
+    pub controls: BTreeMap<String, ControlMapping>,
+    /// Where the check was loaded from; set by the catalog, not check.toml
+    #[serde(skip_deserializing)]
+    pub origin: CheckOrigin,
 }
 
-/// Phase info for the frontend
+/// How a check covers a framework's controls: as a whole (e.g.
+/// `soc2 = ["CC6.1"]`), or each control by the finding that settles it
+/// (e.g. `"5.2.7" = "PermitRootLogin"` under `[controls.cis_ubuntu]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ControlMapping {
+    Check(Vec<String>),
+    Findings(BTreeMap<String, String>),
+}
+
+/// Which check root a check came from
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CheckOrigin {
+    /// "bundled", "user" (~/.clawkeeper/checks) or "env"
+    /// ($CLAWKEEPER_CHECK_PATH)
+    pub source: String,
+    /// The check's directory, holding check.sh and remediate.sh
+    pub dir: String,
+    /// Directories of lower-precedence checks with the same id that this
+    /// one replaces
+    pub shadows: Vec<String>,
+}
+
+fn default_severity() -> String {
+    "medium".to_string()
+}
+
+fn default_weight() -> f64 {
+    1.0
+}
+
+/// A problem with a check's definition, found by the catalog linter or
+/// skipped over by a lenient catalog load
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogProblem {
+    /// The check.toml, check.sh or directory at fault
+    pub path: String,
+    /// None if the problem isn't tied to one check
+    pub check_id: Option<String>,
+    pub message: String,
+}
+
+/// The checks for this host, and the check.toml files left out of them
+/// because they couldn't be loaded
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogListing {
+    pub checks: Vec<CheckMeta>,
+    pub problems: Vec<CatalogProblem>,
+}
+
+/// What the runner knows about the host before any check runs, gathered
+/// once per scan and handed to every check (see `facts`).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HostFacts {
+    /// macOS product version, or VERSION_ID on Linux
+    pub os_version: Option<String>,
+    /// Which of `facts::TOOLS` are on $PATH
+    pub tools: Vec<String>,
+    /// The docker CLI is installed
+    pub docker: bool,
+    /// The Docker daemon answers
+    pub docker_running: bool,
+    /// OpenClaw is installed or configured (CLI, ~/.openclaw or Docker
+    /// Compose setup)
+    pub openclaw: bool,
+    /// "docker" or "native", if an install was found
+    pub openclaw_install: Option<String>,
+    pub openclaw_running: bool,
+    pub openclaw_version: Option<String>,
+    /// TCP ports something is listening on
+    pub listening_ports: Vec<u16>,
+}
+
+/// Host platform detected at scan time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformInfo {
+    /// "macos" or "linux"
+    pub os: String,
+    pub arch: String,
+    /// Linux only: os-release ID (e.g. "ubuntu")
+    pub distro: Option<String>,
+    /// Linux only: distro family (e.g. "debian", "rhel")
+    pub distro_family: Option<String>,
+    pub distro_version: Option<String>,
+    pub distro_name: Option<String>,
+}
+
+/// A phase definition from checks/phases.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseDef {
+    pub id: String,
+    pub label: String,
+    pub order: u32,
+    #[serde(default)]
+    pub description: String,
+    /// Limits this definition to matching hosts; None applies everywhere
+    #[serde(default)]
+    pub platform: Option<String>,
+}
+
+/// Which checks a scan runs. Each non-empty list narrows the selection:
+/// a check must match one entry in every list given.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckFilter {
+    /// Check ids
+    #[serde(default)]
+    pub checks: Vec<String>,
+    #[serde(default)]
+    pub phases: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// critical, high, medium or low
+    #[serde(default)]
+    pub severities: Vec<String>,
+    /// The check.toml `platform` ("macos", "linux" or "all")
+    #[serde(default)]
+    pub platforms: Vec<String>,
+    /// Check ids or tags to leave out
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// A named scan profile from checks/profiles.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileDef {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(flatten)]
+    pub filter: CheckFilter,
+}
+
+/// What to scan: a profile, further narrowed by an ad-hoc filter. The
+/// default runs every check.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanSelection {
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(flatten)]
+    pub filter: CheckFilter,
+}
+
+/// A compliance framework from checks/frameworks.toml
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameworkDef {
+    pub id: String,
+    pub label: String,
+    /// Limits the framework to matching hosts, like a check's `platform`;
+    /// None applies everywhere
+    #[serde(default)]
+    pub platform: Option<String>,
+    /// The controls in scope, in benchmark order
+    #[serde(default)]
+    pub controls: Vec<ControlDef>,
+}
+
+/// One control of a compliance framework
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlDef {
+    pub id: String,
+    pub title: String,
+}
+
+/// How a scan covers one compliance framework
+#[derive(Debug, Clone, Serialize)]
+pub struct FrameworkCoverage {
+    pub framework: String,
+    pub label: String,
+    pub controls: Vec<ControlCoverage>,
+    /// Controls with at least one mapped check
+    pub mapped: u32,
+    pub passed: u32,
+    pub failed: u32,
+    pub waived: u32,
+    /// Mapped, but no mapped check produced a verdict in this scan
+    pub not_assessed: u32,
+    /// No check maps to the control; it needs assessing another way
+    pub unmapped: u32,
+}
+
+/// One control's status in a scan
 #[derive(Debug, Clone, Serialize)]
+pub struct ControlCoverage {
+    pub id: String,
+    pub title: String,
+    /// PASS, FAIL, WAIVED, NOT_ASSESSED or UNMAPPED
+    pub status: String,
+    /// Ids of the checks mapped to the control, as "check_id" or
+    /// "check_id/finding" when one finding settles it
+    pub checks: Vec<String>,
+}
+
+/// Phase info for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PhaseInfo {
     pub id: String,
     pub label: String,
     pub order: u32,
+    pub description: String,
+}
+
+/// Final result of one check, kept after the scan so the tally can be
+/// recomputed (e.g. after a remediation)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub check_id: String,
+    pub check_name: String,
+    pub phase: String,
+    /// PASS, FAIL, FIXED, SKIPPED, WAIVED, TIMEOUT or ERROR
+    pub status: String,
+    pub detail: String,
+    #[serde(default)]
+    pub messages: Vec<CheckMessage>,
+    /// Wall-clock run time of check.sh
+    #[serde(default)]
+    pub duration_ms: u64,
+    /// The fix the check offered, if it prompted for one
+    #[serde(default)]
+    pub remediation: Option<RemediationHint>,
+    /// Copied from the check so saved scans re-score the same way.
+    /// Scans saved before weighting default to equal weights.
+    #[serde(default = "default_severity")]
+    pub severity: String,
+    #[serde(default = "default_weight")]
+    pub weight: f64,
+    /// Every status line the check emitted, in order
+    #[serde(default)]
+    pub findings: Vec<Finding>,
+    /// What the check observed to reach its verdict
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+    /// Set when the script exited abnormally or wrote to stderr
+    #[serde(default)]
+    pub diagnostics: Option<ScriptDiagnostics>,
+    /// Output that broke the check protocol; only collected in strict mode
+    #[serde(default)]
+    pub protocol_violations: Vec<ProtocolViolation>,
+    /// Waivers that turned failures in this check into WAIVED
+    #[serde(default)]
+    pub waivers: Vec<Waiver>,
+}
+
+/// An accepted risk from the waiver file: a failing check, or one finding
+/// within it, left out of the score until the waiver expires.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Waiver {
+    /// Check id
+    pub check: String,
+    /// Finding name within the check, e.g. "X11Forwarding"; None waives
+    /// every failure in the check
+    #[serde(default)]
+    pub finding: Option<String>,
+    pub justification: String,
+    pub owner: String,
+    /// YYYY-MM-DD; the waiver holds through the end of this day (UTC)
+    pub expires: String,
+}
+
+/// A line of check output that doesn't follow the protocol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolViolation {
+    /// 1-based line number on stdout
+    pub line: usize,
+    pub message: String,
+}
+
+/// How a check script exited and what it wrote to stderr.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptDiagnostics {
+    /// None if the script was killed by a signal
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
+    /// The start of stderr, bounded in size
+    pub stderr: String,
+    pub stderr_truncated: bool,
+}
+
+/// One status line from a check. Checks like linux_ssh_hardening emit
+/// several (PermitRootLogin, PasswordAuthentication, ...), which are
+/// aggregated into the check's status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    /// The line's check_name, or the check's own name if it gave none
+    pub name: String,
+    pub status: String,
+    pub detail: String,
+}
+
+/// Supporting data for a verdict, e.g. PermitRootLogin = yes read from
+/// /etc/ssh/sshd_config where "no" was expected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Evidence {
+    /// Name of the finding this supports: the status line that followed
+    /// it. None if no status line did.
+    #[serde(default)]
+    pub finding: Option<String>,
+    /// What was inspected, e.g. "PermitRootLogin"
+    pub key: String,
+    pub observed: String,
+    #[serde(default)]
+    pub expected: Option<String>,
+    /// The file or command the value came from
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// A remediation offered by a check via `emit_prompt`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemediationHint {
+    pub remediation_id: String,
+    /// The question shown to the user, e.g. "Disable automatic login?"
+    pub message: String,
+}
+
+/// An info/warn/error line emitted while a check ran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckMessage {
+    /// "info", "warn" or "error"
+    pub level: String,
+    pub message: String,
+}
+
+/// Tally and grade for a set of check results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSummary {
+    pub passed: u32,
+    pub failed: u32,
+    pub fixed: u32,
+    pub skipped: u32,
+    /// Failures covered by a waiver; left out of the score
+    #[serde(default)]
+    pub waived: u32,
+    pub errors: u32,
+    pub total: u32,
+    pub score: f64,
+    pub grade: String,
+    /// Sub-scores per phase, in scan order
+    #[serde(default)]
+    pub phases: Vec<PhaseScore>,
+    /// Ids of critical checks that failed, timed out or errored; any of
+    /// these caps the grade
+    #[serde(default)]
+    pub critical_failures: Vec<String>,
+}
+
+/// How scores are turned into grades, from checks/grading.toml. Shared
+/// with the bash CLI and agent so every front end grades the same way.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradingPolicy {
+    /// How FIXED, SKIPPED, TIMEOUT and ERROR results count: "pass",
+    /// "fail" or "ignore" (left out of the score)
+    pub fixed: String,
+    pub skipped: String,
+    pub timeout: String,
+    pub error: String,
+    /// Best grade possible while a critical check is failing, timed out or
+    /// errored
+    #[serde(default)]
+    pub critical_failure_cap: Option<String>,
+    /// Minimum score for each grade A–D; anything lower is an F
+    pub thresholds: BTreeMap<String, f64>,
+}
+
+/// Weighted score for one phase of a scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseScore {
+    pub phase: String,
+    pub passed: u32,
+    pub failed: u32,
+    pub skipped: u32,
+    #[serde(default)]
+    pub waived: u32,
+    pub errors: u32,
+    pub score: f64,
+    pub grade: String,
+}
+
+/// A completed scan as persisted in the history store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanRecord {
+    pub id: String,
+    /// Unix timestamps (seconds)
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub hostname: String,
+    pub platform: PlatformInfo,
+    pub summary: ScanSummary,
+    pub checks: Vec<CheckResult>,
+    /// Every check for the host when the scan ran, selected or not, so
+    /// compliance and exports read the mappings and descriptions it ran
+    /// with. Empty in scans saved before it was recorded.
+    #[serde(default)]
+    pub catalog: Vec<CheckMeta>,
+    /// The phases the scan ran, in order
+    #[serde(default)]
+    pub phases: Vec<PhaseInfo>,
+}
+
+/// History list entry: a `ScanRecord` without its per-check results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanListing {
+    pub id: String,
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub hostname: String,
+    pub summary: ScanSummary,
+}
+
+/// Drift between two scans
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanDiff {
+    pub from_id: String,
+    pub to_id: String,
+    pub score_from: f64,
+    pub score_to: f64,
+    pub score_delta: f64,
+    pub grade_from: String,
+    pub grade_to: String,
+    /// Anything else → FAIL, or passing (PASS/FIXED) → TIMEOUT/ERROR
+    pub regressions: Vec<CheckChange>,
+    /// FAIL/TIMEOUT/ERROR → passing (PASS/FIXED)
+    pub fixes: Vec<CheckChange>,
+    /// Any other status change (e.g. PASS → SKIPPED, FAIL → TIMEOUT)
+    pub changed: Vec<CheckChange>,
+    /// Checks only in the newer scan
+    pub added: Vec<CheckChange>,
+    /// Checks only in the older scan
+    pub removed: Vec<CheckChange>,
+    pub unchanged: u32,
+    /// Findings failing in the newer scan that weren't before, including
+    /// within a check that was already failing
+    pub new_findings: Vec<FindingChange>,
+    /// Findings failing in the older scan that no longer are
+    pub resolved_findings: Vec<FindingChange>,
+    /// Any other finding status change, or a non-failing finding that
+    /// appeared or went away
+    pub changed_findings: Vec<FindingChange>,
+}
+
+/// One check's status in each of two scans
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckChange {
+    pub check_id: String,
+    pub check_name: String,
+    pub from_status: Option<String>,
+    pub to_status: Option<String>,
+    pub from_detail: Option<String>,
+    pub to_detail: Option<String>,
+}
+
+/// One finding's status in each of two scans. Findings are matched by
+/// name within a check (the nth of a repeated name to the nth).
+#[derive(Debug, Clone, Serialize)]
+pub struct FindingChange {
+    pub check_id: String,
+    pub check_name: String,
+    pub finding: String,
+    pub from_status: Option<String>,
+    pub to_status: Option<String>,
+    pub from_detail: Option<String>,
+    pub to_detail: Option<String>,
+}
+
+/// One CI gate that a scan did not meet
+#[derive(Debug, Clone, Serialize)]
+pub struct GateViolation {
+    /// "min_score", "min_grade" or "fail_on_tags"
+    pub gate: String,
+    pub message: String,
 }
 
 /// Events streamed to the frontend via Channel
@@ -27,9 +523,16 @@ pub struct PhaseInfo {
 #[serde(tag = "event")]
 pub enum ScanEvent {
     ScanStarted {
+        platform: PlatformInfo,
+        facts: HostFacts,
         checks: Vec<CheckMeta>,
         phases: Vec<PhaseInfo>,
     },
+    /// A check was left out of the scan because its check.toml is broken
+    CatalogWarning {
+        path: String,
+        message: String,
+    },
     PhaseStarted {
         phase_id: String,
         phase_label: String,
@@ -45,11 +548,30 @@ pub enum ScanEvent {
         check_id: String,
         message: String,
     },
+    Finding {
+        check_id: String,
+        name: String,
+        status: String,
+        detail: String,
+    },
+    Diagnostic {
+        check_id: String,
+        exit_code: Option<i32>,
+        signal: Option<i32>,
+        stderr: String,
+        stderr_truncated: bool,
+    },
+    ProtocolViolation {
+        check_id: String,
+        line: usize,
+        message: String,
+    },
     CheckCompleted {
         check_id: String,
         check_name: String,
         status: String,
         detail: String,
+        evidence: Vec<Evidence>,
     },
     Prompt {
         check_id: String,
@@ -58,13 +580,25 @@ pub enum ScanEvent {
         fail_detail: String,
         skip_detail: String,
     },
-    ScanCompleted {
-        passed: u32,
-        failed: u32,
-        skipped: u32,
+    ScanCompleted(ScanSummary),
+    ScanCancelled {
+        completed: u32,
         total: u32,
-        score: f64,
-        grade: String,
+    },
+    GatesEvaluated {
+        passed: bool,
+        violations: Vec<GateViolation>,
+    },
+    RemediationStarted {
+        check_id: String,
+        remediation_id: String,
+    },
+    RemediationCompleted {
+        check_id: String,
+        remediation_id: String,
+        /// FIXED or FAIL
+        status: String,
+        detail: String,
     },
     Error {
         check_id: String,
@@ -72,9 +606,9 @@ pub enum ScanEvent {
     },
 }
 
-/// Flat struct for deserializing JSON lines from check scripts.
-/// Each line could be a status, info/warn, or prompt — we use
-/// Option fields and match on which are present.
+/// Flat struct for deserializing check output that predates the protocol
+/// header (see `protocol`). Each line could be a status, info/warn, or
+/// prompt — we use Option fields and match on which are present.
 #[derive(Debug, Deserialize)]
 pub struct CheckOutputLine {
     // Status line fields
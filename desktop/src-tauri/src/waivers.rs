@@ -0,0 +1,288 @@
+//! Accepted risks: failures the owner of a host has decided to live with,
+//! e.g. Bluetooth on a laptop that needs a headset. A waiver turns a FAIL
+//! into WAIVED, which the score leaves out, until it expires.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::types::{Finding, Waiver};
+
+#[derive(Deserialize)]
+struct WaiverFile {
+    #[serde(default)]
+    waiver: Vec<WaiverEntry>,
+}
+
+/// A waiver as written.
+#[derive(Deserialize)]
+struct WaiverEntry {
+    check: String,
+    #[serde(default)]
+    finding: Option<String>,
+    justification: String,
+    owner: String,
+    expires: Expiry,
+}
+
+/// `expires = 2026-12-31`, or the same date quoted.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Expiry {
+    Date(toml::value::Datetime),
+    Text(String),
+}
+
+/// Where the desktop app and `clawkeeper-scan` look for waivers by default.
+pub fn default_path() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(".clawkeeper").join("waivers.toml"))
+}
+
+/// Load waivers from `path`, or none if the file doesn't exist. Every
+/// waiver needs a justification, an owner and an expiry date.
+pub fn load_waivers(path: &Path) -> Result<Vec<Waiver>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file: WaiverFile = toml::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    file.waiver
+        .into_iter()
+        .map(|entry| {
+            let expires = match entry.expires {
+                Expiry::Date(datetime) => Some(datetime),
+                Expiry::Text(text) => text.trim().parse().ok(),
+            };
+            let date =
+                expires.and_then(|e: toml::value::Datetime| e.date.filter(|_| e.time.is_none()));
+            let Some(date) = date else {
+                return Err(format!(
+                    "{}: waiver for {}: expires must be a date, e.g. 2026-12-31",
+                    path.display(),
+                    entry.check
+                ));
+            };
+            for (key, value) in [
+                ("justification", &entry.justification),
+                ("owner", &entry.owner),
+            ] {
+                if value.trim().is_empty() {
+                    return Err(format!(
+                        "{}: waiver for {}: {} is empty",
+                        path.display(),
+                        entry.check,
+                        key
+                    ));
+                }
+            }
+            Ok(Waiver {
+                check: entry.check,
+                finding: entry.finding,
+                justification: entry.justification,
+                owner: entry.owner,
+                expires: date.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Waivers from the default file, or none without $HOME.
+pub fn load_default() -> Result<Vec<Waiver>, String> {
+    match default_path() {
+        Some(path) => load_waivers(&path),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Today's date (UTC) as YYYY-MM-DD, for comparing with `Waiver::expires`.
+pub fn today() -> String {
+    crate::export::rfc3339(crate::history::now_secs())[..10].to_string()
+}
+
+/// Whether `waiver` has not yet expired on `today` (both YYYY-MM-DD).
+pub fn is_active(waiver: &Waiver, today: &str) -> bool {
+    waiver.expires.as_str() >= today
+}
+
+/// The waiver covering `finding` in check `check_id`, if any. One naming
+/// the finding wins over one for the whole check.
+pub fn find<'a>(waivers: &'a [Waiver], check_id: &str, finding: &Finding) -> Option<&'a Waiver> {
+    let for_check = || waivers.iter().filter(|w| w.check == check_id);
+    for_check()
+        .find(|w| w.finding.as_deref() == Some(finding.name.as_str()))
+        .or_else(|| for_check().find(|w| w.finding.is_none()))
+}
+
+/// One line describing a waiver, e.g. "Waived by alice until 2026-12-31:
+/// needs a headset".
+pub fn describe(waiver: &Waiver) -> String {
+    let subject = match &waiver.finding {
+        Some(finding) => format!("{} waived", finding),
+        None => "Waived".to_string(),
+    };
+    format!(
+        "{} by {} until {}: {}",
+        subject, waiver.owner, waiver.expires, waiver.justification
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Load waivers from `content` written to a scratch file.
+    fn load(name: &str, content: &str) -> Result<Vec<Waiver>, String> {
+        let path = std::env::temp_dir().join(format!(
+            "clawkeeper-waivers-{}-{}.toml",
+            std::process::id(),
+            name
+        ));
+        std::fs::write(&path, content).unwrap();
+        let waivers = load_waivers(&path);
+        std::fs::remove_file(&path).unwrap();
+        waivers
+    }
+
+    fn waiver(check: &str, finding: Option<&str>, expires: &str) -> Waiver {
+        Waiver {
+            check: check.to_string(),
+            finding: finding.map(str::to_string),
+            justification: "needs a headset".to_string(),
+            owner: "alice".to_string(),
+            expires: expires.to_string(),
+        }
+    }
+
+    fn finding(name: &str) -> Finding {
+        Finding {
+            name: name.to_string(),
+            status: "FAIL".to_string(),
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn missing_file_means_no_waivers() {
+        let path = std::env::temp_dir().join("clawkeeper-waivers-does-not-exist.toml");
+        assert!(load_waivers(&path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn expires_as_a_date_or_quoted() {
+        let waivers = load(
+            "dates",
+            r#"
+[[waiver]]
+check = "bluetooth"
+justification = "needs a headset"
+owner = "alice"
+expires = 2026-12-31
+
+[[waiver]]
+check = "linux_ssh_hardening"
+finding = "X11Forwarding"
+justification = "remote GUI tools"
+owner = "bob"
+expires = "2027-01-15"
+"#,
+        )
+        .unwrap();
+        assert_eq!(waivers.len(), 2);
+        assert_eq!(waivers[0].expires, "2026-12-31");
+        assert_eq!(waivers[0].finding, None);
+        assert_eq!(waivers[1].expires, "2027-01-15");
+        assert_eq!(waivers[1].finding.as_deref(), Some("X11Forwarding"));
+    }
+
+    #[test]
+    fn expires_must_be_a_plain_date() {
+        for expires in ["2026-12-31T12:00:00Z", r#""next year""#, r#""2026-13-01""#] {
+            let content = format!(
+                "[[waiver]]\ncheck = \"bluetooth\"\njustification = \"j\"\nowner = \"o\"\nexpires = {}\n",
+                expires
+            );
+            let err = load("bad-date", &content).unwrap_err();
+            assert!(
+                err.contains("expires must be a date"),
+                "{}: {}",
+                expires,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn justification_and_owner_are_required() {
+        let err = load(
+            "no-owner",
+            "[[waiver]]\ncheck = \"bluetooth\"\njustification = \"j\"\nowner = \"  \"\nexpires = 2026-12-31\n",
+        )
+        .unwrap_err();
+        assert!(
+            err.ends_with("waiver for bluetooth: owner is empty"),
+            "{}",
+            err
+        );
+
+        let err = load(
+            "missing-justification",
+            "[[waiver]]\ncheck = \"bluetooth\"\nowner = \"o\"\nexpires = 2026-12-31\n",
+        )
+        .unwrap_err();
+        assert!(err.contains("justification"), "{}", err);
+    }
+
+    #[test]
+    fn active_through_the_expiry_day() {
+        let w = waiver("bluetooth", None, "2026-12-31");
+        assert!(is_active(&w, "2026-01-01"));
+        assert!(is_active(&w, "2026-12-31"));
+        assert!(!is_active(&w, "2027-01-01"));
+    }
+
+    #[test]
+    fn finding_waiver_wins_over_check_waiver() {
+        let waivers = [
+            waiver("ssh", None, "2026-12-31"),
+            waiver("ssh", Some("X11Forwarding"), "2027-06-30"),
+            waiver("other", Some("PermitRootLogin"), "2027-06-30"),
+        ];
+        let found = find(&waivers, "ssh", &finding("X11Forwarding")).unwrap();
+        assert_eq!(found.finding.as_deref(), Some("X11Forwarding"));
+        let found = find(&waivers, "ssh", &finding("PermitRootLogin")).unwrap();
+        assert_eq!(found.finding, None);
+        assert!(find(&waivers, "nope", &finding("X11Forwarding")).is_none());
+    }
+
+    #[test]
+    fn finding_waiver_covers_only_that_finding() {
+        let waivers = [waiver("ssh", Some("X11Forwarding"), "2027-06-30")];
+        assert!(find(&waivers, "ssh", &finding("PermitRootLogin")).is_none());
+    }
+
+    #[test]
+    fn today_is_a_date() {
+        let today = today();
+        assert_eq!(today.len(), 10);
+        assert!(today
+            .parse::<toml::value::Datetime>()
+            .is_ok_and(|d| d.time.is_none()));
+    }
+
+    #[test]
+    fn describes_who_until_when_and_why() {
+        assert_eq!(
+            describe(&waiver("bluetooth", None, "2026-12-31")),
+            "Waived by alice until 2026-12-31: needs a headset"
+        );
+        assert_eq!(
+            describe(&waiver("ssh", Some("X11Forwarding"), "2026-12-31")),
+            "X11Forwarding waived by alice until 2026-12-31: needs a headset"
+        );
+    }
+}
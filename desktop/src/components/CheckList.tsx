@@ -5,9 +5,11 @@ import type { CheckState, PhaseInfo } from "../types/scan";
 export function CheckList({
   phases,
   getChecksForPhase,
+  onRemediate,
 }: {
   phases: PhaseInfo[];
   getChecksForPhase: (phaseId: string) => CheckState[];
+  onRemediate?: (checkId: string, remediationId: string) => void;
 }) {
   return (
     <div className="space-y-3">
@@ -23,7 +25,11 @@ export function CheckList({
             <PhaseHeader phase={phase} checks={checks} />
             <div>
               {checks.map((check) => (
-                <CheckRow key={check.meta.id} check={check} />
+                <CheckRow
+                  key={check.meta.id}
+                  check={check}
+                  onRemediate={onRemediate}
+                />
               ))}
             </div>
           </div>
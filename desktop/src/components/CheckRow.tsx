@@ -5,14 +5,25 @@ import {
   Info,
   AlertTriangle,
   AlertCircle,
+  Loader2,
+  Wrench,
 } from "lucide-react";
 import { cn } from "../lib/utils";
 import { StatusBadge } from "./StatusBadge";
 import type { CheckState } from "../types/scan";
 
-export function CheckRow({ check }: { check: CheckState }) {
+export function CheckRow({
+  check,
+  onRemediate,
+}: {
+  check: CheckState;
+  /** Runs the check's remediation; omitted while a scan is running */
+  onRemediate?: (checkId: string, remediationId: string) => void;
+}) {
   const [expanded, setExpanded] = useState(false);
   const hasMessages = check.messages.length > 0 || check.detail;
+  // A single finding is already shown as the check's detail
+  const findings = check.findings.length > 1 ? check.findings : [];
 
   return (
     <div className="border-b border-[var(--border)] last:border-b-0">
@@ -35,17 +46,73 @@ export function CheckRow({ check }: { check: CheckState }) {
           ) : null}
         </span>
 
-        <span className="flex-1 text-[13px] font-medium">{check.meta.name}</span>
+        <span className="flex-1 text-[13px] font-medium">
+          {check.meta.name}
+          {check.meta.origin.source !== "bundled" && (
+            <span
+              title={check.meta.origin.dir}
+              className="ml-2 text-[11px] font-normal text-[var(--muted-foreground)]"
+            >
+              {check.meta.origin.source === "user" ? "custom" : "external"}
+            </span>
+          )}
+        </span>
         <StatusBadge status={check.status} />
       </button>
 
       {expanded && hasMessages && (
         <div className="px-4 pb-2.5 pl-11 space-y-1 animate-in">
+          {check.status === "FAIL" && check.remediation && (
+            <div className="flex items-center gap-2 pb-1 text-xs">
+              <span className="flex-1">{check.remediation.message}</span>
+              <button
+                onClick={() =>
+                  onRemediate?.(
+                    check.meta.id,
+                    check.remediation!.remediation_id,
+                  )
+                }
+                disabled={!onRemediate || check.remediating}
+                className="inline-flex items-center gap-1.5 rounded-md border border-[var(--border)] px-2 py-1 font-medium transition-colors hover:bg-[var(--muted)] disabled:opacity-50 disabled:cursor-not-allowed"
+              >
+                {check.remediating ? (
+                  <Loader2 className="h-3 w-3 animate-spin" />
+                ) : (
+                  <Wrench className="h-3 w-3" />
+                )}
+                {check.remediating ? "Fixing..." : "Fix"}
+              </button>
+            </div>
+          )}
           {check.detail && (
             <p className="text-xs text-[var(--muted-foreground)]">
               {check.detail}
             </p>
           )}
+          {findings.map((finding, i) => (
+            <div key={`f${i}`} className="flex items-center gap-2 text-xs">
+              <StatusBadge status={finding.status as CheckState["status"]} />
+              <span className="font-medium">{finding.name}</span>
+              <span className="text-[var(--muted-foreground)]">
+                {finding.detail}
+              </span>
+            </div>
+          ))}
+          {check.evidence.map((evidence, i) => (
+            <div key={`e${i}`} className="text-xs text-[var(--muted-foreground)]">
+              <span className="font-medium text-[var(--foreground)]">
+                {evidence.key}
+              </span>{" "}
+              = <code>{evidence.observed}</code>
+              {evidence.expected && (
+                <>
+                  {" "}
+                  (expected <code>{evidence.expected}</code>)
+                </>
+              )}
+              {evidence.source && <> in {evidence.source}</>}
+            </div>
+          ))}
           {check.messages.map((msg, i) => (
             <div key={i} className="flex items-start gap-1.5 text-xs">
               {msg.type === "info" && (
@@ -1,4 +1,4 @@
-import { CheckCircle2, XCircle, MinusCircle } from "lucide-react";
+import { CheckCircle2, XCircle, MinusCircle, ShieldOff } from "lucide-react";
 import type { CheckState, PhaseInfo } from "../types/scan";
 
 export function PhaseHeader({
@@ -11,12 +11,16 @@ export function PhaseHeader({
   const passed = checks.filter((c) => c.status === "PASS").length;
   const failed = checks.filter((c) => c.status === "FAIL").length;
   const skipped = checks.filter((c) => c.status === "SKIPPED").length;
-  const done = passed + failed + skipped;
+  const waived = checks.filter((c) => c.status === "WAIVED").length;
+  const done = passed + failed + skipped + waived;
   const total = checks.length;
 
   return (
     <div className="flex items-center justify-between px-4 py-2.5 bg-[var(--muted)]/50">
-      <h3 className="text-xs font-semibold uppercase tracking-wider text-[var(--muted-foreground)]">
+      <h3
+        title={phase.description || undefined}
+        className="text-xs font-semibold uppercase tracking-wider text-[var(--muted-foreground)]"
+      >
         {phase.label}
       </h3>
       <div className="flex items-center gap-2.5">
@@ -38,6 +42,12 @@ export function PhaseHeader({
             {skipped}
           </span>
         )}
+        {waived > 0 && (
+          <span className="flex items-center gap-1 text-[11px] text-violet-400">
+            <ShieldOff className="h-3 w-3" />
+            {waived}
+          </span>
+        )}
         {done > 0 && (
           <span className="text-[11px] text-[var(--muted-foreground)]">
             {done}/{total}
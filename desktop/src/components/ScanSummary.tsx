@@ -1,5 +1,5 @@
 import { cn } from "../lib/utils";
-import { CheckCircle2, XCircle, MinusCircle } from "lucide-react";
+import { CheckCircle2, XCircle, MinusCircle, ShieldOff } from "lucide-react";
 import type { ScanSummaryData } from "../types/scan";
 
 const gradeColors: Record<string, { text: string; bg: string; border: string }> = {
@@ -36,7 +36,12 @@ export function ScanSummary({ summary }: { summary: ScanSummaryData }) {
         <div className="h-16 w-px bg-[var(--border)]" />
 
         {/* Counts */}
-        <div className="flex-1 grid grid-cols-3 gap-4">
+        <div
+          className={cn(
+            "flex-1 grid gap-4",
+            summary.waived > 0 ? "grid-cols-4" : "grid-cols-3",
+          )}
+        >
           <div className="flex items-center gap-2">
             <CheckCircle2 className="h-4 w-4 text-emerald-400" />
             <div>
@@ -58,6 +63,18 @@ export function ScanSummary({ summary }: { summary: ScanSummaryData }) {
               <p className="text-xs text-[var(--muted-foreground)]">Skipped</p>
             </div>
           </div>
+          {summary.waived > 0 && (
+            <div
+              className="flex items-center gap-2"
+              title="Failures covered by a waiver; left out of the score"
+            >
+              <ShieldOff className="h-4 w-4 text-violet-400" />
+              <div>
+                <p className="text-lg font-bold">{summary.waived}</p>
+                <p className="text-xs text-[var(--muted-foreground)]">Waived</p>
+              </div>
+            </div>
+          )}
         </div>
       </div>
     </div>
@@ -1,10 +1,21 @@
-import { Play, Loader2, RotateCcw } from "lucide-react";
+import { useState } from "react";
+import { Play, Loader2, RotateCcw, Square } from "lucide-react";
 import { useScan } from "../hooks/useScan";
 import { CheckList } from "./CheckList";
 import { ScanSummary } from "./ScanSummary";
 
 export function ScanView() {
-  const { state, startScan, getChecksForPhase } = useScan();
+  const {
+    state,
+    profiles,
+    startScan,
+    cancelScan,
+    runRemediation,
+    getChecksForPhase,
+  } = useScan();
+  const [chosen, setChosen] = useState<string>();
+  // The first profile in profiles.toml is the default
+  const profile = chosen ?? profiles[0]?.id;
 
   return (
     <div className="p-6 max-w-3xl mx-auto space-y-6">
@@ -19,7 +30,7 @@ export function ScanView() {
       {/* Action bar */}
       <div className="flex items-center gap-3">
         <button
-          onClick={startScan}
+          onClick={() => startScan(profile ? { profile } : undefined)}
           disabled={state.running}
           className="inline-flex items-center gap-2 rounded-lg bg-[var(--foreground)] px-4 py-2 text-sm font-medium text-[var(--background)] transition-opacity hover:opacity-90 disabled:opacity-50 disabled:cursor-not-allowed"
         >
@@ -41,6 +52,32 @@ export function ScanView() {
           )}
         </button>
 
+        {state.running && (
+          <button
+            onClick={cancelScan}
+            className="inline-flex items-center gap-2 rounded-lg border border-[var(--border)] px-4 py-2 text-sm font-medium transition-colors hover:bg-[var(--muted)]"
+          >
+            <Square className="h-4 w-4" />
+            Cancel
+          </button>
+        )}
+
+        {profiles.length > 0 && (
+          <select
+            value={profile}
+            onChange={(e) => setChosen(e.target.value)}
+            disabled={state.running}
+            title={profiles.find((p) => p.id === profile)?.description}
+            className="rounded-lg border border-[var(--border)] bg-[var(--background)] px-2 py-2 text-sm disabled:opacity-50"
+          >
+            {profiles.map((p) => (
+              <option key={p.id} value={p.id} title={p.description}>
+                {p.label}
+              </option>
+            ))}
+          </select>
+        )}
+
         {state.running && (
           <span className="text-xs text-[var(--muted-foreground)]">
             {Array.from(state.checks.values()).filter(
@@ -49,8 +86,28 @@ export function ScanView() {
             of {state.checks.size} checks complete
           </span>
         )}
+
+        {state.cancelled && !state.running && (
+          <span className="text-xs text-[var(--muted-foreground)]">
+            Scan cancelled
+          </span>
+        )}
       </div>
 
+      {/* Checks skipped because their check.toml is broken */}
+      {state.catalogWarnings.length > 0 && (
+        <div className="rounded-lg border border-[var(--border)] p-3 space-y-1">
+          {state.catalogWarnings.map((w) => (
+            <p
+              key={w.path}
+              className="text-xs font-mono text-[var(--muted-foreground)]"
+            >
+              Skipped {w.path}: {w.message}
+            </p>
+          ))}
+        </div>
+      )}
+
       {/* Summary */}
       {state.summary && !state.running && (
         <ScanSummary summary={state.summary} />
@@ -61,6 +118,7 @@ export function ScanView() {
         <CheckList
           phases={state.phases}
           getChecksForPhase={getChecksForPhase}
+          onRemediate={state.running ? undefined : runRemediation}
         />
       )}
 
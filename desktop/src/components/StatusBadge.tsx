@@ -5,16 +5,24 @@ const statusStyles: Record<CheckStatus, string> = {
   pending: "bg-[var(--muted)] text-[var(--muted-foreground)]",
   running: "bg-blue-500/15 text-blue-400",
   PASS: "bg-emerald-500/15 text-emerald-400",
+  FIXED: "bg-emerald-500/15 text-emerald-400",
   FAIL: "bg-red-500/15 text-red-400",
   SKIPPED: "bg-zinc-500/15 text-zinc-400",
+  WAIVED: "bg-violet-500/15 text-violet-400",
+  TIMEOUT: "bg-amber-500/15 text-amber-400",
+  ERROR: "bg-amber-500/15 text-amber-400",
 };
 
 const statusLabels: Record<CheckStatus, string> = {
   pending: "Pending",
   running: "Running",
   PASS: "Pass",
+  FIXED: "Fixed",
   FAIL: "Fail",
   SKIPPED: "Skipped",
+  WAIVED: "Waived",
+  TIMEOUT: "Timed out",
+  ERROR: "Error",
 };
 
 export function StatusBadge({ status }: { status: CheckStatus }) {
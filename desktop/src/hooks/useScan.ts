@@ -1,138 +1,266 @@
-import { useState, useCallback } from "react";
+import { useState, useCallback, useEffect } from "react";
 import { invoke, Channel } from "@tauri-apps/api/core";
-import type { ScanEvent, ScanState, CheckState } from "../types/scan";
+import type {
+  ScanEvent,
+  ScanState,
+  CheckState,
+  ProfileDef,
+  ScanSelection,
+} from "../types/scan";
 
 const initialState: ScanState = {
   running: false,
   checks: new Map(),
   phases: [],
   summary: null,
+  cancelled: false,
+  catalogWarnings: [],
 };
 
+/** Fold one scan or remediation event into the scan state. */
+function applyEvent(prev: ScanState, evt: ScanEvent): ScanState {
+  const checks = new Map(prev.checks);
+
+  switch (evt.event) {
+    case "ScanStarted": {
+      for (const meta of evt.checks) {
+        checks.set(meta.id, {
+          meta,
+          status: "pending",
+          detail: "",
+          messages: [],
+          findings: [],
+          evidence: [],
+        });
+      }
+      return { ...prev, checks, phases: evt.phases };
+    }
+
+    case "CatalogWarning": {
+      return {
+        ...prev,
+        catalogWarnings: [
+          ...prev.catalogWarnings,
+          { path: evt.path, message: evt.message },
+        ],
+      };
+    }
+
+    case "CheckStarted": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        // A re-run after a remediation starts over on findings
+        checks.set(evt.check_id, {
+          ...check,
+          status: "running",
+          findings: [],
+          remediation: undefined,
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "Info": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, {
+          ...check,
+          messages: [
+            ...check.messages,
+            { type: "info", message: evt.message },
+          ],
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "Warn": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, {
+          ...check,
+          messages: [
+            ...check.messages,
+            { type: "warn", message: evt.message },
+          ],
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "Finding": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, {
+          ...check,
+          findings: [
+            ...check.findings,
+            { name: evt.name, status: evt.status, detail: evt.detail },
+          ],
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "Diagnostic": {
+      const check = checks.get(evt.check_id);
+      if (check && evt.stderr) {
+        checks.set(evt.check_id, {
+          ...check,
+          messages: [
+            ...check.messages,
+            { type: "error", message: evt.stderr },
+          ],
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "ProtocolViolation": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, {
+          ...check,
+          messages: [
+            ...check.messages,
+            {
+              type: "warn",
+              message: `Protocol violation on line ${evt.line}: ${evt.message}`,
+            },
+          ],
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "CheckCompleted": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, {
+          ...check,
+          status: evt.status as CheckState["status"],
+          detail: evt.detail,
+          evidence: evt.evidence,
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "Prompt": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, {
+          ...check,
+          remediation: {
+            remediation_id: evt.remediation_id,
+            message: evt.message,
+          },
+          messages: [
+            ...check.messages,
+            { type: "warn", message: evt.message },
+          ],
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "Error": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, {
+          ...check,
+          messages: [
+            ...check.messages,
+            { type: "error", message: evt.message },
+          ],
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "ScanCompleted": {
+      return {
+        ...prev,
+        running: false,
+        summary: {
+          passed: evt.passed,
+          failed: evt.failed,
+          fixed: evt.fixed,
+          skipped: evt.skipped,
+          waived: evt.waived,
+          errors: evt.errors,
+          total: evt.total,
+          score: evt.score,
+          grade: evt.grade,
+          phases: evt.phases,
+          critical_failures: evt.critical_failures,
+        },
+      };
+    }
+
+    case "RemediationStarted": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, { ...check, remediating: true });
+      }
+      return { ...prev, checks };
+    }
+
+    case "RemediationCompleted": {
+      const check = checks.get(evt.check_id);
+      if (check) {
+        checks.set(evt.check_id, {
+          ...check,
+          remediating: false,
+          messages: [
+            ...check.messages,
+            {
+              type: evt.status === "FIXED" ? "info" : "error",
+              message: `Fix ${evt.status === "FIXED" ? "applied" : "failed"}: ${evt.detail}`,
+            },
+          ],
+        });
+      }
+      return { ...prev, checks };
+    }
+
+    case "ScanCancelled": {
+      return { ...prev, running: false, cancelled: true };
+    }
+
+    default:
+      return prev;
+  }
+}
+
 export function useScan() {
   const [state, setState] = useState<ScanState>(initialState);
+  const [profiles, setProfiles] = useState<ProfileDef[]>([]);
+
+  useEffect(() => {
+    invoke<ProfileDef[]>("list_profiles")
+      .then(setProfiles)
+      .catch((err) => console.error("Failed to load profiles:", err));
+  }, []);
 
-  const startScan = useCallback(async () => {
-    setState({ running: true, checks: new Map(), phases: [], summary: null });
+  const startScan = useCallback(async (selection?: ScanSelection) => {
+    setState({
+      running: true,
+      checks: new Map(),
+      phases: [],
+      summary: null,
+      cancelled: false,
+      catalogWarnings: [],
+    });
 
     const onEvent = new Channel<ScanEvent>();
 
     onEvent.onmessage = (evt: ScanEvent) => {
-      setState((prev) => {
-        const checks = new Map(prev.checks);
-
-        switch (evt.event) {
-          case "ScanStarted": {
-            for (const meta of evt.checks) {
-              checks.set(meta.id, {
-                meta,
-                status: "pending",
-                detail: "",
-                messages: [],
-              });
-            }
-            return { ...prev, checks, phases: evt.phases };
-          }
-
-          case "CheckStarted": {
-            const check = checks.get(evt.check_id);
-            if (check) {
-              checks.set(evt.check_id, { ...check, status: "running" });
-            }
-            return { ...prev, checks };
-          }
-
-          case "Info": {
-            const check = checks.get(evt.check_id);
-            if (check) {
-              checks.set(evt.check_id, {
-                ...check,
-                messages: [
-                  ...check.messages,
-                  { type: "info", message: evt.message },
-                ],
-              });
-            }
-            return { ...prev, checks };
-          }
-
-          case "Warn": {
-            const check = checks.get(evt.check_id);
-            if (check) {
-              checks.set(evt.check_id, {
-                ...check,
-                messages: [
-                  ...check.messages,
-                  { type: "warn", message: evt.message },
-                ],
-              });
-            }
-            return { ...prev, checks };
-          }
-
-          case "CheckCompleted": {
-            const check = checks.get(evt.check_id);
-            if (check) {
-              checks.set(evt.check_id, {
-                ...check,
-                status: evt.status as CheckState["status"],
-                detail: evt.detail,
-              });
-            }
-            return { ...prev, checks };
-          }
-
-          case "Prompt": {
-            const check = checks.get(evt.check_id);
-            if (check) {
-              checks.set(evt.check_id, {
-                ...check,
-                messages: [
-                  ...check.messages,
-                  { type: "warn", message: evt.message },
-                ],
-              });
-            }
-            return { ...prev, checks };
-          }
-
-          case "Error": {
-            const check = checks.get(evt.check_id);
-            if (check) {
-              checks.set(evt.check_id, {
-                ...check,
-                messages: [
-                  ...check.messages,
-                  { type: "error", message: evt.message },
-                ],
-              });
-            }
-            return { ...prev, checks };
-          }
-
-          case "ScanCompleted": {
-            return {
-              ...prev,
-              running: false,
-              summary: {
-                passed: evt.passed,
-                failed: evt.failed,
-                skipped: evt.skipped,
-                total: evt.total,
-                score: evt.score,
-                grade: evt.grade,
-              },
-            };
-          }
-
-          default:
-            return prev;
-        }
-      });
+      setState((prev) => applyEvent(prev, evt));
     };
 
     try {
-      await invoke("start_scan", { onEvent });
+      await invoke("start_scan", { selection, onEvent });
     } catch (err) {
       setState((prev) => ({
         ...prev,
@@ -140,16 +268,55 @@ export function useScan() {
         summary: prev.summary ?? {
           passed: 0,
           failed: 0,
+          fixed: 0,
           skipped: 0,
+          waived: 0,
+          errors: 0,
           total: 0,
           score: 0,
           grade: "F",
+          phases: [],
+          critical_failures: [],
         },
       }));
       console.error("Scan failed:", err);
     }
   }, []);
 
+  const cancelScan = useCallback(async () => {
+    try {
+      await invoke("cancel_scan");
+    } catch (err) {
+      console.error("Cancel failed:", err);
+    }
+  }, []);
+
+  /** Run a check's remediation, then re-run the check to verify it. */
+  const runRemediation = useCallback(
+    async (checkId: string, remediationId: string) => {
+      const onEvent = new Channel<ScanEvent>();
+      onEvent.onmessage = (evt: ScanEvent) => {
+        setState((prev) => applyEvent(prev, evt));
+      };
+
+      try {
+        await invoke("run_remediation", { checkId, remediationId, onEvent });
+      } catch (err) {
+        setState((prev) =>
+          applyEvent(prev, {
+            event: "RemediationCompleted",
+            check_id: checkId,
+            remediation_id: remediationId,
+            status: "FAIL",
+            detail: String(err),
+          }),
+        );
+        console.error("Remediation failed:", err);
+      }
+    },
+    [],
+  );
+
   const getChecksForPhase = useCallback(
     (phaseId: string): CheckState[] => {
       return Array.from(state.checks.values()).filter(
@@ -159,5 +326,12 @@ export function useScan() {
     [state.checks],
   );
 
-  return { state, startScan, getChecksForPhase };
+  return {
+    state,
+    profiles,
+    startScan,
+    cancelScan,
+    runRemediation,
+    getChecksForPhase,
+  };
 }
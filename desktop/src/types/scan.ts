@@ -7,6 +7,106 @@ export interface CheckMeta {
   description: string;
   requires_sudo: boolean;
   order: number;
+  exclusive: boolean;
+  timeout_secs: number | null;
+  tags: string[];
+  severity: Severity;
+  weight: number | null;
+  requires: string[];
+  after: string[];
+  /** Compliance controls by framework id, e.g. { soc2: ["CC6.1"] } */
+  controls: Record<string, ControlMapping>;
+  origin: CheckOrigin;
+}
+
+/**
+ * Mirrors Rust ControlMapping: control ids settled by the whole check, or
+ * control id → the finding that settles it
+ */
+export type ControlMapping = string[] | Record<string, string>;
+
+/** Mirrors Rust CheckOrigin: which check root a check was loaded from */
+export interface CheckOrigin {
+  source: "bundled" | "user" | "env";
+  dir: string;
+  shadows: string[];
+}
+
+export type Severity = "critical" | "high" | "medium" | "low";
+
+/** Mirrors Rust PlatformInfo from types.rs */
+export interface PlatformInfo {
+  os: string;
+  arch: string;
+  distro: string | null;
+  distro_family: string | null;
+  distro_version: string | null;
+  distro_name: string | null;
+}
+
+/** Mirrors Rust HostFacts from types.rs */
+export interface HostFacts {
+  os_version: string | null;
+  tools: string[];
+  docker: boolean;
+  docker_running: boolean;
+  openclaw: boolean;
+  openclaw_install: string | null;
+  openclaw_running: boolean;
+  openclaw_version: string | null;
+  listening_ports: number[];
+}
+
+/** Mirrors Rust CheckFilter from types.rs; empty lists don't filter */
+export interface CheckFilter {
+  checks?: string[];
+  phases?: string[];
+  tags?: string[];
+  severities?: Severity[];
+  platforms?: string[];
+  exclude?: string[];
+}
+
+/** Mirrors Rust ProfileDef from types.rs */
+export interface ProfileDef extends CheckFilter {
+  id: string;
+  label: string;
+  description: string;
+}
+
+/** Mirrors Rust ScanSelection from types.rs */
+export interface ScanSelection extends CheckFilter {
+  profile?: string;
+}
+
+/** Mirrors ControlCoverage.status in types.rs */
+export type ControlStatus =
+  | "PASS"
+  | "FAIL"
+  | "WAIVED"
+  | "NOT_ASSESSED"
+  | "UNMAPPED";
+
+/** Mirrors Rust ControlCoverage from types.rs */
+export interface ControlCoverage {
+  id: string;
+  title: string;
+  status: ControlStatus;
+  /** "check_id", or "check_id/finding" when one finding settles it */
+  checks: string[];
+}
+
+/** Mirrors Rust FrameworkCoverage from types.rs */
+export interface FrameworkCoverage {
+  framework: string;
+  label: string;
+  controls: ControlCoverage[];
+  mapped: number;
+  passed: number;
+  failed: number;
+  waived: number;
+  not_assessed: number;
+  unmapped: number;
 }
 
 /** Mirrors Rust PhaseInfo from types.rs */
@@ -14,21 +114,51 @@ export interface PhaseInfo {
   id: string;
   label: string;
   order: number;
+  description: string;
 }
 
 /** Discriminated union matching Rust ScanEvent (serde tag = "event") */
 export type ScanEvent =
-  | { event: "ScanStarted"; checks: CheckMeta[]; phases: PhaseInfo[] }
+  | {
+      event: "ScanStarted";
+      platform: PlatformInfo;
+      facts: HostFacts;
+      checks: CheckMeta[];
+      phases: PhaseInfo[];
+    }
+  | { event: "CatalogWarning"; path: string; message: string }
   | { event: "PhaseStarted"; phase_id: string; phase_label: string }
   | { event: "CheckStarted"; check_id: string }
   | { event: "Info"; check_id: string; message: string }
   | { event: "Warn"; check_id: string; message: string }
+  | {
+      event: "Finding";
+      check_id: string;
+      name: string;
+      status: string;
+      detail: string;
+    }
+  | {
+      event: "Diagnostic";
+      check_id: string;
+      exit_code: number | null;
+      signal: number | null;
+      stderr: string;
+      stderr_truncated: boolean;
+    }
+  | {
+      event: "ProtocolViolation";
+      check_id: string;
+      line: number;
+      message: string;
+    }
   | {
       event: "CheckCompleted";
       check_id: string;
       check_name: string;
       status: string;
       detail: string;
+      evidence: Evidence[];
     }
   | {
       event: "Prompt";
@@ -42,30 +172,221 @@ export type ScanEvent =
       event: "ScanCompleted";
       passed: number;
       failed: number;
+      fixed: number;
       skipped: number;
+      waived: number;
+      errors: number;
       total: number;
       score: number;
       grade: string;
+      phases: PhaseScore[];
+      critical_failures: string[];
+    }
+  | { event: "ScanCancelled"; completed: number; total: number }
+  | {
+      event: "GatesEvaluated";
+      passed: boolean;
+      violations: { gate: string; message: string }[];
+    }
+  | { event: "RemediationStarted"; check_id: string; remediation_id: string }
+  | {
+      event: "RemediationCompleted";
+      check_id: string;
+      remediation_id: string;
+      status: string;
+      detail: string;
     }
   | { event: "Error"; check_id: string; message: string };
 
+/** Mirrors Rust CheckResult from types.rs */
+export interface CheckResult {
+  check_id: string;
+  check_name: string;
+  phase: string;
+  status: string;
+  detail: string;
+  messages: { level: "info" | "warn" | "error"; message: string }[];
+  duration_ms: number;
+  remediation: RemediationHint | null;
+  severity: Severity;
+  weight: number;
+  findings: Finding[];
+  evidence: Evidence[];
+  diagnostics: ScriptDiagnostics | null;
+  protocol_violations: ProtocolViolation[];
+  waivers: Waiver[];
+}
+
+/** Mirrors Rust Waiver from types.rs: an accepted risk */
+export interface Waiver {
+  check: string;
+  /** A finding within the check; null waives the whole check */
+  finding: string | null;
+  justification: string;
+  owner: string;
+  /** YYYY-MM-DD */
+  expires: string;
+}
+
+/** Mirrors Rust ProtocolViolation from types.rs (strict mode only) */
+export interface ProtocolViolation {
+  line: number;
+  message: string;
+}
+
+/** Mirrors Rust ScriptDiagnostics from types.rs */
+export interface ScriptDiagnostics {
+  exit_code: number | null;
+  signal: number | null;
+  stderr: string;
+  stderr_truncated: boolean;
+}
+
+/** Mirrors Rust Finding from types.rs: one status line of a check */
+export interface Finding {
+  name: string;
+  status: string;
+  detail: string;
+}
+
+/** Mirrors Rust Evidence from types.rs: what a check observed */
+export interface Evidence {
+  finding: string | null;
+  key: string;
+  observed: string;
+  expected: string | null;
+  source: string | null;
+}
+
+/** Mirrors Rust RemediationHint from types.rs */
+export interface RemediationHint {
+  remediation_id: string;
+  message: string;
+}
+
+/** Mirrors Rust ScanRecord from types.rs (a saved scan) */
+export interface ScanRecord {
+  id: string;
+  started_at: number;
+  finished_at: number;
+  hostname: string;
+  platform: PlatformInfo;
+  summary: ScanSummaryData;
+  checks: CheckResult[];
+  catalog: CheckMeta[];
+  phases: PhaseInfo[];
+}
+
+/** Mirrors Rust ScanListing from types.rs (history list entry) */
+export interface ScanListing {
+  id: string;
+  started_at: number;
+  finished_at: number;
+  hostname: string;
+  summary: ScanSummaryData;
+}
+
+/** Mirrors Rust CheckChange from types.rs */
+export interface CheckChange {
+  check_id: string;
+  check_name: string;
+  from_status: string | null;
+  to_status: string | null;
+  from_detail: string | null;
+  to_detail: string | null;
+}
+
+/** Mirrors Rust ScanDiff from types.rs (drift between two scans) */
+export interface ScanDiff {
+  from_id: string;
+  to_id: string;
+  score_from: number;
+  score_to: number;
+  score_delta: number;
+  grade_from: string;
+  grade_to: string;
+  regressions: CheckChange[];
+  fixes: CheckChange[];
+  changed: CheckChange[];
+  added: CheckChange[];
+  removed: CheckChange[];
+  unchanged: number;
+  new_findings: FindingChange[];
+  resolved_findings: FindingChange[];
+  changed_findings: FindingChange[];
+}
+
+/** Mirrors Rust FindingChange from types.rs */
+export interface FindingChange {
+  check_id: string;
+  check_name: string;
+  finding: string;
+  from_status: string | null;
+  to_status: string | null;
+  from_detail: string | null;
+  to_detail: string | null;
+}
+
 /** Frontend state for a single check */
-export type CheckStatus = "pending" | "running" | "PASS" | "FAIL" | "SKIPPED";
+export type CheckStatus =
+  | "pending"
+  | "running"
+  | "PASS"
+  | "FIXED"
+  | "FAIL"
+  | "SKIPPED"
+  | "WAIVED"
+  | "TIMEOUT"
+  | "ERROR";
 
 export interface CheckState {
   meta: CheckMeta;
   status: CheckStatus;
   detail: string;
   messages: { type: "info" | "warn" | "error"; message: string }[];
+  findings: Finding[];
+  evidence: Evidence[];
+  /** The fix the check offered, if it prompted for one */
+  remediation?: RemediationHint;
+  /** A remediation for this check is running */
+  remediating?: boolean;
 }
 
 export interface ScanSummaryData {
   passed: number;
   failed: number;
+  fixed: number;
   skipped: number;
+  waived: number;
+  errors: number;
   total: number;
   score: number;
   grade: string;
+  phases: PhaseScore[];
+  critical_failures: string[];
+}
+
+/** Mirrors Rust GradingPolicy from types.rs (checks/grading.toml) */
+export interface GradingPolicy {
+  fixed: "pass" | "fail" | "ignore";
+  skipped: "pass" | "fail" | "ignore";
+  timeout: "pass" | "fail" | "ignore";
+  error: "pass" | "fail" | "ignore";
+  critical_failure_cap: string | null;
+  /** Minimum score for each grade A–D; anything lower is an F */
+  thresholds: Record<string, number>;
+}
+
+/** Mirrors Rust PhaseScore from types.rs */
+export interface PhaseScore {
+  phase: string;
+  passed: number;
+  failed: number;
+  skipped: number;
+  waived: number;
+  errors: number;
+  score: number;
+  grade: string;
 }
 
 export interface ScanState {
@@ -73,6 +394,30 @@ export interface ScanState {
   checks: Map<string, CheckState>;
   phases: PhaseInfo[];
   summary: ScanSummaryData | null;
+  /** The last scan was stopped before it finished */
+  cancelled: boolean;
+  /** Checks left out because their check.toml is broken */
+  catalogWarnings: CatalogWarning[];
+}
+
+/** A check.toml problem reported by a CatalogWarning event */
+export interface CatalogWarning {
+  path: string;
+  message: string;
+}
+
+/** A problem with a check's definition, matching Rust CatalogProblem */
+export interface CatalogProblem {
+  path: string;
+  check_id: string | null;
+  message: string;
+}
+
+/** What get_catalog returns: the checks for this host and the check.toml
+ * files left out of them */
+export interface CatalogListing {
+  checks: CheckMeta[];
+  problems: CatalogProblem[];
 }
 
 /** Deploy types matching Rust deploy.rs */
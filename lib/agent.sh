@@ -311,13 +311,9 @@ agent_run() {
         skipped_count=$(grep "^Accepted risks:" "$report_file" | head -1 | sed 's/Accepted risks: *//')
     fi
 
-    # Calculate grade
-    if [ "$score" -ge 95 ]; then grade="A"
-    elif [ "$score" -ge 85 ]; then grade="B"
-    elif [ "$score" -ge 70 ]; then grade="C"
-    elif [ "$score" -ge 50 ]; then grade="D"
-    else grade="F"
-    fi
+    # The scan records its grade; older reports only have the score
+    grade=$(grep "^Grade:" "$report_file" | head -1 | sed 's/Grade: *//')
+    [ -z "$grade" ] && grade=$(compute_grade "$score")
 
     # Parse individual checks from report (STATUS | CHECK_NAME | DETAIL)
     local checks_json="["
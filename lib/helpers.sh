@@ -7,6 +7,21 @@
 # --- JSON Output Helpers (used by check.sh / remediate.sh scripts) ----------
 # These emit structured JSON lines to stdout. Both the CLI orchestrator and
 # the Tauri backend parse this output.
+#
+# Output follows check protocol v1: a {"protocol":1} header, then one object
+# per line tagged by "type": status, info, warn, prompt, evidence or metric.
+# Anything else belongs on stderr.
+
+CLAWKEEPER_PROTOCOL_VERSION=1
+
+# Print the protocol header before a script's first line of output. Each
+# check runs in its own process (a subshell in the bundle), so each gets one.
+_emit_header() {
+    if [ -z "${_CLAWKEEPER_HEADER_SENT:-}" ]; then
+        printf '{"protocol":%s}\n' "$CLAWKEEPER_PROTOCOL_VERSION"
+        _CLAWKEEPER_HEADER_SENT=1
+    fi
+}
 
 # Escape a string for safe JSON embedding
 _json_escape() {
@@ -21,34 +36,65 @@ _json_escape() {
 emit_pass() {
     local detail="$1"
     local check_name="${2:-}"
-    printf '{"status":"PASS","check_name":"%s","detail":"%s"}\n' \
+    _emit_header
+    printf '{"type":"status","status":"PASS","check_name":"%s","detail":"%s"}\n' \
         "$(_json_escape "$check_name")" "$(_json_escape "$detail")"
 }
 
 emit_fail() {
     local detail="$1"
     local check_name="${2:-}"
-    printf '{"status":"FAIL","check_name":"%s","detail":"%s"}\n' \
+    _emit_header
+    printf '{"type":"status","status":"FAIL","check_name":"%s","detail":"%s"}\n' \
         "$(_json_escape "$check_name")" "$(_json_escape "$detail")"
 }
 
 emit_info() {
     local message="$1"
+    _emit_header
     printf '{"type":"info","message":"%s"}\n' "$(_json_escape "$message")"
 }
 
 emit_warn() {
     local message="$1"
+    _emit_header
     printf '{"type":"warn","message":"%s"}\n' "$(_json_escape "$message")"
 }
 
 emit_skipped() {
     local detail="$1"
     local check_name="${2:-}"
-    printf '{"status":"SKIPPED","check_name":"%s","detail":"%s"}\n' \
+    _emit_header
+    printf '{"type":"status","status":"SKIPPED","check_name":"%s","detail":"%s"}\n' \
         "$(_json_escape "$check_name")" "$(_json_escape "$detail")"
 }
 
+# Record what a check observed, e.g.
+#   emit_evidence "PermitRootLogin" "yes" "no" "/etc/ssh/sshd_config"
+# Evidence supports the next status line; expected and source are optional.
+emit_evidence() {
+    local key="$1"
+    local observed="$2"
+    local expected="${3:-}"
+    local source="${4:-}"
+    _emit_header
+    printf '{"type":"evidence","key":"%s","observed":"%s","expected":"%s","source":"%s"}\n' \
+        "$(_json_escape "$key")" \
+        "$(_json_escape "$observed")" \
+        "$(_json_escape "$expected")" \
+        "$(_json_escape "$source")"
+}
+
+# Record a measured value. value must be a number; unit is optional.
+emit_metric() {
+    local name="$1"
+    local value="$2"
+    local unit="${3:-}"
+    _emit_header
+    printf '{"type":"metric","name":"%s","value":%s,"unit":"%s"}\n' \
+        "$(_json_escape "$name")" "$value" "$(_json_escape "$unit")"
+}
+
 # Emit a remediation prompt. The orchestrator will ask the user and call
 # remediate.sh if accepted. fail_detail is used when the user declines in
 # scan mode; skip_detail when they decline in setup mode.
@@ -57,13 +103,23 @@ emit_prompt() {
     local remediation_id="$2"
     local fail_detail="${3:-}"
     local skip_detail="${4:-}"
-    printf '{"action":"prompt","message":"%s","remediation_id":"%s","fail_detail":"%s","skip_detail":"%s"}\n' \
+    _emit_header
+    printf '{"type":"prompt","message":"%s","remediation_id":"%s","fail_detail":"%s","skip_detail":"%s"}\n' \
         "$(_json_escape "$message")" \
         "$(_json_escape "$remediation_id")" \
         "$(_json_escape "$fail_detail")" \
         "$(_json_escape "$skip_detail")"
 }
 
+# --- Host Facts (used by check.sh scripts) ---------------------------------
+# The desktop app and clawkeeper-scan probe the host once per scan and pass
+# what they found as CLAWKEEPER_FACT_* variables (OPENCLAW_VERSION,
+# DOCKER_RUNNING, ...). The bash CLI doesn't, so a check should fall back to
+# detecting for itself when have_facts fails.
+have_facts() {
+    [ "${CLAWKEEPER_FACTS:-}" = "1" ]
+}
+
 # --- JSON Parsing Helper (used by the orchestrator) -------------------------
 # Extract a value from a simple flat JSON object. No jq dependency.
 # Usage: _jval '{"key":"value"}' "key"  → prints "value"
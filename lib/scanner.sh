@@ -42,10 +42,13 @@ run_check() {
     local remediate_func="__remediate_${check_id}"
 
     # Read metadata from the __meta_<id> function (set by bundle) or fallback
-    local check_name="$check_id"
+    local check_name="$check_id" check_severity="medium" check_weight=""
     if type "__meta_${check_id}" &>/dev/null; then
         check_name=$("__meta_${check_id}" name)
+        check_severity=$("__meta_${check_id}" severity)
+        check_weight=$("__meta_${check_id}" weight)
     fi
+    local first_line=${#REPORT_LINES[@]}
 
     local mode="scan"
     [ "$SCAN_ONLY" != true ] && mode="setup"
@@ -57,7 +60,7 @@ run_check() {
     # In compact mode, check if output contains any prompts
     _COMPACT_THIS_CHECK=false
     if [ "$COMPACT_OUTPUT" = true ]; then
-        if ! echo "$json_output" | grep -q '"action":"prompt"'; then
+        if ! echo "$json_output" | grep -qE '"(type|action)":"prompt"'; then
             _COMPACT_THIS_CHECK=true
         else
             # Flush buffered compact output before verbose check
@@ -83,12 +86,9 @@ run_check() {
         j_check=$(_jval "$line" "check_name")
         [ -z "$j_check" ] && j_check="$check_name"
 
-        if [ -n "$j_type" ]; then
-            case "$j_type" in
-                info) info "$j_message" ;;
-                warn) warn "$j_message" ;;
-            esac
-        elif [ "$j_action" = "prompt" ]; then
+        # Protocol v1 tags every line with "type"; older output used
+        # "action" for prompts and no tag on status lines
+        if [ "$j_type" = "prompt" ] || [ "$j_action" = "prompt" ]; then
             if ask_yn "$j_message"; then
                 # Run remediation
                 local rem_output
@@ -103,17 +103,17 @@ run_check() {
                     r_check=$(_jval "$rem_line" "check_name")
                     [ -z "$r_check" ] && r_check="$j_check"
 
-                    if [ -n "$r_type" ]; then
-                        case "$r_type" in
-                            info) info "$r_message" ;;
-                            warn) warn "$r_message" ;;
-                        esac
-                    elif [ -n "$r_status" ]; then
+                    if [ -n "$r_status" ]; then
                         case "$r_status" in
                             PASS)  fixed "$r_detail" "$r_check" ;;
                             FIXED) fixed "$r_detail" "$r_check" ;;
                             FAIL)  fail "$r_detail" "$r_check" ;;
                         esac
+                    else
+                        case "$r_type" in
+                            info) info "$r_message" ;;
+                            warn) warn "$r_message" ;;
+                        esac
                     fi
                 done <<< "$rem_output"
             else
@@ -133,8 +133,42 @@ run_check() {
                 FIXED)   fixed "$j_detail" "$j_check" ;;
                 SKIPPED) skipped "$j_detail" "$j_check" ;;
             esac
+        else
+            case "$j_type" in
+                info) info "$j_message" ;;
+                warn) warn "$j_message" ;;
+            esac
         fi
     done <<< "$json_output"
+
+    _record_check_score "$first_line" "$check_severity" "$check_weight"
+}
+
+# Folds the report lines a check added (from index $1) into one status, as
+# the desktop scanner does: FAIL > FIXED > PASS > SKIPPED, ERROR if it
+# reported nothing. Appends "status|weight|severity" to CHECK_SCORES.
+_record_check_score() {
+    local first="$1" severity="$2" weight="$3" status="" i line_status
+    for ((i = first; i < ${#REPORT_LINES[@]}; i++)); do
+        line_status="${REPORT_LINES[$i]%%|*}"
+        case "$line_status" in
+            FAIL) status="FAIL" ;;
+            FIXED) [ "$status" != "FAIL" ] && status="FIXED" ;;
+            PASS) [ "$status" != "FAIL" ] && [ "$status" != "FIXED" ] && status="PASS" ;;
+            SKIPPED) [ -z "$status" ] && status="SKIPPED" ;;
+        esac
+    done
+    [ -z "$status" ] && status="ERROR"
+
+    if [ -z "$weight" ]; then
+        case "$severity" in
+            critical) weight=10 ;;
+            high) weight=5 ;;
+            low) weight=1 ;;
+            *) weight=3 ;;
+        esac
+    fi
+    CHECK_SCORES+=("$status|$weight|$severity")
 }
 
 # --- Admin / Install Capability Check ---------------------------------------
@@ -315,6 +349,80 @@ detect_openclaw_installed() {
     fi
 }
 
+# --- Grading ----------------------------------------------------------------
+# Thresholds and status handling come from checks/grading.toml, which
+# bundle.sh inlines as __grading_policy. The defaults below match that file.
+
+_grading_value() {
+    local key="$1" default="$2" value=""
+    if type __grading_policy &>/dev/null; then
+        value=$(__grading_policy | sed -n "s/^${key} *= *\"\{0,1\}\([A-Za-z0-9.]*\).*/\1/p" | head -1)
+    fi
+    echo "${value:-$default}"
+}
+
+# Weighted percentage of scored checks passing, from CHECK_SCORES. FIXED,
+# SKIPPED and ERROR count as "pass", "fail" or "ignore" per the policy; a
+# scan with nothing scored is 100.
+_weighted_score() {
+    printf '%s\n' ${CHECK_SCORES[@]+"${CHECK_SCORES[@]}"} | awk -F'|' \
+        -v fixed="$(_grading_value fixed pass)" \
+        -v skipped="$(_grading_value skipped ignore)" \
+        -v error="$(_grading_value error ignore)" '
+        NF >= 2 {
+            counts = "fail"
+            if ($1 == "PASS") counts = "pass"
+            else if ($1 == "FIXED") counts = fixed
+            else if ($1 == "SKIPPED") counts = skipped
+            else if ($1 == "ERROR") counts = error
+            if (counts == "pass") { passing += $2; scored += $2 }
+            else if (counts == "fail") scored += $2
+        }
+        END { if (scored > 0) printf "%.4f\n", passing * 100 / scored; else print 100 }'
+}
+
+# The weighted score rounded to a whole percentage.
+compute_score() {
+    printf '%.0f\n' "$(_weighted_score)"
+}
+
+# Letter grade: the best grade whose threshold the score meets (by default
+# this scan's weighted score), capped at critical_failure_cap while a
+# critical check is failing.
+compute_grade() {
+    local score="${1:-$(_weighted_score)}" grade="F" candidate default min
+    for candidate in A B C D; do
+        case "$candidate" in
+            A) default=95 ;;
+            B) default=85 ;;
+            C) default=70 ;;
+            D) default=50 ;;
+        esac
+        min=$(_grading_value "$candidate" "$default")
+        if awk -v s="$score" -v m="$min" 'BEGIN { exit !(s >= m) }'; then
+            grade="$candidate"
+            break
+        fi
+    done
+
+    local cap="C"
+    type __grading_policy &>/dev/null && cap=$(_grading_value critical_failure_cap "")
+    if [ -n "$cap" ] && [[ "$grade" < "$cap" ]] && _has_critical_failure; then
+        grade="$cap"
+    fi
+    echo "$grade"
+}
+
+_has_critical_failure() {
+    local entry
+    for entry in ${CHECK_SCORES[@]+"${CHECK_SCORES[@]}"}; do
+        case "$entry" in
+            FAIL\|*\|critical|ERROR\|*\|critical) return 0 ;;
+        esac
+    done
+    return 1
+}
+
 # --- Report -----------------------------------------------------------------
 
 print_report() {
@@ -326,32 +434,21 @@ print_report() {
     fi
     echo ""
 
-    # Calculate grade — SKIPPED = accepted risk (not penalized like FAIL)
-    local scored_checks=$((PASS + FAIL + FIXED))
-    local effective_pass=$((PASS + FIXED))
-    local score=0
-
-    if [ "$scored_checks" -gt 0 ]; then
-        score=$(( (effective_pass * 100) / scored_checks ))
-    fi
+    local score grade
+    score=$(compute_score)
+    grade=$(compute_grade)
 
-    local grade="F"
     local grade_color="$RED"
     local gum_grade_color="$GUM_RED"
-    if [ "$score" -ge 95 ]; then
-        grade="A"; grade_color="$GREEN"; gum_grade_color="$GUM_GREEN"
-    elif [ "$score" -ge 85 ]; then
-        grade="B"; grade_color="$GREEN"; gum_grade_color="$GUM_GREEN"
-    elif [ "$score" -ge 70 ]; then
-        grade="C"; grade_color="$YELLOW"; gum_grade_color="$GUM_YELLOW"
-    elif [ "$score" -ge 50 ]; then
-        grade="D"; grade_color="$YELLOW"; gum_grade_color="$GUM_YELLOW"
-    fi
+    case "$grade" in
+        A|B) grade_color="$GREEN"; gum_grade_color="$GUM_GREEN" ;;
+        C|D) grade_color="$YELLOW"; gum_grade_color="$GUM_YELLOW" ;;
+    esac
 
     if [ "$HAS_GUM" = true ]; then
-        echo "  $(gum style --bold "Security Grade:") $(gum style --bold --foreground "$gum_grade_color" "$grade") $(gum style --foreground "$GUM_DIM" "(${score}% of checks passing)")"
+        echo "  $(gum style --bold "Security Grade:") $(gum style --bold --foreground "$gum_grade_color" "$grade") $(gum style --foreground "$GUM_DIM" "(${score}% weighted by severity)")"
     else
-        echo -e "  ${BOLD}Security Grade: ${grade_color}${BOLD}$grade${RESET} ${DIM}(${score}% of checks passing)${RESET}"
+        echo -e "  ${BOLD}Security Grade: ${grade_color}${BOLD}$grade${RESET} ${DIM}(${score}% weighted by severity)${RESET}"
     fi
     echo ""
 
@@ -486,12 +583,9 @@ save_report() {
         os_info="$PLATFORM"
     fi
 
-    local scored_checks=$((PASS + FAIL + FIXED))
-    local effective_pass=$((PASS + FIXED))
-    local score=0
-    if [ "$scored_checks" -gt 0 ]; then
-        score=$(( (effective_pass * 100) / scored_checks ))
-    fi
+    local score grade
+    score=$(compute_score)
+    grade=$(compute_grade)
 
     {
         echo "CLAW Keeper Security Report"
@@ -503,6 +597,7 @@ save_report() {
         echo "---"
         echo ""
         echo "Score: ${score}%"
+        echo "Grade: $grade"
         echo "Passed: $PASS"
         echo "Fixed: $FIXED"
         echo "Failed: $FAIL"
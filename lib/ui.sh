@@ -51,6 +51,7 @@ INTERACTIVE=true
 SCAN_ONLY=false
 REPORT_FILE=""
 REPORT_LINES=()
+CHECK_SCORES=()
 HOMEBREW_FAILED=false
 CAN_INSTALL_SOFTWARE=true
 
@@ -9,12 +9,13 @@
 # Concatenation order:
 #   1. helpers.sh    — JSON output helpers
 #   2. ui.sh         — colors, formatting, output helpers
-#   3. scanner.sh    — check runner, detection, reporting
-#   4. agent.sh      — SaaS agent management
-#   5. deploy.sh     — OpenClaw deployment (native + Docker)
-#   6. uninstall.sh  — secure removal
-#   7. checks/       — extracted security checks
-#   8. orchestrator.sh — CLI entrypoint (main, usage, platform detect)
+#   3. grading.toml  — grading policy, wrapped as __grading_policy()
+#   4. scanner.sh    — check runner, detection, reporting
+#   5. agent.sh      — SaaS agent management
+#   6. deploy.sh     — OpenClaw deployment (native + Docker)
+#   7. uninstall.sh  — secure removal
+#   8. checks/       — extracted security checks
+#   9. orchestrator.sh — CLI entrypoint (main, usage, platform detect)
 # ============================================================================
 
 set -euo pipefail
@@ -58,6 +59,17 @@ HEADER
 
     cat "$LIB_DIR/ui.sh"
 
+    echo ""
+    echo "# === Grading policy (from checks/grading.toml) ==========================="
+    echo ""
+
+    # --- Inline the grading policy for compute_score / compute_grade ---
+    echo "__grading_policy() {"
+    echo "    cat <<'POLICY'"
+    cat "$CHECKS_DIR/grading.toml"
+    echo "POLICY"
+    echo "}"
+
     echo ""
     echo "# === Scanner (check runner, detection, reporting) ========================"
     echo ""
@@ -107,9 +119,13 @@ HEADER
 
         # Read metadata from TOML (simple key=value parser)
         local_name="$local_id"
+        local_severity=""
+        local_weight=""
         if [ -f "$toml_file" ]; then
             local_name=$(grep '^name *=' "$toml_file" | sed 's/^name *= *"\(.*\)"/\1/' | head -1)
             [ -z "$local_name" ] && local_name="$local_id"
+            local_severity=$(grep '^severity *=' "$toml_file" | sed 's/^severity *= *"\(.*\)".*/\1/' | head -1 || true)
+            local_weight=$(grep '^weight *=' "$toml_file" | sed 's/^weight *= *\([0-9.]*\).*/\1/' | head -1 || true)
         fi
 
         echo "# --- Check: $local_id ---"
@@ -119,8 +135,10 @@ HEADER
         cat <<METAFUNC
 __meta_${local_id}() {
     case "\$1" in
-        name) echo "$local_name" ;;
-        id)   echo "$local_id" ;;
+        name)     echo "$local_name" ;;
+        id)       echo "$local_id" ;;
+        severity) echo "${local_severity:-medium}" ;;
+        weight)   echo "$local_weight" ;;
     esac
 }
 METAFUNC